@@ -0,0 +1,108 @@
+//! Derive macros for Gamma-VK
+//!
+//! Enable the `derive` feature of `gamma-vk` to use these through its re-exports
+//! instead of depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{DeriveInput, parse_macro_input, parse_quote, spanned::Spanned};
+
+/// Derive `gamma_vk::ecs::Component` with no lifecycle hooks
+///
+/// Components must be `Send + Sync + 'static`. Types with lifetime parameters are
+/// rejected outright; generic type parameters are bounded accordingly.
+///
+/// # Examples
+///
+/// ```ignore
+/// use gamma_vk::ecs::Component;
+///
+/// #[derive(Component)]
+/// struct Position {
+///     x: f32,
+///     y: f32,
+/// }
+/// ```
+#[proc_macro_derive(Component)]
+pub fn derive_component(input: TokenStream) -> TokenStream {
+    expand_component(parse_macro_input!(input as DeriveInput)).into()
+}
+
+fn expand_component(mut input: DeriveInput) -> TokenStream2 {
+    if let Some(lifetime) = input.generics.lifetimes().next() {
+        return syn::Error::new(
+            lifetime.span(),
+            "Component cannot be derived for types with lifetime parameters; \
+             components must be 'static",
+        )
+        .to_compile_error();
+    }
+
+    let type_params: Vec<_> = input
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = input.generics.make_where_clause();
+    for param in type_params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: ::core::marker::Send + ::core::marker::Sync + 'static));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
+        impl #impl_generics ::gamma_vk::ecs::Component for #name #type_generics #where_clause {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(input: TokenStream2) -> String {
+        expand_component(syn::parse2(input).unwrap()).to_string()
+    }
+
+    #[test]
+    fn test_derive_emits_empty_component_impl() {
+        let output = expand(quote! {
+            struct Position {
+                x: f32,
+                y: f32,
+            }
+        });
+
+        assert_eq!(
+            output,
+            quote! { impl ::gamma_vk::ecs::Component for Position {} }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_derive_bounds_generic_parameters() {
+        let output = expand(quote! {
+            struct Tagged<T> {
+                value: T,
+            }
+        });
+
+        assert!(output.contains("impl < T > :: gamma_vk :: ecs :: Component for Tagged < T >"));
+        assert!(output.contains("T : :: core :: marker :: Send"));
+        assert!(output.contains("'static"));
+    }
+
+    #[test]
+    fn test_derive_rejects_lifetime_parameters() {
+        let output = expand(quote! {
+            struct Borrowed<'a> {
+                name: &'a str,
+            }
+        });
+
+        assert!(output.starts_with(":: core :: compile_error !"));
+        assert!(output.contains("components must be 'static"));
+    }
+}
@@ -0,0 +1,11 @@
+#version 450
+
+// Fullscreen triangle generated from gl_VertexIndex; draw with 3 vertices and
+// no vertex buffer. The triangle overshoots the viewport and is clipped.
+
+layout(location = 0) out vec2 uv;
+
+void main() {
+    uv = vec2((gl_VertexIndex << 1) & 2, gl_VertexIndex & 2);
+    gl_Position = vec4(uv * 2.0 - 1.0, 0.0, 1.0);
+}
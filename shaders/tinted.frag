@@ -0,0 +1,16 @@
+#version 450
+
+// 192 bytes of push constants: within desktop limits, over the 128-byte minimum
+layout(push_constant) uniform Transforms {
+    mat4 model;
+    mat4 view;
+    mat4 projection;
+} transforms;
+
+layout(location = 0) in vec2 texCoord;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    outColor = transforms.projection * transforms.view * transforms.model * vec4(texCoord, 0.0, 1.0);
+}
@@ -3,14 +3,54 @@
 //! This module provides RAII-managed buffer types with automatic resource cleanup
 //! and type-safe buffer usage patterns.
 
-use std::sync::Arc;
+use bytemuck::Pod;
+use std::{marker::PhantomData, ops::Range, ptr::NonNull, sync::Arc};
 use vulkano::{
-    buffer::{Buffer as VulkanoBuffer, BufferCreateInfo, BufferUsage, Subbuffer},
-    device::Device,
-    memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
+    Validated, VulkanError,
+    buffer::{
+        AllocateBufferError, Buffer as VulkanoBuffer, BufferCreateInfo, BufferMemory, BufferUsage,
+        IndexBuffer as VulkanoIndexBuffer, IndexType, Subbuffer,
+    },
+    command_buffer::BufferCopy,
+    device::{Device, DeviceOwned},
+    memory::{
+        MappedMemoryRange, MemoryPropertyFlags,
+        allocator::{
+            AllocationCreateInfo, MemoryAllocatorError, MemoryTypeFilter, StandardMemoryAllocator,
+            align_down, align_up,
+        },
+    },
+    sync::HostAccessError,
 };
 
-use crate::{GammaVkError, Result};
+use crate::{CommandRecorder, GammaVkError, Result, VulkanContext};
+
+/// How the CPU accesses a host-visible buffer
+///
+/// This selects the memory type: sequential writes suit write-combined memory,
+/// while reads through write-combined memory are extremely slow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AccessPattern {
+    /// Written once in order from the CPU, such as upload and staging buffers
+    #[default]
+    SequentialWrite,
+    /// Read back or updated in arbitrary order from the CPU
+    RandomAccess,
+}
+
+impl AccessPattern {
+    /// Get the memory type filter for this access pattern
+    pub fn memory_type_filter(self) -> MemoryTypeFilter {
+        match self {
+            Self::SequentialWrite => {
+                MemoryTypeFilter::PREFER_HOST | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE
+            }
+            Self::RandomAccess => {
+                MemoryTypeFilter::PREFER_HOST | MemoryTypeFilter::HOST_RANDOM_ACCESS
+            }
+        }
+    }
+}
 
 /// A managed buffer wrapper providing RAII resource management
 ///
@@ -37,6 +77,10 @@ use crate::{GammaVkError, Result};
 pub struct Buffer {
     /// The underlying Vulkano subbuffer
     buffer: Subbuffer<[u8]>,
+    /// Memory type preference the buffer was allocated with, reused by `resized`
+    memory_type_filter: MemoryTypeFilter,
+    /// Allocator the buffer was created from, reused by `resized`
+    allocator: Arc<StandardMemoryAllocator>,
 }
 
 impl Buffer {
@@ -55,14 +99,44 @@ impl Buffer {
     /// # Errors
     ///
     /// This function will return an error if:
-    /// * The allocator runs out of memory
+    /// * The driver runs out of memory, reported as [`GammaVkError::OutOfMemory`]
+    ///   so callers can free resources and retry
     /// * The requested size exceeds device limits
     /// * The usage flags are invalid or unsupported
+    ///
+    /// In debug builds, a warning is logged for buffers smaller than the device's
+    /// `nonCoherentAtomSize`; such tiny buffers still waste a whole atom of memory.
     pub fn new_host_visible(
-        _device: &Arc<Device>,
+        device: &Arc<Device>,
+        allocator: &Arc<StandardMemoryAllocator>,
+        size: u64,
+        usage: BufferUsage,
+    ) -> Result<Self> {
+        Self::new_host_visible_with_access(
+            device,
+            allocator,
+            size,
+            usage,
+            AccessPattern::SequentialWrite,
+        )
+    }
+
+    /// Create a new host-visible buffer optimized for the given CPU access pattern
+    ///
+    /// Use [`AccessPattern::RandomAccess`] for buffers that are read back or updated
+    /// in place; [`new_host_visible`](Self::new_host_visible) uses
+    /// [`AccessPattern::SequentialWrite`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as
+    /// [`new_host_visible`](Self::new_host_visible).
+    pub fn new_host_visible_with_access(
+        device: &Arc<Device>,
         allocator: &Arc<StandardMemoryAllocator>,
         size: u64,
         usage: BufferUsage,
+        access: AccessPattern,
     ) -> Result<Self> {
         // Validate size per Vulkan spec VUID-VkBufferCreateInfo-size-00912
         if size == 0 {
@@ -71,6 +145,22 @@ impl Buffer {
             ));
         }
 
+        if cfg!(debug_assertions) {
+            let atom_size = device
+                .physical_device()
+                .properties()
+                .non_coherent_atom_size
+                .as_devicesize();
+            if size < atom_size {
+                log::warn!(
+                    "Host-visible buffer of {} bytes is smaller than the device's non-coherent atom size of {} bytes; consider packing small buffers together",
+                    size,
+                    atom_size
+                );
+            }
+        }
+
+        profile_scope!("Buffer allocation");
         let buffer = VulkanoBuffer::new_slice::<u8>(
             allocator.clone(),
             BufferCreateInfo {
@@ -78,17 +168,18 @@ impl Buffer {
                 ..Default::default()
             },
             AllocationCreateInfo {
-                memory_type_filter: MemoryTypeFilter::PREFER_HOST
-                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                memory_type_filter: access.memory_type_filter(),
                 ..Default::default()
             },
             size,
         )
-        .map_err(|e| {
-            GammaVkError::buffer_creation(format!("Failed to create host-visible buffer: {}", e))
-        })?;
+        .map_err(|e| allocation_error(e, "host-visible", false, size))?;
 
-        Ok(Buffer { buffer })
+        Ok(Buffer {
+            buffer,
+            memory_type_filter: access.memory_type_filter(),
+            allocator: allocator.clone(),
+        })
     }
 
     /// Create a new device-local buffer (optimal for GPU access)
@@ -120,6 +211,7 @@ impl Buffer {
             ));
         }
 
+        profile_scope!("Buffer allocation");
         let buffer = VulkanoBuffer::new_slice::<u8>(
             allocator.clone(),
             BufferCreateInfo {
@@ -132,11 +224,13 @@ impl Buffer {
             },
             size,
         )
-        .map_err(|e| {
-            GammaVkError::buffer_creation(format!("Failed to create device-local buffer: {}", e))
-        })?;
+        .map_err(|e| allocation_error(e, "device-local", true, size))?;
 
-        Ok(Buffer { buffer })
+        Ok(Buffer {
+            buffer,
+            memory_type_filter: MemoryTypeFilter::PREFER_DEVICE,
+            allocator: allocator.clone(),
+        })
     }
 
     /// Create a new buffer with custom allocation preferences
@@ -161,6 +255,8 @@ impl Buffer {
             ));
         }
 
+        profile_scope!("Buffer allocation");
+        let memory_type_filter = allocation_info.memory_type_filter;
         let buffer = VulkanoBuffer::new_slice::<u8>(
             allocator.clone(),
             BufferCreateInfo {
@@ -171,10 +267,17 @@ impl Buffer {
             size,
         )
         .map_err(|e| {
-            GammaVkError::buffer_creation(format!("Failed to create custom buffer: {}", e))
+            let device_local = (memory_type_filter.required_flags
+                | memory_type_filter.preferred_flags)
+                .intersects(MemoryPropertyFlags::DEVICE_LOCAL);
+            allocation_error(e, "custom", device_local, size)
         })?;
 
-        Ok(Buffer { buffer })
+        Ok(Buffer {
+            buffer,
+            memory_type_filter,
+            allocator: allocator.clone(),
+        })
     }
 
     /// Get the size of the buffer in bytes
@@ -182,6 +285,14 @@ impl Buffer {
         self.buffer.len()
     }
 
+    /// Check whether the buffer holds zero bytes
+    ///
+    /// Always `false`, since zero-size buffers are rejected at creation. Provided
+    /// for generic code that checks emptiness rather than comparing `size()` to 0.
+    pub fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
     /// Get the underlying Vulkano subbuffer
     ///
     /// This provides access to the raw buffer for advanced use cases
@@ -200,25 +311,82 @@ impl Buffer {
     ///
     /// Returns an error if:
     /// * The data is larger than the buffer
-    /// * Memory mapping fails (buffer not host-visible)
-    /// * Buffer memory is not host-accessible
+    /// * The buffer is not host-visible ([`GammaVkError::BufferNotHostVisible`])
+    /// * Another access currently holds the buffer ([`GammaVkError::BufferBusy`]);
+    ///   unlike the other errors, this one is worth retrying
+    ///
+    /// Without the `checked` feature, oversized data is not reported as an error
+    /// and panics instead.
+    ///
+    /// # Coherency
+    ///
+    /// No explicit flush is needed: on memory without `HOST_COHERENT`, the written
+    /// range is flushed as soon as the write completes, and reads such as
+    /// [`read_struct`](Self::read_struct) invalidate the range before reading.
+    /// Coherent memory skips both.
     pub fn write_data(&self, data: &[u8]) -> Result<()> {
-        if data.len() > self.buffer.len() as usize {
-            return Err(GammaVkError::buffer_creation(format!(
-                "Data size {} exceeds buffer size {}",
-                data.len(),
-                self.buffer.len()
-            )));
-        }
+        write_subbuffer(&self.buffer, data)
+    }
 
-        let mut write_lock = self.buffer.write().map_err(|e| {
+    /// Write a plain-old-data value to the start of the buffer
+    ///
+    /// This is the ergonomic path for updating host-visible uniform buffers, such as
+    /// a per-frame camera block.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// * `size_of::<T>()` exceeds the buffer size
+    /// * The buffer is not host-visible ([`GammaVkError::BufferNotHostVisible`])
+    /// * Another access currently holds the buffer ([`GammaVkError::BufferBusy`])
+    pub fn write_struct<T: Pod>(&self, value: &T) -> Result<()> {
+        self.write_data(bytemuck::bytes_of(value))
+    }
+
+    /// Read a plain-old-data value from the start of the buffer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// * `size_of::<T>()` exceeds the buffer size
+    /// * The buffer is not host-visible ([`GammaVkError::BufferNotHostVisible`])
+    /// * The GPU or another host write currently holds the buffer
+    ///   ([`GammaVkError::BufferBusy`])
+    ///
+    /// Without the `checked` feature, an oversized `T` panics instead.
+    pub fn read_struct<T: Pod>(&self) -> Result<T> {
+        let size = size_of::<T>();
+        ensure!(
+            size <= self.buffer.len() as usize,
             GammaVkError::buffer_creation(format!(
-                "Failed to lock buffer for writing (buffer may not be host-visible): {}",
-                e
+                "Data size {} exceeds buffer size {}",
+                size,
+                self.buffer.len()
             ))
-        })?;
+        );
+
+        let read_lock = self.buffer.read().map_err(read_lock_error)?;
+
+        Ok(bytemuck::pod_read_unaligned(&read_lock[..size]))
+    }
 
-        write_lock[..data.len()].copy_from_slice(data);
+    /// Write data to the buffer starting at a byte offset
+    pub(crate) fn write_data_at(&self, offset: u64, data: &[u8]) -> Result<()> {
+        let end = offset
+            .checked_add(data.len() as u64)
+            .filter(|&end| end <= self.buffer.len())
+            .ok_or_else(|| {
+                GammaVkError::buffer_creation(format!(
+                    "Data size {} at offset {} exceeds buffer size {}",
+                    data.len(),
+                    offset,
+                    self.buffer.len()
+                ))
+            })?;
+
+        let mut write_lock = self.buffer.write().map_err(write_lock_error)?;
+
+        write_lock[offset as usize..end as usize].copy_from_slice(data);
         Ok(())
     }
 
@@ -226,51 +394,296 @@ impl Buffer {
     ///
     /// This method checks if the buffer's memory can be accessed from the CPU.
     /// Host-visible memory has the VK_MEMORY_PROPERTY_HOST_VISIBLE_BIT set.
+    ///
+    /// The answer comes from the memory type the buffer was bound to, so it does
+    /// not depend on whether the buffer is currently locked.
     pub fn is_host_visible(&self) -> bool {
-        // In Vulkano 0.35, the most reliable way to check host visibility
-        // is to attempt to obtain a write lock. If the memory is not host-visible,
-        // this will fail. This approach is more accurate than trying to inspect
-        // memory properties directly, which Vulkano doesn't expose in a straightforward way.
-        self.buffer.write().is_ok()
+        is_host_visible(&self.buffer)
     }
 
-    /// Create a staging buffer and copy data to device-local buffer
+    /// Upload `data` to the start of this buffer
     ///
-    /// This helper method creates a temporary host-visible staging buffer,
-    /// uploads data to it, then copies to the device-local buffer.
+    /// Device-local buffers are filled from the context's staging buffer in one
+    /// submission, which is waited on, so the copy has finished and later
+    /// submissions (a draw reading the buffer as vertices, for example) see the
+    /// data. Host-visible buffers are written directly.
     ///
-    /// # Arguments
+    /// # Errors
     ///
-    /// * `device` - Vulkan device for command buffer creation
-    /// * `allocator` - Memory allocator for staging buffer
-    /// * `data` - Data to upload to the device-local buffer
+    /// Returns an error if `data` is empty or larger than the buffer, a
+    /// device-local buffer lacks `TRANSFER_DST` usage, or the upload fails.
     ///
-    /// # Note
+    /// # Examples
     ///
-    /// This is a placeholder for future staging buffer implementation.
-    /// Real implementation would require command buffer recording and submission.
-    pub fn upload_via_staging(
-        &self,
-        _device: &Arc<Device>,
-        _allocator: &Arc<StandardMemoryAllocator>,
-        _data: &[u8],
-    ) -> Result<()> {
-        // TODO: Implement staging buffer pattern for device-local buffers
-        // This would involve:
-        // 1. Create temporary host-visible staging buffer
-        // 2. Write data to staging buffer
-        // 3. Record copy command from staging to device-local buffer
-        // 4. Submit command buffer and wait for completion
-        // 5. Clean up staging buffer
-        Err(GammaVkError::buffer_creation(
-            "Staging buffer upload not yet implemented".to_string(),
-        ))
+    /// ```no_run
+    /// use gamma_vk::{Buffer, VulkanContext, buffer::usage};
+    ///
+    /// let context = VulkanContext::new()?;
+    /// let vertices = [0u8; 1024];
+    /// let buffer = Buffer::new_device_local(
+    ///     &context.device(),
+    ///     &context.memory_allocator(),
+    ///     vertices.len() as u64,
+    ///     usage::STATIC_VERTEX,
+    /// )?;
+    /// buffer.upload_staged(&context, &vertices)?;
+    /// # Ok::<(), gamma_vk::GammaVkError>(())
+    /// ```
+    pub fn upload_staged(&self, context: &VulkanContext, data: &[u8]) -> Result<()> {
+        if data.is_empty() || data.len() as u64 > self.size() {
+            return Err(GammaVkError::buffer_creation(format!(
+                "Upload of {} bytes must be non-empty and fit in buffer size {}",
+                data.len(),
+                self.size()
+            )));
+        }
+
+        if self.is_host_visible() {
+            return self.write_data(data);
+        }
+
+        context.with_staging(data.len() as u64, |staging| {
+            staging.write_data(data)?;
+            let mut recorder = CommandRecorder::new(context)?;
+            recorder.copy_buffer_regions(
+                staging,
+                self,
+                &[BufferCopy {
+                    size: data.len() as u64,
+                    ..Default::default()
+                }],
+            )?;
+            recorder.submit_and_wait(&context.graphics_queue())
+        })
     }
 
     /// Get buffer usage flags
     pub fn usage(&self) -> BufferUsage {
         self.buffer.buffer().usage()
     }
+
+    /// Check that this buffer was created with every flag in `required`
+    ///
+    /// Call this where a buffer is bound, so a mismatch fails immediately instead
+    /// of as a validation error at draw time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the missing flags, such as `UNIFORM_BUFFER`.
+    pub fn assert_usage(&self, required: BufferUsage) -> Result<()> {
+        let missing = required.difference(self.usage());
+        if !missing.is_empty() {
+            return Err(GammaVkError::buffer_creation(format!(
+                "Buffer is missing required {:?} usage",
+                missing
+            )));
+        }
+        Ok(())
+    }
+
+    /// Get the alignment that offsets into this buffer must respect
+    ///
+    /// Combines every requirement implied by the buffer's usage and memory: the
+    /// device's `minUniformBufferOffsetAlignment`, `minStorageBufferOffsetAlignment`
+    /// and `minTexelBufferOffsetAlignment` for the matching usages, 4 bytes for
+    /// vertex and index data, and `nonCoherentAtomSize` for host-visible memory
+    /// that is not host-coherent, where flushed ranges must be atom-aligned. The
+    /// result is always a power of two.
+    ///
+    /// When suballocating regions from a shared buffer, round each region's offset
+    /// up with `vulkano::memory::allocator::align_up(offset, alignment)` before
+    /// calling [`slice`](Self::slice).
+    pub fn required_alignment(&self) -> u64 {
+        let usage = self.usage();
+        let limits = self.buffer.device().physical_device().properties();
+
+        let mut alignment = 1;
+        if usage.intersects(BufferUsage::VERTEX_BUFFER | BufferUsage::INDEX_BUFFER) {
+            alignment = alignment.max(4);
+        }
+        if usage.intersects(BufferUsage::UNIFORM_BUFFER) {
+            alignment = alignment.max(limits.min_uniform_buffer_offset_alignment.as_devicesize());
+        }
+        if usage.intersects(BufferUsage::STORAGE_BUFFER) {
+            alignment = alignment.max(limits.min_storage_buffer_offset_alignment.as_devicesize());
+        }
+        if usage.intersects(BufferUsage::UNIFORM_TEXEL_BUFFER | BufferUsage::STORAGE_TEXEL_BUFFER) {
+            alignment = alignment.max(limits.min_texel_buffer_offset_alignment.as_devicesize());
+        }
+        if let BufferMemory::Normal(allocation) = self.buffer.buffer().memory()
+            && let Some(atom_size) = allocation.atom_size()
+        {
+            alignment = alignment.max(atom_size.as_devicesize());
+        }
+        alignment
+    }
+
+    /// Reallocate this buffer at `new_size`, preserving its contents
+    ///
+    /// The new buffer comes from the allocator this buffer was created with, uses
+    /// the same usage flags and memory type preference, and receives the first
+    /// `min(old, new)` bytes; shrinking truncates. Host-visible buffers are copied
+    /// on the CPU. Other buffers are copied on the GPU through the context's
+    /// graphics queue, which requires `TRANSFER_SRC` usage; their replacement gains
+    /// `TRANSFER_SRC | TRANSFER_DST` so it can be resized again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `new_size` is zero, allocation fails, the buffer is not
+    /// host-visible and lacks `TRANSFER_SRC` usage, or the buffer is host-visible
+    /// but its replacement was allocated in memory the CPU cannot write.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gamma_vk::{Buffer, VulkanContext};
+    /// use vulkano::buffer::BufferUsage;
+    ///
+    /// let context = VulkanContext::new()?;
+    /// let allocator = context.memory_allocator();
+    /// let buffer = Buffer::new_host_visible(&context.device(), &allocator, 1024, BufferUsage::VERTEX_BUFFER)?;
+    /// let buffer = buffer.resized(&context, 4096)?;
+    /// assert_eq!(buffer.size(), 4096);
+    /// # Ok::<(), gamma_vk::GammaVkError>(())
+    /// ```
+    pub fn resized(self, context: &VulkanContext, new_size: u64) -> Result<Buffer> {
+        let host_visible = self.is_host_visible();
+        let usage = if host_visible {
+            self.usage()
+        } else {
+            // Check before allocating, so a failed resize leaves no stray buffer
+            if !self.usage().contains(BufferUsage::TRANSFER_SRC) {
+                return Err(GammaVkError::buffer_creation(
+                    "Resizing a device-local buffer requires TRANSFER_SRC usage",
+                ));
+            }
+            self.usage() | BufferUsage::TRANSFER_SRC | BufferUsage::TRANSFER_DST
+        };
+
+        let resized = Buffer::new_custom(
+            &context.device(),
+            &self.allocator,
+            new_size,
+            usage,
+            AllocationCreateInfo {
+                memory_type_filter: self.memory_type_filter,
+                ..Default::default()
+            },
+        )?;
+
+        if host_visible {
+            // The replacement lacks TRANSFER_DST, so it can only be filled on the CPU
+            if !resized.is_host_visible() {
+                return Err(GammaVkError::buffer_creation(
+                    "Resized buffer was allocated in memory that is not host-visible",
+                ));
+            }
+            let copy_size = self.size().min(new_size) as usize;
+            let read_lock = self.buffer.read().map_err(read_lock_error)?;
+            resized.write_data(&read_lock[..copy_size])?;
+            return Ok(resized);
+        }
+
+        let mut recorder = CommandRecorder::new(context)?;
+        recorder.copy_buffer(&self, &resized)?;
+        recorder.submit_and_wait(&context.graphics_queue())?;
+
+        Ok(resized)
+    }
+
+    /// Fill this buffer with zeros, returning it for chaining after creation
+    ///
+    /// Buffers start with undefined contents; zeroing them makes reads of
+    /// never-written data deterministic, which helps when debugging. Host-visible
+    /// buffers are cleared on the CPU. Other buffers are filled on the GPU through
+    /// the context's graphics queue, which requires `TRANSFER_DST` usage and a size
+    /// that is a multiple of 4.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GammaVkError::BufferBusy`] if a host-visible buffer is being
+    /// accessed elsewhere. Returns an error if a GPU fill is needed and the buffer
+    /// lacks `TRANSFER_DST` usage or has a size that is not a multiple of 4, or the
+    /// fill fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gamma_vk::{Buffer, VulkanContext};
+    /// use vulkano::buffer::BufferUsage;
+    ///
+    /// let context = VulkanContext::new()?;
+    /// let buffer = Buffer::new_device_local(
+    ///     &context.device(),
+    ///     &context.memory_allocator(),
+    ///     1024,
+    ///     BufferUsage::STORAGE_BUFFER | BufferUsage::TRANSFER_DST,
+    /// )?
+    /// .zeroed(&context)?;
+    /// # Ok::<(), gamma_vk::GammaVkError>(())
+    /// ```
+    pub fn zeroed(self, context: &VulkanContext) -> Result<Self> {
+        if self.is_host_visible() {
+            self.buffer.write().map_err(write_lock_error)?.fill(0);
+            return Ok(self);
+        }
+
+        if !self.usage().contains(BufferUsage::TRANSFER_DST) {
+            return Err(GammaVkError::buffer_creation(
+                "Zeroing a device-local buffer requires TRANSFER_DST usage",
+            ));
+        }
+
+        let mut recorder = CommandRecorder::new(context)?;
+        recorder.fill_buffer(&self, 0)?;
+        recorder.submit_and_wait(&context.graphics_queue())?;
+
+        Ok(self)
+    }
+
+    /// Create a view of a byte range of this buffer
+    ///
+    /// The view shares this buffer's allocation, so it can bind or update one
+    /// region, such as a single mesh inside a shared vertex arena, without a new
+    /// allocation. The view keeps the allocation alive on its own.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the range is empty or extends past the end of the buffer.
+    pub fn slice(&self, range: Range<u64>) -> Result<BufferSlice> {
+        if range.start >= range.end || range.end > self.size() {
+            return Err(GammaVkError::buffer_creation(format!(
+                "Slice range {}..{} is empty or exceeds buffer size {}",
+                range.start,
+                range.end,
+                self.size()
+            )));
+        }
+
+        Ok(BufferSlice {
+            buffer: self.buffer.clone().slice(range),
+        })
+    }
+
+    /// Convert this buffer into a cloneable shared handle
+    ///
+    /// Buffers are move-only by default. Use this when several subsystems need to
+    /// reference the same GPU buffer, making the intent to share explicit.
+    pub fn share(self) -> SharedBuffer {
+        SharedBuffer {
+            buffer: Arc::new(self),
+        }
+    }
+}
+
+impl std::fmt::Debug for Buffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Buffer")
+            .field("size", &self.size())
+            .field("usage", &self.usage())
+            .field("host_visible", &self.is_host_visible())
+            .finish()
+    }
 }
 
 impl Drop for Buffer {
@@ -285,6 +698,167 @@ impl Drop for Buffer {
     }
 }
 
+/// A reference-counted handle to a shared [`Buffer`]
+///
+/// Created with [`Buffer::share`]. Cloning a SharedBuffer produces another handle to
+/// the same GPU buffer; the buffer is released when the last handle is dropped.
+#[derive(Clone)]
+pub struct SharedBuffer {
+    buffer: Arc<Buffer>,
+}
+
+impl std::ops::Deref for SharedBuffer {
+    type Target = Buffer;
+
+    fn deref(&self) -> &Buffer {
+        &self.buffer
+    }
+}
+
+impl std::fmt::Debug for SharedBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedBuffer")
+            .field("buffer", &*self.buffer)
+            .finish()
+    }
+}
+
+/// A bounded view of a byte range within a [`Buffer`]
+///
+/// Created with [`Buffer::slice`]. Offsets are relative to the start of the slice.
+#[derive(Clone)]
+pub struct BufferSlice {
+    buffer: Subbuffer<[u8]>,
+}
+
+impl BufferSlice {
+    /// Get the size of the slice in bytes
+    pub fn size(&self) -> u64 {
+        self.buffer.len()
+    }
+
+    /// Get the underlying Vulkano subbuffer covering the slice
+    pub fn inner(&self) -> &Subbuffer<[u8]> {
+        &self.buffer
+    }
+
+    /// Write data to the start of the slice (only works with host-visible buffers)
+    ///
+    /// Only the slice's own range of the parent allocation is locked and written,
+    /// so writes never touch neighboring slices of the same buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// * The data is larger than the slice
+    /// * The buffer is not host-visible ([`GammaVkError::BufferNotHostVisible`])
+    /// * Another access currently holds the slice's range
+    ///   ([`GammaVkError::BufferBusy`])
+    pub fn write_data(&self, data: &[u8]) -> Result<()> {
+        write_subbuffer(&self.buffer, data)
+    }
+}
+
+impl std::fmt::Debug for BufferSlice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BufferSlice")
+            .field("offset", &self.buffer.offset())
+            .field("size", &self.size())
+            .finish()
+    }
+}
+
+/// Write `data` to the start of a host-visible subbuffer
+///
+/// Dropping the write lock flushes the range when the memory is not host-coherent.
+fn write_subbuffer(buffer: &Subbuffer<[u8]>, data: &[u8]) -> Result<()> {
+    ensure!(
+        data.len() <= buffer.len() as usize,
+        GammaVkError::buffer_creation(format!(
+            "Data size {} exceeds buffer size {}",
+            data.len(),
+            buffer.len()
+        ))
+    );
+    if !is_host_visible(buffer) {
+        return Err(GammaVkError::BufferNotHostVisible);
+    }
+
+    let mut write_lock = buffer.write().map_err(write_lock_error)?;
+
+    write_lock[..data.len()].copy_from_slice(data);
+    Ok(())
+}
+
+/// Convert a failed buffer allocation into an error, keeping out-of-memory distinct
+fn allocation_error(
+    error: Validated<AllocateBufferError>,
+    kind: &str,
+    device_local: bool,
+    requested: u64,
+) -> GammaVkError {
+    let is_out_of_memory = |error: &VulkanError| {
+        matches!(
+            error,
+            VulkanError::OutOfDeviceMemory | VulkanError::OutOfHostMemory
+        )
+    };
+
+    match &error {
+        Validated::Error(
+            AllocateBufferError::CreateBuffer(e) | AllocateBufferError::BindMemory(e),
+        ) if is_out_of_memory(e) => GammaVkError::OutOfMemory {
+            device_local,
+            requested,
+        },
+        Validated::Error(AllocateBufferError::AllocateMemory(
+            MemoryAllocatorError::AllocateDeviceMemory(Validated::Error(e)),
+        )) if is_out_of_memory(e) => GammaVkError::OutOfMemory {
+            device_local,
+            requested,
+        },
+        _ => GammaVkError::buffer_creation(format!("Failed to create {} buffer: {}", kind, error)),
+    }
+}
+
+/// Check the memory type a subbuffer is bound to for `HOST_VISIBLE`
+fn is_host_visible(buffer: &Subbuffer<[u8]>) -> bool {
+    let BufferMemory::Normal(allocation) = buffer.buffer().memory() else {
+        return false;
+    };
+    let memory_type_index = allocation.device_memory().memory_type_index() as usize;
+
+    buffer
+        .device()
+        .physical_device()
+        .memory_properties()
+        .memory_types[memory_type_index]
+        .property_flags
+        .intersects(MemoryPropertyFlags::HOST_VISIBLE)
+}
+
+/// Convert a failed host lock into an error, separating busy buffers from
+/// buffers the CPU can never access
+fn host_lock_error(error: HostAccessError, access: &str) -> GammaVkError {
+    match error {
+        HostAccessError::NotHostMapped => GammaVkError::BufferNotHostVisible,
+        HostAccessError::AccessConflict(conflict) => GammaVkError::BufferBusy {
+            message: conflict.to_string(),
+        },
+        e => GammaVkError::buffer_creation(format!("Failed to lock buffer for {}: {}", access, e)),
+    }
+}
+
+/// Convert a failed write lock into an error
+fn write_lock_error(error: HostAccessError) -> GammaVkError {
+    host_lock_error(error, "writing")
+}
+
+/// Convert a failed read lock into an error
+fn read_lock_error(error: HostAccessError) -> GammaVkError {
+    host_lock_error(error, "reading")
+}
+
 /// Type-safe vertex buffer wrapper
 ///
 /// VertexBuffer prevents accidentally using vertex buffers in inappropriate contexts
@@ -310,12 +884,31 @@ impl VertexBuffer {
         allocator: &Arc<StandardMemoryAllocator>,
         size: u64,
     ) -> Result<Self> {
-        let buffer = Buffer::new_device_local(
-            device,
-            allocator,
-            size,
-            BufferUsage::VERTEX_BUFFER | BufferUsage::TRANSFER_DST,
-        )?;
+        Self::new_device_local_with_usage(device, allocator, size, BufferUsage::empty())
+    }
+
+    /// Create a new device-local vertex buffer with additional usage flags
+    ///
+    /// `extra` is combined with the required `VERTEX_BUFFER | TRANSFER_DST` usage, for example
+    /// `TRANSFER_SRC` to copy the buffer back for debugging.
+    pub fn new_device_local_with_usage(
+        device: &Arc<Device>,
+        allocator: &Arc<StandardMemoryAllocator>,
+        size: u64,
+        extra: BufferUsage,
+    ) -> Result<Self> {
+        let buffer =
+            Buffer::new_device_local(device, allocator, size, usage::STATIC_VERTEX | extra)?;
+        Ok(VertexBuffer { buffer })
+    }
+
+    /// Wrap an existing buffer as a vertex buffer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the missing flag if the buffer lacks `VERTEX_BUFFER` usage.
+    pub fn from_buffer(buffer: Buffer) -> Result<Self> {
+        buffer.assert_usage(BufferUsage::VERTEX_BUFFER)?;
         Ok(VertexBuffer { buffer })
     }
 
@@ -328,6 +921,36 @@ impl VertexBuffer {
     pub fn size(&self) -> u64 {
         self.buffer.size()
     }
+
+    /// Record a bind of this buffer to the given vertex input binding
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer lacks `VERTEX_BUFFER` usage or the device
+    /// rejects the binding index.
+    pub fn bind(&self, recorder: &mut CommandRecorder, binding_index: u32) -> Result<()> {
+        if !self.buffer.usage().contains(BufferUsage::VERTEX_BUFFER) {
+            return Err(GammaVkError::command_execution(
+                "Cannot bind vertex buffer without VERTEX_BUFFER usage",
+            ));
+        }
+
+        recorder
+            .builder_mut()
+            .bind_vertex_buffers(binding_index, self.buffer.inner().clone())
+            .map_err(|e| {
+                GammaVkError::command_execution(format!("Failed to bind vertex buffer: {}", e))
+            })?;
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for VertexBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VertexBuffer")
+            .field("buffer", &self.buffer)
+            .finish()
+    }
 }
 
 /// Type-safe index buffer wrapper
@@ -354,16 +977,39 @@ impl IndexBuffer {
         device: &Arc<Device>,
         allocator: &Arc<StandardMemoryAllocator>,
         size: u64,
+    ) -> Result<Self> {
+        Self::new_device_local_with_usage(device, allocator, size, BufferUsage::empty())
+    }
+
+    /// Create a new device-local index buffer with additional usage flags
+    ///
+    /// `extra` is combined with the required `INDEX_BUFFER | TRANSFER_DST` usage,
+    /// for example `TRANSFER_SRC` to copy the buffer back for debugging.
+    pub fn new_device_local_with_usage(
+        device: &Arc<Device>,
+        allocator: &Arc<StandardMemoryAllocator>,
+        size: u64,
+        extra: BufferUsage,
     ) -> Result<Self> {
         let buffer = Buffer::new_device_local(
             device,
             allocator,
             size,
-            BufferUsage::INDEX_BUFFER | BufferUsage::TRANSFER_DST,
+            BufferUsage::INDEX_BUFFER | BufferUsage::TRANSFER_DST | extra,
         )?;
         Ok(IndexBuffer { buffer })
     }
 
+    /// Wrap an existing buffer as an index buffer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the missing flag if the buffer lacks `INDEX_BUFFER` usage.
+    pub fn from_buffer(buffer: Buffer) -> Result<Self> {
+        buffer.assert_usage(BufferUsage::INDEX_BUFFER)?;
+        Ok(IndexBuffer { buffer })
+    }
+
     /// Get the underlying buffer
     pub fn buffer(&self) -> &Buffer {
         &self.buffer
@@ -373,6 +1019,57 @@ impl IndexBuffer {
     pub fn size(&self) -> u64 {
         self.buffer.size()
     }
+
+    /// Record a bind of this buffer as the index buffer, reading indices as `index_type`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer lacks `INDEX_BUFFER` usage, its size is not a
+    /// multiple of the index size, or the index type is unsupported by the device.
+    pub fn bind(&self, recorder: &mut CommandRecorder, index_type: IndexType) -> Result<()> {
+        if !self.buffer.usage().contains(BufferUsage::INDEX_BUFFER) {
+            return Err(GammaVkError::command_execution(
+                "Cannot bind index buffer without INDEX_BUFFER usage",
+            ));
+        }
+
+        if !self.size().is_multiple_of(index_type.size()) {
+            return Err(GammaVkError::command_execution(format!(
+                "Index buffer size {} is not a multiple of the {:?} index size",
+                self.size(),
+                index_type
+            )));
+        }
+
+        let bytes = self.buffer.inner().clone();
+        let index_buffer = match index_type {
+            IndexType::U8 => VulkanoIndexBuffer::U8(bytes),
+            IndexType::U16 => VulkanoIndexBuffer::U16(bytes.reinterpret()),
+            IndexType::U32 => VulkanoIndexBuffer::U32(bytes.reinterpret()),
+            _ => {
+                return Err(GammaVkError::command_execution(format!(
+                    "Unsupported index type {:?}",
+                    index_type
+                )));
+            }
+        };
+
+        recorder
+            .builder_mut()
+            .bind_index_buffer(index_buffer)
+            .map_err(|e| {
+                GammaVkError::command_execution(format!("Failed to bind index buffer: {}", e))
+            })?;
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for IndexBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IndexBuffer")
+            .field("buffer", &self.buffer)
+            .finish()
+    }
 }
 
 /// Type-safe uniform buffer wrapper
@@ -401,12 +1098,31 @@ impl UniformBuffer {
         allocator: &Arc<StandardMemoryAllocator>,
         size: u64,
     ) -> Result<Self> {
-        let buffer = Buffer::new_device_local(
-            device,
-            allocator,
-            size,
-            BufferUsage::UNIFORM_BUFFER | BufferUsage::TRANSFER_DST,
-        )?;
+        Self::new_device_local_with_usage(device, allocator, size, BufferUsage::empty())
+    }
+
+    /// Create a new device-local uniform buffer with additional usage flags
+    ///
+    /// `extra` is combined with the required `UNIFORM_BUFFER | TRANSFER_DST` usage, for example
+    /// `TRANSFER_SRC` to copy the buffer back for debugging.
+    pub fn new_device_local_with_usage(
+        device: &Arc<Device>,
+        allocator: &Arc<StandardMemoryAllocator>,
+        size: u64,
+        extra: BufferUsage,
+    ) -> Result<Self> {
+        let buffer =
+            Buffer::new_device_local(device, allocator, size, usage::STATIC_UNIFORM | extra)?;
+        Ok(UniformBuffer { buffer })
+    }
+
+    /// Wrap an existing buffer as a uniform buffer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the missing flag if the buffer lacks `UNIFORM_BUFFER` usage.
+    pub fn from_buffer(buffer: Buffer) -> Result<Self> {
+        buffer.assert_usage(BufferUsage::UNIFORM_BUFFER)?;
         Ok(UniformBuffer { buffer })
     }
 
@@ -419,4 +1135,421 @@ impl UniformBuffer {
     pub fn size(&self) -> u64 {
         self.buffer.size()
     }
+
+    /// Round `element_size` up to the device's `minUniformBufferOffsetAlignment`
+    ///
+    /// Use this stride when packing several uniform blocks into one buffer and
+    /// addressing them with dynamic descriptor offsets.
+    pub fn aligned_stride(context: &VulkanContext, element_size: u64) -> u64 {
+        let alignment = context
+            .physical_device()
+            .properties()
+            .min_uniform_buffer_offset_alignment;
+        align_up(element_size, alignment)
+    }
+
+    /// Write one element into slot `index` of a packed uniform array
+    ///
+    /// The slot offset is `index * stride`, where the stride is `data.len()` rounded
+    /// up to the device's uniform offset alignment.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slot does not fit in the buffer or the buffer is
+    /// not host-visible.
+    pub fn write_element(&self, index: u64, data: &[u8]) -> Result<()> {
+        let alignment = self
+            .buffer
+            .inner()
+            .device()
+            .physical_device()
+            .properties()
+            .min_uniform_buffer_offset_alignment;
+        let stride = align_up(data.len() as u64, alignment);
+        let offset = index.checked_mul(stride).ok_or_else(|| {
+            GammaVkError::buffer_creation(format!(
+                "Uniform element index {} overflows buffer offset",
+                index
+            ))
+        })?;
+
+        self.buffer.write_data_at(offset, data)
+    }
+}
+
+impl std::fmt::Debug for UniformBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UniformBuffer")
+            .field("buffer", &self.buffer)
+            .finish()
+    }
+}
+
+/// Uniform buffer holding exactly one value of type `T`
+///
+/// The buffer is sized to `size_of::<T>()` rounded up to the device's
+/// `minUniformBufferOffsetAlignment`, so callers never compute byte sizes or
+/// offsets by hand. `T` must match the shader's uniform block layout, typically
+/// by being `#[repr(C)]` with std140-compatible padding.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gamma_vk::{TypedUniformBuffer, VulkanContext};
+///
+/// #[repr(C)]
+/// #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+/// struct Camera {
+///     view_proj: [[f32; 4]; 4],
+/// }
+///
+/// let context = VulkanContext::new()?;
+/// let camera = TypedUniformBuffer::<Camera>::new_host_visible(&context)?;
+/// camera.update(&Camera { view_proj: [[0.0; 4]; 4] })?;
+/// # Ok::<(), gamma_vk::GammaVkError>(())
+/// ```
+pub struct TypedUniformBuffer<T: Pod> {
+    buffer: Buffer,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Pod> TypedUniformBuffer<T> {
+    /// Create a host-visible uniform buffer updated directly from the CPU
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `T` is zero-sized or larger than the device's
+    /// `maxUniformBufferRange`, or if allocation fails.
+    pub fn new_host_visible(context: &VulkanContext) -> Result<Self> {
+        let size = Self::aligned_size(context)?;
+        let buffer = Buffer::new_host_visible(
+            &context.device(),
+            &context.memory_allocator(),
+            size,
+            BufferUsage::UNIFORM_BUFFER,
+        )?;
+        Ok(Self {
+            buffer,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Create a device-local uniform buffer updated through staging
+    ///
+    /// Use [`update_staged`](Self::update_staged) to write it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `T` is zero-sized or larger than the device's
+    /// `maxUniformBufferRange`, or if allocation fails.
+    pub fn new_device_local(context: &VulkanContext) -> Result<Self> {
+        let size = Self::aligned_size(context)?;
+        let buffer = Buffer::new_device_local(
+            &context.device(),
+            &context.memory_allocator(),
+            size,
+            usage::STATIC_UNIFORM,
+        )?;
+        Ok(Self {
+            buffer,
+            _marker: PhantomData,
+        })
+    }
+
+    fn aligned_size(context: &VulkanContext) -> Result<u64> {
+        let size = size_of::<T>() as u64;
+        let physical_device = context.physical_device();
+        let properties = physical_device.properties();
+        if size == 0 {
+            return Err(GammaVkError::buffer_creation(
+                "Uniform buffer type must not be zero-sized",
+            ));
+        }
+        if size > properties.max_uniform_buffer_range as u64 {
+            return Err(GammaVkError::buffer_creation(format!(
+                "Uniform type {} is {} bytes but the device allows at most {} \
+                 (maxUniformBufferRange)",
+                std::any::type_name::<T>(),
+                size,
+                properties.max_uniform_buffer_range
+            )));
+        }
+        Ok(align_up(
+            size,
+            properties.min_uniform_buffer_offset_alignment,
+        ))
+    }
+
+    /// Write `value` into a host-visible buffer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer is not host-visible; use
+    /// [`update_staged`](Self::update_staged) for device-local buffers.
+    pub fn update(&self, value: &T) -> Result<()> {
+        self.buffer.write_struct(value)
+    }
+
+    /// Write `value` through the context's staging buffer and wait for the copy
+    ///
+    /// Works for both host-visible and device-local buffers. Host-visible buffers
+    /// are written directly without a GPU copy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if staging allocation, recording or submission fails.
+    pub fn update_staged(&self, context: &VulkanContext, value: &T) -> Result<()> {
+        if self.buffer.is_host_visible() {
+            return self.update(value);
+        }
+
+        let bytes = bytemuck::bytes_of(value);
+        context.with_staging(bytes.len() as u64, |staging| {
+            staging.write_data(bytes)?;
+            let mut recorder = CommandRecorder::new(context)?;
+            recorder.copy_buffer_regions(
+                staging,
+                &self.buffer,
+                &[BufferCopy {
+                    size: bytes.len() as u64,
+                    ..Default::default()
+                }],
+            )?;
+            recorder.submit_and_wait(&context.graphics_queue())
+        })
+    }
+
+    /// Read the current value back from a host-visible buffer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer is not host-visible.
+    pub fn read(&self) -> Result<T> {
+        self.buffer.read_struct()
+    }
+
+    /// Get the underlying buffer, for binding in descriptor sets
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Get the buffer size in bytes, including alignment padding
+    pub fn size(&self) -> u64 {
+        self.buffer.size()
+    }
+}
+
+impl<T: Pod> std::fmt::Debug for TypedUniformBuffer<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TypedUniformBuffer")
+            .field("type", &std::any::type_name::<T>())
+            .field("buffer", &self.buffer)
+            .finish()
+    }
+}
+
+/// A host-visible buffer whose memory stays mapped and writable across frames
+///
+/// [`Buffer::write_data`] takes the buffer's host write lock on every call, and a
+/// held lock makes submissions that use the buffer fail. This type takes the
+/// mapped pointer once, when it is created, and hands the memory out without any
+/// locking, so rewriting per-frame data such as uniforms is a plain memory write.
+///
+/// Vulkano cannot see writes made this way, so keeping them away from the GPU is
+/// the caller's job: [`as_mut_slice`](Self::as_mut_slice) and
+/// [`flush_range`](Self::flush_range) are `unsafe`. The usual pattern is one
+/// buffer per [`FrameContext`](crate::FrameContext) slot, written only after the
+/// slot's fence has signaled. On memory without `HOST_COHERENT`, flush the
+/// written bytes before submitting work that reads them.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gamma_vk::{PersistentMappedBuffer, VulkanContext};
+/// use vulkano::buffer::BufferUsage;
+///
+/// let context = VulkanContext::new()?;
+/// let mut camera = PersistentMappedBuffer::new(
+///     &context.device(),
+///     &context.memory_allocator(),
+///     64,
+///     BufferUsage::UNIFORM_BUFFER,
+/// )?;
+///
+/// // Safety: no submitted work uses the buffer yet
+/// unsafe {
+///     camera.as_mut_slice()[..4].copy_from_slice(&1.0f32.to_ne_bytes());
+///     camera.flush_range(0..4)?;
+/// }
+/// # Ok::<(), gamma_vk::GammaVkError>(())
+/// ```
+pub struct PersistentMappedBuffer {
+    buffer: Buffer,
+    mapped: NonNull<[u8]>,
+}
+
+// Safety: `mapped` points into memory owned by `buffer`, which is itself Send and
+// Sync, and the memory is only handed out mutably through `&mut self`
+unsafe impl Send for PersistentMappedBuffer {}
+unsafe impl Sync for PersistentMappedBuffer {}
+
+impl PersistentMappedBuffer {
+    /// Create a new host-visible buffer and keep its memory mapped
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as [`Buffer::new_host_visible`].
+    pub fn new(
+        device: &Arc<Device>,
+        allocator: &Arc<StandardMemoryAllocator>,
+        size: u64,
+        usage: BufferUsage,
+    ) -> Result<Self> {
+        Self::from_buffer(Buffer::new_host_visible(device, allocator, size, usage)?)
+    }
+
+    /// Keep the memory of an existing host-visible buffer mapped
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GammaVkError::BufferNotHostVisible`] if the buffer's memory is not
+    /// host-visible.
+    pub fn from_buffer(buffer: Buffer) -> Result<Self> {
+        // Vulkano maps host-visible allocations for as long as they live, so the
+        // pointer stays valid while `buffer` does
+        let mapped = buffer
+            .buffer
+            .mapped_slice()
+            .map_err(|e| host_lock_error(e, "mapping"))?;
+        Ok(Self { buffer, mapped })
+    }
+
+    /// Get the underlying buffer, for binding in commands and descriptor sets
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Get the size of the buffer in bytes
+    pub fn size(&self) -> u64 {
+        self.buffer.size()
+    }
+
+    /// Check whether writes are visible to the device without flushing
+    pub fn is_coherent(&self) -> bool {
+        self.allocation().atom_size().is_none()
+    }
+
+    /// Get the mapped memory for direct writes
+    ///
+    /// No lock is taken, so this costs nothing per frame.
+    ///
+    /// # Safety
+    ///
+    /// While the returned slice is alive:
+    /// * No submitted GPU work that accesses the buffer may be pending or
+    ///   executing; wait on the fence of the last submission that used it first
+    /// * No other host access may go through clones of the underlying
+    ///   subbuffer, such as [`Buffer::inner`]
+    pub unsafe fn as_mut_slice(&mut self) -> &mut [u8] {
+        // Safety: the mapping outlives `self`, and `&mut self` plus the caller's
+        // contract rule out every other access
+        unsafe { self.mapped.as_mut() }
+    }
+
+    /// Make host writes to bytes `range` of the buffer visible to the device
+    ///
+    /// Call this after writing through [`as_mut_slice`](Self::as_mut_slice) and
+    /// before submitting work that reads the written bytes. The range is widened
+    /// to the device's non-coherent atom size. Coherent memory needs no flush, so
+    /// this returns immediately.
+    ///
+    /// # Safety
+    ///
+    /// No submitted GPU work that accesses the buffer may be pending or executing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `range` is empty or extends past the end of the buffer,
+    /// or the flush fails.
+    pub unsafe fn flush_range(&self, range: Range<u64>) -> Result<()> {
+        if range.is_empty() || range.end > self.size() {
+            return Err(GammaVkError::buffer_creation(format!(
+                "Flush range {}..{} must be non-empty and within buffer size {}",
+                range.start,
+                range.end,
+                self.size()
+            )));
+        }
+
+        let allocation = self.allocation();
+        let Some(atom_size) = allocation.atom_size() else {
+            return Ok(());
+        };
+
+        // The allocator aligns non-coherent allocations to the atom size, so the
+        // widened range stays inside this buffer's allocation
+        let offset = self.buffer.buffer.offset();
+        let start = align_down(offset + range.start, atom_size);
+        let end = align_up(offset + range.end, atom_size).min(allocation.size());
+        let memory_range = MappedMemoryRange {
+            offset: start,
+            size: end - start,
+            ..Default::default()
+        };
+
+        // Safety: the caller guarantees no device access to the buffer is pending
+        unsafe { allocation.flush_range(memory_range) }.map_err(|e| {
+            GammaVkError::buffer_creation(format!("Failed to flush mapped memory: {}", e))
+        })
+    }
+
+    /// The allocation backing the buffer
+    fn allocation(&self) -> &vulkano::memory::ResourceMemory {
+        match self.buffer.buffer.buffer().memory() {
+            BufferMemory::Normal(allocation) => allocation,
+            _ => unreachable!("host-visible buffers are always bound to normal memory"),
+        }
+    }
+}
+
+impl std::fmt::Debug for PersistentMappedBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PersistentMappedBuffer")
+            .field("buffer", &self.buffer)
+            .field("coherent", &self.is_coherent())
+            .finish()
+    }
+}
+
+/// Named `BufferUsage` presets for common buffer roles
+///
+/// Device-local buffers can only be filled by transfers, so presets for them
+/// include `TRANSFER_DST`; host-visible presets leave it out.
+pub mod usage {
+    use vulkano::buffer::BufferUsage;
+
+    /// Device-local vertex data uploaded once through a staging buffer
+    pub const STATIC_VERTEX: BufferUsage =
+        BufferUsage::VERTEX_BUFFER.union(BufferUsage::TRANSFER_DST);
+
+    /// Host-visible vertex data rewritten from the CPU, such as per-frame particles
+    pub const DYNAMIC_VERTEX: BufferUsage = BufferUsage::VERTEX_BUFFER;
+
+    /// Device-local index data uploaded once through a staging buffer
+    ///
+    /// Usage flags do not encode the index width; bind with `IndexType::U32`.
+    pub const INDEX_32: BufferUsage = BufferUsage::INDEX_BUFFER.union(BufferUsage::TRANSFER_DST);
+
+    /// Device-local uniform data updated through transfers
+    pub const STATIC_UNIFORM: BufferUsage =
+        BufferUsage::UNIFORM_BUFFER.union(BufferUsage::TRANSFER_DST);
+
+    /// Storage buffer that compute shaders read and write, uploaded to and read
+    /// back through transfers
+    pub const STORAGE_READWRITE: BufferUsage = BufferUsage::STORAGE_BUFFER
+        .union(BufferUsage::TRANSFER_SRC)
+        .union(BufferUsage::TRANSFER_DST);
+
+    /// Host-visible staging buffer that is the source of uploads
+    pub const STAGING_SRC: BufferUsage = BufferUsage::TRANSFER_SRC;
 }
@@ -0,0 +1,786 @@
+//! Command recording and submission for Gamma-VK
+//!
+//! This module provides a thin wrapper over Vulkano's command buffer builder for
+//! recording one-time commands and submitting them to a queue.
+
+use std::{sync::Arc, time::Duration};
+use vulkano::{
+    Validated, VulkanError,
+    buffer::{BufferUsage, IndexType},
+    command_buffer::{
+        AutoCommandBufferBuilder, BufferCopy, CommandBufferUsage, CopyBufferInfo,
+        DispatchIndirectCommand, PrimaryAutoCommandBuffer, PrimaryCommandBufferAbstract,
+        RenderingAttachmentInfo, RenderingInfo,
+    },
+    descriptor_set::DescriptorSet,
+    device::{DeviceOwned, Queue},
+    image::view::ImageView,
+    instance::debug::DebugUtilsLabel,
+    pipeline::{ComputePipeline, GraphicsPipeline, Pipeline, PipelineBindPoint},
+    render_pass::{AttachmentLoadOp, AttachmentStoreOp},
+    sync::{GpuFuture, PipelineStage},
+};
+
+use crate::{Buffer, GammaVkError, IndexBuffer, QueryPool, Result, VertexBuffer, VulkanContext};
+
+/// The draw call a [`DrawDescriptor`] resolves to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawCommand {
+    /// Non-indexed `draw` over `vertex_count` vertices
+    Draw { vertex_count: u32 },
+    /// Indexed `draw_indexed` over `index_count` indices of `index_type`
+    DrawIndexed {
+        index_count: u32,
+        index_type: IndexType,
+    },
+}
+
+/// A vertex buffer, optional index buffer, and the counts needed to draw them
+///
+/// Counts are derived from buffer sizes when the descriptor is created, so they
+/// cannot drift from the buffers at draw time.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gamma_vk::{CommandRecorder, DrawDescriptor, IndexBuffer, VertexBuffer};
+/// use vulkano::buffer::IndexType;
+/// # use std::sync::Arc;
+/// # use vulkano::pipeline::GraphicsPipeline;
+///
+/// # fn example(
+/// #     recorder: &mut CommandRecorder,
+/// #     pipeline: &Arc<GraphicsPipeline>,
+/// #     vertices: &VertexBuffer,
+/// #     indices: &IndexBuffer,
+/// # ) -> gamma_vk::Result<()> {
+/// let mesh = DrawDescriptor::indexed(vertices, 32, indices, IndexType::U16)?;
+/// recorder.draw_mesh(pipeline, None, &mesh)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct DrawDescriptor<'a> {
+    vertex_buffer: &'a VertexBuffer,
+    vertex_count: u32,
+    indices: Option<(&'a IndexBuffer, IndexType)>,
+}
+
+impl<'a> DrawDescriptor<'a> {
+    /// Describe a non-indexed draw of every vertex in `vertex_buffer`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `vertex_stride` is zero or does not evenly divide the
+    /// buffer size.
+    pub fn new(vertex_buffer: &'a VertexBuffer, vertex_stride: u32) -> Result<Self> {
+        let vertex_count = element_count(vertex_buffer.size(), vertex_stride.into(), "Vertex")?;
+        Ok(Self {
+            vertex_buffer,
+            vertex_count,
+            indices: None,
+        })
+    }
+
+    /// Describe an indexed draw of every index in `index_buffer`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either buffer size is not a multiple of its element size.
+    pub fn indexed(
+        vertex_buffer: &'a VertexBuffer,
+        vertex_stride: u32,
+        index_buffer: &'a IndexBuffer,
+        index_type: IndexType,
+    ) -> Result<Self> {
+        element_count(index_buffer.size(), index_type.size(), "Index")?;
+        Ok(Self {
+            indices: Some((index_buffer, index_type)),
+            ..Self::new(vertex_buffer, vertex_stride)?
+        })
+    }
+
+    /// Get the number of vertices in the vertex buffer
+    pub fn vertex_count(&self) -> u32 {
+        self.vertex_count
+    }
+
+    /// Check whether this descriptor draws with an index buffer
+    pub fn is_indexed(&self) -> bool {
+        self.indices.is_some()
+    }
+
+    /// Get the draw call this descriptor records
+    pub fn command(&self) -> DrawCommand {
+        match self.indices {
+            Some((index_buffer, index_type)) => DrawCommand::DrawIndexed {
+                index_count: (index_buffer.size() / index_type.size()) as u32,
+                index_type,
+            },
+            None => DrawCommand::Draw {
+                vertex_count: self.vertex_count,
+            },
+        }
+    }
+}
+
+/// Divide a buffer size into whole elements, rejecting partial elements
+fn element_count(size: u64, element_size: u64, kind: &str) -> Result<u32> {
+    if element_size == 0 || !size.is_multiple_of(element_size) {
+        return Err(GammaVkError::command_execution(format!(
+            "{} buffer size {} is not a multiple of element size {}",
+            kind, size, element_size
+        )));
+    }
+
+    u32::try_from(size / element_size)
+        .map_err(|_| GammaVkError::command_execution(format!("{} count exceeds u32::MAX", kind)))
+}
+
+/// Records commands into a one-time-submit primary command buffer
+///
+/// CommandRecorder is consumed on submission, so a recorded command buffer cannot
+/// accidentally be submitted twice.
+///
+/// There is no explicit barrier command. Vulkano's builder tracks the byte
+/// ranges and images each command accesses and inserts the pipeline barriers
+/// between dependent commands itself, for example between a compute dispatch
+/// that writes a buffer and a later one that reads it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gamma_vk::{Buffer, CommandRecorder, VulkanContext};
+/// use vulkano::buffer::BufferUsage;
+///
+/// let context = VulkanContext::new()?;
+/// let allocator = context.memory_allocator();
+/// let src = Buffer::new_host_visible(&context.device(), &allocator, 256, BufferUsage::TRANSFER_SRC)?;
+/// let dst = Buffer::new_device_local(&context.device(), &allocator, 256, BufferUsage::TRANSFER_DST)?;
+///
+/// let mut recorder = CommandRecorder::new(&context)?;
+/// recorder.copy_buffer(&src, &dst)?;
+/// recorder.submit_and_wait(&context.graphics_queue())?;
+/// # Ok::<(), gamma_vk::GammaVkError>(())
+/// ```
+pub struct CommandRecorder {
+    /// The underlying Vulkano command buffer builder
+    builder: AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+    /// Whether the command buffer may be submitted more than once
+    reusable: bool,
+    /// Number of debug label regions currently open
+    label_depth: u32,
+}
+
+impl CommandRecorder {
+    /// Begin recording a command buffer for the context's graphics queue family
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the command buffer cannot be allocated.
+    pub fn new(context: &VulkanContext) -> Result<Self> {
+        Self::with_usage(context, CommandBufferUsage::OneTimeSubmit)
+    }
+
+    /// Begin recording a command buffer that can be submitted many times
+    ///
+    /// Use this to record a static command list once and replay it every frame.
+    /// Finish recording with [`build_reusable`](Self::build_reusable) and submit
+    /// the result with [`ReusableCommandBuffer::execute`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the command buffer cannot be allocated.
+    pub fn new_reusable(context: &VulkanContext) -> Result<Self> {
+        Self::with_usage(context, CommandBufferUsage::MultipleSubmit)
+    }
+
+    fn with_usage(context: &VulkanContext, usage: CommandBufferUsage) -> Result<Self> {
+        let builder = AutoCommandBufferBuilder::primary(
+            context.command_buffer_allocator(),
+            context.graphics_queue_family_index(),
+            usage,
+        )
+        .map_err(|e| {
+            GammaVkError::command_execution(format!("Failed to allocate command buffer: {}", e))
+        })?;
+
+        Ok(Self {
+            builder,
+            reusable: usage == CommandBufferUsage::MultipleSubmit,
+            label_depth: 0,
+        })
+    }
+
+    /// Record a copy of `src` into `dst`
+    ///
+    /// Copies `min(src.size(), dst.size())` bytes starting at offset 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `src` lacks `TRANSFER_SRC` usage or `dst` lacks
+    /// `TRANSFER_DST` usage.
+    pub fn copy_buffer(&mut self, src: &Buffer, dst: &Buffer) -> Result<&mut Self> {
+        self.builder
+            .copy_buffer(CopyBufferInfo::buffers(
+                src.inner().clone(),
+                dst.inner().clone(),
+            ))
+            .map_err(|e| {
+                GammaVkError::command_execution(format!("Failed to record buffer copy: {}", e))
+            })?;
+        Ok(self)
+    }
+
+    /// Record a copy of several byte ranges of `src` into `dst` as one command
+    ///
+    /// All regions are copied by a single `vkCmdCopyBuffer`, which suits scatter
+    /// and gather operations such as mesh compaction or partial updates far better
+    /// than one copy per region. Bytes of `dst` outside every region are untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `regions` is empty, a region is empty or extends past
+    /// the end of `src` or `dst`, `src` lacks `TRANSFER_SRC` usage, `dst` lacks
+    /// `TRANSFER_DST` usage, or regions overlap when `src` and `dst` are the same
+    /// buffer.
+    pub fn copy_buffer_regions(
+        &mut self,
+        src: &Buffer,
+        dst: &Buffer,
+        regions: &[BufferCopy],
+    ) -> Result<&mut Self> {
+        if regions.is_empty() {
+            return Err(GammaVkError::command_execution(
+                "Buffer copy needs at least one region",
+            ));
+        }
+
+        for (index, region) in regions.iter().enumerate() {
+            let fits = |offset: u64, size: u64| {
+                offset
+                    .checked_add(region.size)
+                    .is_some_and(|end| end <= size)
+            };
+            if region.size == 0
+                || !fits(region.src_offset, src.size())
+                || !fits(region.dst_offset, dst.size())
+            {
+                return Err(GammaVkError::command_execution(format!(
+                    "Copy region {} ({} bytes from offset {} to offset {}) is empty or exceeds \
+                     source size {} or destination size {}",
+                    index,
+                    region.size,
+                    region.src_offset,
+                    region.dst_offset,
+                    src.size(),
+                    dst.size()
+                )));
+            }
+        }
+
+        self.builder
+            .copy_buffer(CopyBufferInfo {
+                regions: regions.iter().cloned().collect(),
+                ..CopyBufferInfo::buffers(src.inner().clone(), dst.inner().clone())
+            })
+            .map_err(|e| {
+                GammaVkError::command_execution(format!("Failed to record buffer copy: {}", e))
+            })?;
+        Ok(self)
+    }
+
+    /// Record a fill of `buffer` with a repeated 32-bit `value`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer size is not a multiple of 4 or the buffer
+    /// lacks `TRANSFER_DST` usage.
+    pub fn fill_buffer(&mut self, buffer: &Buffer, value: u32) -> Result<&mut Self> {
+        if !buffer.size().is_multiple_of(4) {
+            return Err(GammaVkError::command_execution(format!(
+                "Buffer size {} is not a multiple of 4; fills write whole 32-bit words",
+                buffer.size()
+            )));
+        }
+
+        self.builder
+            .fill_buffer(buffer.inner().clone().reinterpret::<[u32]>(), value)
+            .map_err(|e| {
+                GammaVkError::command_execution(format!("Failed to record buffer fill: {}", e))
+            })?;
+        Ok(self)
+    }
+
+    /// Record a complete draw of a mesh
+    ///
+    /// Binds `pipeline`, the optional descriptor set at set 0, the vertex buffer at
+    /// binding 0 and, for indexed meshes, the index buffer; then records `draw` or
+    /// `draw_indexed` according to [`DrawDescriptor::command`]. Must be recorded
+    /// inside a render pass compatible with `pipeline`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any bind or the draw fails validation.
+    pub fn draw_mesh(
+        &mut self,
+        pipeline: &Arc<GraphicsPipeline>,
+        descriptor_set: Option<Arc<DescriptorSet>>,
+        mesh: &DrawDescriptor<'_>,
+    ) -> Result<&mut Self> {
+        self.builder
+            .bind_pipeline_graphics(pipeline.clone())
+            .map_err(|e| {
+                GammaVkError::command_execution(format!("Failed to bind pipeline: {}", e))
+            })?;
+
+        if let Some(descriptor_set) = descriptor_set {
+            self.builder
+                .bind_descriptor_sets(
+                    PipelineBindPoint::Graphics,
+                    pipeline.layout().clone(),
+                    0,
+                    descriptor_set,
+                )
+                .map_err(|e| {
+                    GammaVkError::command_execution(format!("Failed to bind descriptor set: {}", e))
+                })?;
+        }
+
+        mesh.vertex_buffer.bind(self, 0)?;
+        if let Some((index_buffer, index_type)) = mesh.indices {
+            index_buffer.bind(self, index_type)?;
+        }
+
+        // Safety: buffer contents and shader resource accesses are the caller's
+        // responsibility, as with any draw; Vulkano validates the remaining state.
+        let result = match mesh.command() {
+            DrawCommand::Draw { vertex_count } => unsafe {
+                self.builder.draw(vertex_count, 1, 0, 0)
+            },
+            DrawCommand::DrawIndexed { index_count, .. } => unsafe {
+                self.builder.draw_indexed(index_count, 1, 0, 0, 0)
+            },
+        };
+        result.map_err(|e| {
+            GammaVkError::command_execution(format!("Failed to record draw: {}", e))
+        })?;
+
+        Ok(self)
+    }
+
+    /// Record a compute dispatch whose workgroup counts are read from a buffer
+    ///
+    /// Binds `pipeline` and the optional descriptor set at set 0, then dispatches
+    /// with the [`DispatchIndirectCommand`] (three `u32` counts, 12 bytes) stored at
+    /// byte `offset` of `indirect_buffer`. This lets an earlier GPU pass decide how
+    /// much work a later one does without a CPU round trip.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `indirect_buffer` lacks `INDIRECT_BUFFER` usage,
+    /// `offset` is not a multiple of 4, fewer than 12 bytes remain at `offset`, or
+    /// a bind or the dispatch fails validation.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gamma_vk::{Buffer, CommandRecorder};
+    /// use std::sync::Arc;
+    /// use vulkano::pipeline::ComputePipeline;
+    ///
+    /// # fn example(recorder: &mut CommandRecorder, pipeline: &Arc<ComputePipeline>, counts: &Buffer) -> gamma_vk::Result<()> {
+    /// // `counts` was written by an earlier culling pass
+    /// recorder.dispatch_indirect(pipeline, None, counts, 0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dispatch_indirect(
+        &mut self,
+        pipeline: &Arc<ComputePipeline>,
+        descriptor_set: Option<Arc<DescriptorSet>>,
+        indirect_buffer: &Buffer,
+        offset: u64,
+    ) -> Result<&mut Self> {
+        const COMMAND_SIZE: u64 = size_of::<DispatchIndirectCommand>() as u64;
+
+        if !indirect_buffer
+            .usage()
+            .contains(BufferUsage::INDIRECT_BUFFER)
+        {
+            return Err(GammaVkError::command_execution(
+                "Indirect dispatch buffer is missing required INDIRECT_BUFFER usage",
+            ));
+        }
+        if !offset.is_multiple_of(4)
+            || offset
+                .checked_add(COMMAND_SIZE)
+                .is_none_or(|end| end > indirect_buffer.size())
+        {
+            return Err(GammaVkError::command_execution(format!(
+                "Indirect dispatch offset {} must be a multiple of 4 with {} bytes \
+                 available in a buffer of size {}",
+                offset,
+                COMMAND_SIZE,
+                indirect_buffer.size()
+            )));
+        }
+
+        self.builder
+            .bind_pipeline_compute(pipeline.clone())
+            .map_err(|e| {
+                GammaVkError::command_execution(format!("Failed to bind pipeline: {}", e))
+            })?;
+
+        if let Some(descriptor_set) = descriptor_set {
+            self.builder
+                .bind_descriptor_sets(
+                    PipelineBindPoint::Compute,
+                    pipeline.layout().clone(),
+                    0,
+                    descriptor_set,
+                )
+                .map_err(|e| {
+                    GammaVkError::command_execution(format!("Failed to bind descriptor set: {}", e))
+                })?;
+        }
+
+        let command = indirect_buffer
+            .inner()
+            .clone()
+            .slice(offset..offset + COMMAND_SIZE)
+            .reinterpret::<[DispatchIndirectCommand]>();
+
+        // Safety: the workgroup counts and shader resource accesses are the
+        // caller's responsibility, as with any dispatch; Vulkano validates the
+        // remaining state.
+        unsafe { self.builder.dispatch_indirect(command) }.map_err(|e| {
+            GammaVkError::command_execution(format!("Failed to record indirect dispatch: {}", e))
+        })?;
+
+        Ok(self)
+    }
+
+    /// Begin dynamic rendering into the given color attachments
+    ///
+    /// Attachments are stored at the end of rendering. With `clear_color` set they
+    /// are cleared to it first; otherwise their previous contents are loaded. The
+    /// render area covers the attachments' full extent. Close the scope with
+    /// [`end_rendering`](Self::end_rendering).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if dynamic rendering is not enabled on the device, or the
+    /// attachments fail validation (for example, mismatched extents).
+    pub fn begin_rendering(
+        &mut self,
+        color_attachments: &[Arc<ImageView>],
+        clear_color: Option<[f32; 4]>,
+    ) -> Result<&mut Self> {
+        if !crate::render::is_enabled(self.builder.device()) {
+            return Err(GammaVkError::command_execution(
+                "Dynamic rendering is not enabled; build the context with enable_dynamic_rendering()",
+            ));
+        }
+
+        let color_attachments = color_attachments
+            .iter()
+            .map(|view| {
+                Some(RenderingAttachmentInfo {
+                    load_op: if clear_color.is_some() {
+                        AttachmentLoadOp::Clear
+                    } else {
+                        AttachmentLoadOp::Load
+                    },
+                    store_op: AttachmentStoreOp::Store,
+                    clear_value: clear_color.map(Into::into),
+                    ..RenderingAttachmentInfo::image_view(view.clone())
+                })
+            })
+            .collect();
+
+        self.builder
+            .begin_rendering(RenderingInfo {
+                color_attachments,
+                ..Default::default()
+            })
+            .map_err(|e| {
+                GammaVkError::command_execution(format!("Failed to begin rendering: {}", e))
+            })?;
+        Ok(self)
+    }
+
+    /// End the dynamic rendering scope opened by [`begin_rendering`](Self::begin_rendering)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no dynamic rendering scope is active.
+    pub fn end_rendering(&mut self) -> Result<&mut Self> {
+        self.builder.end_rendering().map_err(|e| {
+            GammaVkError::command_execution(format!("Failed to end rendering: {}", e))
+        })?;
+        Ok(self)
+    }
+
+    /// Record `record` between a pair of GPU timestamps
+    ///
+    /// Returns the scope's index in `queries`, or `None` without writing timestamps
+    /// when the device does not support them; `record` runs either way. Read the
+    /// durations with [`VulkanContext::resolve_timestamps`] after the submission
+    /// completes. Must be recorded outside a render pass.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `queries` is full, a timestamp cannot be recorded, or
+    /// `record` fails.
+    pub fn time_scope(
+        &mut self,
+        queries: &mut QueryPool,
+        label: impl Into<String>,
+        record: impl FnOnce(&mut Self) -> Result<()>,
+    ) -> Result<Option<u32>> {
+        let Some((pool, start)) = queries.begin_scope(label.into())? else {
+            record(self)?;
+            return Ok(None);
+        };
+
+        let timestamp_error =
+            |e| GammaVkError::command_execution(format!("Failed to record timestamp: {}", e));
+
+        // Safety: the scope's queries were just reserved. Reserved queries are
+        // only handed out again after the unsafe `QueryPool::clear`, whose caller
+        // guarantees that no submission using them is still pending.
+        unsafe {
+            self.builder
+                .reset_query_pool(pool.clone(), start..start + 2)
+                .map_err(timestamp_error)?;
+            self.builder
+                .write_timestamp(pool.clone(), start, PipelineStage::TopOfPipe)
+                .map_err(timestamp_error)?;
+        }
+
+        record(self)?;
+
+        // Safety: as above
+        unsafe {
+            self.builder
+                .write_timestamp(pool, start + 1, PipelineStage::BottomOfPipe)
+                .map_err(timestamp_error)?;
+        }
+
+        Ok(Some(start / 2))
+    }
+
+    /// Record the commands from `record` inside a named debug label region
+    ///
+    /// Capture tools such as RenderDoc and Nsight show the region as a collapsible
+    /// group named `name`. When the instance was created without the
+    /// `ext_debug_utils` extension, `record` runs with no label and no error.
+    /// Regions may be nested; [`debug_label_depth`](Self::debug_label_depth)
+    /// reports how many are open.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the label cannot be recorded or `record` fails. The
+    /// region is closed before a `record` error is returned.
+    pub fn debug_label(
+        &mut self,
+        name: impl Into<String>,
+        record: impl FnOnce(&mut Self) -> Result<()>,
+    ) -> Result<&mut Self> {
+        let enabled = self
+            .builder
+            .device()
+            .instance()
+            .enabled_extensions()
+            .ext_debug_utils;
+        if !enabled {
+            record(self)?;
+            return Ok(self);
+        }
+
+        let label_error =
+            |e| GammaVkError::command_execution(format!("Failed to record debug label: {}", e));
+
+        self.builder
+            .begin_debug_utils_label(DebugUtilsLabel {
+                label_name: name.into(),
+                ..Default::default()
+            })
+            .map_err(label_error)?;
+        self.label_depth += 1;
+
+        let recorded = record(self);
+
+        // Safety: the region was opened above in this same command buffer
+        let ended = unsafe { self.builder.end_debug_utils_label() };
+        self.label_depth -= 1;
+        ended.map_err(label_error)?;
+
+        recorded.map(|()| self)
+    }
+
+    /// Get the number of debug label regions currently open
+    ///
+    /// Only regions actually recorded count, so this stays zero when the instance
+    /// lacks `ext_debug_utils`.
+    pub fn debug_label_depth(&self) -> u32 {
+        self.label_depth
+    }
+
+    /// Get the underlying Vulkano command buffer builder
+    ///
+    /// This provides an escape hatch for recording commands not yet wrapped
+    /// by Gamma-VK.
+    pub fn builder_mut(&mut self) -> &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer> {
+        &mut self.builder
+    }
+
+    /// Submit the recorded commands and block until the GPU finishes executing them
+    ///
+    /// This waits indefinitely. Prefer [`submit_and_wait_timeout`](Self::submit_and_wait_timeout)
+    /// in tools and tests where a faulting GPU must not hang the process.
+    pub fn submit_and_wait(self, queue: &Arc<Queue>) -> Result<()> {
+        self.submit_and_wait_impl(queue, None)
+    }
+
+    /// Submit the recorded commands and wait at most `timeout` for them to finish
+    ///
+    /// The timeout is passed to the fence wait with nanosecond precision.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if submission fails or the timeout elapses. On timeout the
+    /// in-flight submission is intentionally leaked rather than waited on, so any
+    /// resources it uses stay locked for the rest of the program.
+    pub fn submit_and_wait_timeout(self, queue: &Arc<Queue>, timeout: Duration) -> Result<()> {
+        self.submit_and_wait_impl(queue, Some(timeout))
+    }
+
+    /// Finish recording a reusable command buffer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the recorder was not created with
+    /// [`new_reusable`](Self::new_reusable) or the command buffer fails to build.
+    pub fn build_reusable(self) -> Result<ReusableCommandBuffer> {
+        if !self.reusable {
+            return Err(GammaVkError::command_execution(
+                "Command buffer was recorded for one-time submission; use CommandRecorder::new_reusable",
+            ));
+        }
+
+        Ok(ReusableCommandBuffer {
+            command_buffer: self.build()?,
+        })
+    }
+
+    /// Finish recording and return the command buffer without submitting it
+    ///
+    /// Use this to submit several command buffers at once with
+    /// [`VulkanContext::submit_batch`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the command buffer fails to build, for example because
+    /// a render pass or rendering scope was left open.
+    pub fn build(self) -> Result<Arc<PrimaryAutoCommandBuffer>> {
+        self.builder.build().map_err(|e| {
+            GammaVkError::command_execution(format!("Failed to build command buffer: {}", e))
+        })
+    }
+
+    fn submit_and_wait_impl(self, queue: &Arc<Queue>, timeout: Option<Duration>) -> Result<()> {
+        submit_and_wait(self.build()?, queue, timeout)
+    }
+}
+
+impl std::fmt::Debug for CommandRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommandRecorder")
+            .field("reusable", &self.reusable)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A recorded command buffer that can be submitted any number of times
+///
+/// Created by [`CommandRecorder::build_reusable`]. The command buffer keeps every
+/// resource it references alive, but their contents must stay valid for all
+/// submissions: a buffer rewritten between executions is read with its new
+/// contents, and resources must not be in use by another submission.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gamma_vk::{Buffer, CommandRecorder, VulkanContext};
+/// use vulkano::buffer::BufferUsage;
+///
+/// let context = VulkanContext::new()?;
+/// let allocator = context.memory_allocator();
+/// let buffer = Buffer::new_device_local(&context.device(), &allocator, 256, BufferUsage::TRANSFER_DST)?;
+///
+/// let mut recorder = CommandRecorder::new_reusable(&context)?;
+/// recorder.fill_buffer(&buffer, 0)?;
+/// let clear = recorder.build_reusable()?;
+///
+/// for _frame in 0..3 {
+///     clear.execute(&context.graphics_queue())?;
+/// }
+/// # Ok::<(), gamma_vk::GammaVkError>(())
+/// ```
+#[derive(Debug)]
+pub struct ReusableCommandBuffer {
+    command_buffer: Arc<PrimaryAutoCommandBuffer>,
+}
+
+impl ReusableCommandBuffer {
+    /// Submit the commands and block until the GPU finishes executing them
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if submission fails or a referenced resource is still in
+    /// use by another submission.
+    pub fn execute(&self, queue: &Arc<Queue>) -> Result<()> {
+        submit_and_wait(self.command_buffer.clone(), queue, None)
+    }
+
+    /// Get the underlying Vulkano command buffer
+    pub fn inner(&self) -> &Arc<PrimaryAutoCommandBuffer> {
+        &self.command_buffer
+    }
+}
+
+/// Submit a built command buffer and wait for it, with an optional timeout
+fn submit_and_wait(
+    command_buffer: Arc<PrimaryAutoCommandBuffer>,
+    queue: &Arc<Queue>,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    profile_scope!("Command buffer submission");
+    let future = command_buffer
+        .execute(queue.clone())
+        .map_err(|e| {
+            GammaVkError::command_execution(format!("Failed to submit command buffer: {}", e))
+        })?
+        .then_signal_fence_and_flush()
+        .map_err(|e| {
+            GammaVkError::command_execution(format!("Failed to flush command buffer: {}", e))
+        })?;
+
+    match future.wait(timeout) {
+        Ok(()) => Ok(()),
+        Err(Validated::Error(VulkanError::Timeout)) => {
+            // Dropping the future would block on the fence without a timeout
+            std::mem::forget(future);
+            Err(GammaVkError::command_execution(format!(
+                "Timed out after {:?} waiting for command buffer to complete",
+                timeout.unwrap_or_default()
+            )))
+        }
+        Err(e) => Err(GammaVkError::command_execution(format!(
+            "Failed to wait for command buffer: {}",
+            e
+        ))),
+    }
+}
@@ -3,15 +3,257 @@
 //! This module provides the main VulkanContext struct that manages Vulkan instance
 //! creation and provides a foundation for all graphics operations.
 
-use std::sync::Arc;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 use vulkano::{
-    Version, VulkanLibrary,
-    device::{Device, DeviceCreateInfo, Queue, QueueCreateInfo, physical::PhysicalDevice},
+    DeviceSize, Validated, Version, VulkanError, VulkanLibrary,
+    buffer::BufferUsage,
+    command_buffer::{PrimaryAutoCommandBuffer, allocator::StandardCommandBufferAllocator},
+    descriptor_set::{
+        DescriptorSet, WriteDescriptorSet,
+        allocator::StandardDescriptorSetAllocator,
+        layout::{DescriptorSetLayout, DescriptorType},
+    },
+    device::{
+        Device, DeviceCreateInfo, DeviceExtensions, DeviceFeatures, Queue, QueueCreateInfo,
+        QueueFlags,
+        physical::{PhysicalDevice, PhysicalDeviceType},
+    },
+    format::{Format, FormatFeatures, FormatProperties},
+    image::{SampleCount, SampleCounts},
     instance::{Instance, InstanceCreateInfo, InstanceExtensions},
-    memory::allocator::StandardMemoryAllocator,
+    memory::{
+        MemoryHeapFlags, MemoryPropertyFlags,
+        allocator::{GenericMemoryAllocatorCreateInfo, StandardMemoryAllocator, Suballocator},
+    },
+    sync::{self, GpuFuture, future::FenceSignalFuture},
 };
 
-use crate::{GammaVkError, Result};
+use vulkano::swapchain::{ColorSpace, PresentMode, Surface, SurfaceInfo as VulkanoSurfaceInfo};
+#[cfg(feature = "windowing")]
+use winit::raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+
+use crate::{Buffer, FrameContext, GammaVkError, QueryPool, Result};
+
+/// Controls whether the instance enables portability enumeration
+///
+/// Portability enumeration (`VK_KHR_portability_enumeration`) exposes non-conformant
+/// implementations such as MoltenVK. On native Linux/Windows loaders it can also pull
+/// in software or portability drivers and change device enumeration order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PortabilityMode {
+    /// Enable portability enumeration only where it is needed (macOS)
+    #[default]
+    Auto,
+    /// Always try portability enumeration, falling back to a standard instance
+    Always,
+    /// Never enable portability enumeration
+    Never,
+}
+
+impl PortabilityMode {
+    /// Whether instance creation should attempt portability enumeration
+    pub fn is_enabled(self) -> bool {
+        match self {
+            PortabilityMode::Auto => cfg!(target_os = "macos"),
+            PortabilityMode::Always => true,
+            PortabilityMode::Never => false,
+        }
+    }
+}
+
+/// Capabilities of a single queue family on the physical device
+///
+/// Reported by [`VulkanContext::queue_families`] for users who want to make their
+/// own queue-assignment decisions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueFamilyInfo {
+    /// The queue family index
+    pub index: u32,
+    /// The number of queues available in this family
+    pub queue_count: u32,
+    /// The operations supported by queues in this family
+    pub queue_flags: QueueFlags,
+}
+
+impl QueueFamilyInfo {
+    /// Whether queues in this family support graphics operations
+    pub fn supports_graphics(&self) -> bool {
+        self.queue_flags.intersects(QueueFlags::GRAPHICS)
+    }
+
+    /// Whether queues in this family support compute operations
+    pub fn supports_compute(&self) -> bool {
+        self.queue_flags.intersects(QueueFlags::COMPUTE)
+    }
+
+    /// Whether queues in this family support transfer operations
+    ///
+    /// Graphics and compute queues implicitly support transfers even when the
+    /// `TRANSFER` flag is not reported.
+    pub fn supports_transfer(&self) -> bool {
+        self.queue_flags
+            .intersects(QueueFlags::TRANSFER | QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+    }
+
+    /// Whether queues in this family support sparse memory binding
+    pub fn supports_sparse_binding(&self) -> bool {
+        self.queue_flags.intersects(QueueFlags::SPARSE_BINDING)
+    }
+}
+
+/// A snapshot of the live allocations held by a memory allocator
+///
+/// Returned by [`VulkanContext::allocation_stats`]. A count that keeps growing
+/// across frames signals leaked buffers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocationStats {
+    /// The number of suballocations currently alive
+    pub live_allocations: usize,
+    /// The bytes occupied by live suballocations, including alignment padding
+    pub allocated_bytes: DeviceSize,
+    /// The bytes of device memory reserved in blocks, used or not
+    pub reserved_bytes: DeviceSize,
+}
+
+/// What a surface supports, for choosing swapchain parameters
+///
+/// Returned by [`VulkanContext::surface_capabilities`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SurfaceInfo {
+    /// Supported image format and color space pairs
+    pub formats: Vec<(Format, ColorSpace)>,
+    /// Supported presentation modes; always includes `Fifo`
+    pub present_modes: Vec<PresentMode>,
+    /// Minimum number of swapchain images
+    pub min_image_count: u32,
+    /// Maximum number of swapchain images, or `None` if unlimited
+    pub max_image_count: Option<u32>,
+    /// Current surface size, or `None` if the swapchain decides it
+    pub current_extent: Option<[u32; 2]>,
+    /// Smallest supported swapchain image size
+    pub min_image_extent: [u32; 2],
+    /// Largest supported swapchain image size
+    pub max_image_extent: [u32; 2],
+}
+
+impl SurfaceInfo {
+    /// Pick a swapchain format, preferring `B8G8R8A8_SRGB` in the sRGB color space
+    ///
+    /// Falls back to the first supported pair. Returns `None` only if the surface
+    /// reports no formats.
+    pub fn preferred_format(&self) -> Option<(Format, ColorSpace)> {
+        let preferred = (Format::B8G8R8A8_SRGB, ColorSpace::SrgbNonLinear);
+        if self.formats.contains(&preferred) {
+            Some(preferred)
+        } else {
+            self.formats.first().copied()
+        }
+    }
+
+    /// Pick a present mode: `Fifo`, which every surface supports and which vsyncs
+    pub fn preferred_present_mode(&self) -> PresentMode {
+        PresentMode::Fifo
+    }
+
+    /// Pick an image count one above the minimum, within the maximum
+    ///
+    /// The extra image lets the application render ahead instead of waiting on
+    /// the presentation engine.
+    pub fn preferred_image_count(&self) -> u32 {
+        let count = self.min_image_count + 1;
+        self.max_image_count.map_or(count, |max| count.min(max))
+    }
+}
+
+/// A stage of context creation checked by [`VulkanContext::diagnose`]
+///
+/// Stages are listed in the order they are attempted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DiagnosticStage {
+    /// Loading the Vulkan loader library
+    LibraryLoad,
+    /// Creating the Vulkan instance
+    InstanceCreation,
+    /// Finding at least one physical device
+    PhysicalDeviceEnumeration,
+    /// Finding a physical device with a graphics queue family
+    GraphicsQueue,
+    /// Creating the logical device
+    DeviceCreation,
+}
+
+/// The outcome of one [`DiagnosticStage`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageReport {
+    /// The stage that was attempted
+    pub stage: DiagnosticStage,
+    /// Whether the stage succeeded
+    pub success: bool,
+    /// What was found on success, or why the stage failed
+    pub message: String,
+}
+
+/// Per-stage results of attempting context creation
+///
+/// Returned by [`VulkanContext::diagnose`]. Stages run in order and stop at the
+/// first failure, so the last report is either the failing stage or
+/// [`DiagnosticStage::DeviceCreation`] on a healthy system.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContextDiagnostics {
+    stages: Vec<StageReport>,
+}
+
+impl ContextDiagnostics {
+    /// Get the report of every attempted stage, in order
+    pub fn stages(&self) -> &[StageReport] {
+        &self.stages
+    }
+
+    /// Get the last stage that was attempted
+    pub fn final_stage(&self) -> Option<DiagnosticStage> {
+        self.stages.last().map(|report| report.stage)
+    }
+
+    /// Get the report of the stage that failed, if any
+    pub fn failure(&self) -> Option<&StageReport> {
+        self.stages.iter().find(|report| !report.success)
+    }
+
+    /// Check whether every stage through device creation succeeded
+    pub fn is_healthy(&self) -> bool {
+        self.failure().is_none() && self.final_stage() == Some(DiagnosticStage::DeviceCreation)
+    }
+
+    fn pass(&mut self, stage: DiagnosticStage, message: impl Into<String>) {
+        self.stages.push(StageReport {
+            stage,
+            success: true,
+            message: message.into(),
+        });
+    }
+
+    fn fail(mut self, stage: DiagnosticStage, message: impl Into<String>) -> Self {
+        self.stages.push(StageReport {
+            stage,
+            success: false,
+            message: message.into(),
+        });
+        self
+    }
+}
+
+impl std::fmt::Display for ContextDiagnostics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for report in &self.stages {
+            let status = if report.success { "ok" } else { "FAILED" };
+            writeln!(f, "{:?}: {} - {}", report.stage, status, report.message)?;
+        }
+        Ok(())
+    }
+}
 
 /// Builder for creating a VulkanContext with custom configuration
 ///
@@ -41,6 +283,17 @@ pub struct VulkanContextBuilder {
     enable_validation: bool,
     prefer_discrete_gpu: bool,
     required_extensions: Vec<String>,
+    allocator_block_size: Option<DeviceSize>,
+    allocator_memory_type_bits: u32,
+    portability: PortabilityMode,
+    creation_retries: u32,
+    instance_extensions: InstanceExtensions,
+    device_extensions: DeviceExtensions,
+    forced_device_uuid: Option<[u8; 16]>,
+    api_version: Option<Version>,
+    dynamic_rendering: bool,
+    queue_priority: f32,
+    min_device_local_memory: Option<DeviceSize>,
 }
 
 impl Default for VulkanContextBuilder {
@@ -53,6 +306,17 @@ impl Default for VulkanContextBuilder {
             enable_validation: cfg!(debug_assertions),
             prefer_discrete_gpu: true,
             required_extensions: Vec::new(),
+            allocator_block_size: None,
+            allocator_memory_type_bits: u32::MAX,
+            portability: PortabilityMode::Auto,
+            creation_retries: 1,
+            instance_extensions: InstanceExtensions::empty(),
+            device_extensions: DeviceExtensions::empty(),
+            forced_device_uuid: None,
+            api_version: None,
+            dynamic_rendering: false,
+            queue_priority: 1.0,
+            min_device_local_memory: None,
         }
     }
 }
@@ -123,9 +387,127 @@ impl VulkanContextBuilder {
         // self
     }
 
+    /// Set the block size used by the memory allocator for every memory type
+    ///
+    /// By default the allocator uses 256 MiB blocks for heaps of 1 GiB or more and
+    /// 64 MiB blocks for smaller heaps. Smaller blocks waste less memory for apps that
+    /// make few, tiny allocations, at the cost of more `vkAllocateMemory` calls as usage
+    /// grows. Larger blocks reduce driver allocations for apps with many or large
+    /// resources, but reserve more memory up front. Allocations larger than half the
+    /// block size always get a dedicated block.
+    pub fn allocator_block_size(mut self, block_size: DeviceSize) -> Self {
+        self.allocator_block_size = Some(block_size);
+        self
+    }
+
+    /// Restrict which memory type indices the memory allocator may allocate from
+    ///
+    /// Each set bit allows the memory type at that index. Memory types that cannot be
+    /// suballocated safely (lazily allocated, protected, device coherent, RDMA capable)
+    /// are always excluded regardless of this mask.
+    pub fn allocator_memory_type_bits(mut self, memory_type_bits: u32) -> Self {
+        self.allocator_memory_type_bits = memory_type_bits;
+        self
+    }
+
+    /// Set when portability enumeration is enabled (default: `PortabilityMode::Auto`)
+    pub fn portability(mut self, mode: PortabilityMode) -> Self {
+        self.portability = mode;
+        self
+    }
+
+    /// Set how many extra attempts are made when instance or device creation fails
+    /// with a transient error (default: 1)
+    ///
+    /// Only device-lost and initialization-failed errors are retried. Errors such as
+    /// a missing Vulkan library are never retried.
+    pub fn creation_retries(mut self, retries: u32) -> Self {
+        self.creation_retries = retries;
+        self
+    }
+
+    /// Set the Vulkan API version to request for the instance
+    ///
+    /// Request 1.2 or 1.3 to use core features gated on the instance version, such
+    /// as timeline semaphores or dynamic rendering. By default the highest version
+    /// supported by the library is used. Building fails if the library does not
+    /// support the requested version.
+    pub fn api_version(mut self, version: Version) -> Self {
+        self.api_version = Some(version);
+        self
+    }
+
+    /// Select exactly the physical device with this UUID
+    ///
+    /// Makes device selection reproducible on machines with several drivers, such
+    /// as CI runners with both software and hardware implementations. Building
+    /// fails if no device reports a matching UUID.
+    pub fn force_device_uuid(mut self, uuid: [u8; 16]) -> Self {
+        self.forced_device_uuid = Some(uuid);
+        self
+    }
+
+    /// Enable dynamic rendering so pipelines can target attachments without a render pass
+    ///
+    /// Only devices supporting the `dynamic_rendering` feature are considered. On
+    /// devices below Vulkan 1.3 the `VK_KHR_dynamic_rendering` extension is enabled as
+    /// well. See [`crate::render::DynamicRendering`].
+    pub fn enable_dynamic_rendering(mut self) -> Self {
+        self.dynamic_rendering = true;
+        self
+    }
+
+    /// Set the scheduling priority of the graphics queue (default: 1.0)
+    ///
+    /// Priorities range from 0.0 to 1.0 and are only a hint: implementations may
+    /// give more execution time to higher-priority queues of the same device, for
+    /// example to keep presentation responsive next to background work. Building
+    /// fails if the priority is outside `[0.0, 1.0]`.
+    pub fn queue_priority(mut self, priority: f32) -> Self {
+        self.queue_priority = priority;
+        self
+    }
+
+    /// Only select physical devices with a device-local heap of at least `bytes`
+    ///
+    /// Devices are judged by their largest device-local memory heap. This rules out
+    /// small integrated GPUs on machines that also have a discrete one. Building
+    /// fails, listing each candidate's heap size, if no device qualifies.
+    pub fn min_device_local_memory(mut self, bytes: DeviceSize) -> Self {
+        self.min_device_local_memory = Some(bytes);
+        self
+    }
+
     /// Build the VulkanContext with the configured settings
     pub fn build(self) -> Result<VulkanContext> {
-        VulkanContext::new_with_config(self)
+        VulkanContext::new_with_config(self, None)
+    }
+
+    /// Build the context for presenting to a surface created on its instance
+    ///
+    /// Enables `surface_extensions` on the instance and `khr_swapchain` on the
+    /// device, then calls `create_surface` with the new instance. Only devices with
+    /// a queue family that can present to that surface are considered. A family
+    /// supporting both graphics and presentation is preferred; if none exists, a
+    /// separate present family is used and
+    /// [`VulkanContext::present_queue`] differs from the graphics queue. The surface
+    /// is only used for selection and is dropped before this returns.
+    ///
+    /// [`VulkanContext::for_window`] covers the common case of a winit window.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GammaVkError` if `create_surface` fails, no device can present to
+    /// the surface, or context creation otherwise fails.
+    #[cfg(feature = "windowing")]
+    pub fn build_with_surface(
+        mut self,
+        surface_extensions: InstanceExtensions,
+        create_surface: impl FnOnce(Arc<Instance>) -> Result<Arc<Surface>>,
+    ) -> Result<VulkanContext> {
+        self.instance_extensions = self.instance_extensions.union(&surface_extensions);
+        self.device_extensions.khr_swapchain = true;
+        VulkanContext::new_with_config(self, Some(Box::new(create_surface)))
     }
 }
 
@@ -147,8 +529,22 @@ pub struct VulkanContext {
     graphics_queue: Arc<Queue>,
     /// The graphics queue family index
     graphics_queue_family_index: u32,
+    /// The priority the graphics queue was created with
+    queue_priority: f32,
+    /// The queue used for presentation, when built for a surface
+    present_queue: Option<Arc<Queue>>,
     /// The memory allocator for GPU memory management
     memory_allocator: Arc<StandardMemoryAllocator>,
+    /// Allocator block size override, reused by `create_allocator`
+    allocator_block_size: Option<DeviceSize>,
+    /// Allocator memory type mask, reused by `create_allocator`
+    allocator_memory_type_bits: u32,
+    /// The command buffer allocator for command recording
+    command_buffer_allocator: Arc<StandardCommandBufferAllocator>,
+    /// The descriptor set allocator shared by all descriptor set creation
+    descriptor_set_allocator: Arc<StandardDescriptorSetAllocator>,
+    /// Reusable host-visible staging buffer, grown on demand
+    staging_buffer: Mutex<Option<Buffer>>,
 }
 
 impl VulkanContext {
@@ -173,8 +569,9 @@ impl VulkanContext {
 
     /// Create a new VulkanContext with proper Vulkan initialization
     ///
-    /// This method attempts to create a Vulkan instance with MoltenVK portability
-    /// support first, then falls back to standard Vulkan if that fails.
+    /// On macOS this method attempts to create a Vulkan instance with MoltenVK
+    /// portability support first, then falls back to standard Vulkan if that fails.
+    /// See [`PortabilityMode`] to change this behavior.
     ///
     /// # Errors
     ///
@@ -195,100 +592,332 @@ impl VulkanContext {
         Self::builder().build()
     }
 
-    /// Create a new VulkanContext with a specific configuration
-    fn new_with_config(config: VulkanContextBuilder) -> Result<Self> {
-        // Load the Vulkan library
-        let library = VulkanLibrary::new().map_err(GammaVkError::LibraryLoad)?;
+    /// Create a VulkanContext ready for swapchain creation against a window
+    ///
+    /// Enables exactly the surface instance extensions the window's platform needs,
+    /// plus the `khr_swapchain` device extension, and selects a device and queue
+    /// family that can present to the window. A temporary surface is created for
+    /// the selection; create your own with [`Surface::from_window`] for the swapchain.
+    /// See [`VulkanContextBuilder::build_with_surface`] for how queues are chosen.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GammaVkError` if the window's display handle is unavailable, the
+    /// surface extensions are not supported, or no device can present to the window.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use gamma_vk::VulkanContext;
+    ///
+    /// // `window` is a winit window created in `ApplicationHandler::resumed`
+    /// let context = VulkanContext::for_window(&window)?;
+    /// ```
+    #[cfg(feature = "windowing")]
+    pub fn for_window(window: &(impl HasWindowHandle + HasDisplayHandle)) -> Result<Self> {
+        let surface_extensions = Surface::required_extensions(window).map_err(|e| {
+            GammaVkError::initialization(format!("Failed to get window display handle: {}", e))
+        })?;
+
+        Self::builder().build_with_surface(surface_extensions, |instance| {
+            // Safety: the surface is dropped during context creation, while `window`
+            // is still borrowed
+            unsafe { Surface::from_window_ref(instance, window) }.map_err(|e| {
+                GammaVkError::initialization(format!("Failed to create window surface: {}", e))
+            })
+        })
+    }
 
-        // Build instance extensions
-        let extensions = InstanceExtensions {
-            khr_portability_enumeration: true,
-            ..InstanceExtensions::empty()
+    /// Check each stage of context creation and report how far it gets
+    ///
+    /// Attempts to load the library, create an instance with default settings,
+    /// enumerate physical devices, find a graphics queue family, and create a
+    /// logical device, stopping at the first failure. No full context is built and
+    /// this never panics, so it suits "your Vulkan setup is broken because..."
+    /// messages.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gamma_vk::VulkanContext;
+    ///
+    /// let diagnostics = VulkanContext::diagnose();
+    /// if !diagnostics.is_healthy() {
+    ///     eprintln!("Vulkan is not usable:\n{}", diagnostics);
+    /// }
+    /// ```
+    pub fn diagnose() -> ContextDiagnostics {
+        let mut diagnostics = ContextDiagnostics::default();
+
+        let library = match VulkanLibrary::new() {
+            Ok(library) => library,
+            Err(e) => return diagnostics.fail(DiagnosticStage::LibraryLoad, e.to_string()),
         };
+        diagnostics.pass(
+            DiagnosticStage::LibraryLoad,
+            format!("Loaded Vulkan {}", library.api_version()),
+        );
 
-        // Note: Vulkano's extension system is compile-time based
-        // Dynamic extension loading would require a different approach
-        // For now, we just support the basic extensions needed
+        let instance = match Self::create_instance(&library, &Self::builder()) {
+            Ok(instance) => instance,
+            Err(e) => return diagnostics.fail(DiagnosticStage::InstanceCreation, e.to_string()),
+        };
+        diagnostics.pass(
+            DiagnosticStage::InstanceCreation,
+            format!("Created instance for Vulkan {}", instance.api_version()),
+        );
 
-        // Try with portability enumeration for MoltenVK first
-        let instance = match Instance::new(
-            library.clone(),
-            InstanceCreateInfo {
-                application_name: config.application_name.clone(),
-                application_version: config.application_version,
-                engine_name: config.engine_name.clone(),
-                engine_version: config.engine_version,
-                enabled_extensions: extensions,
-                flags: vulkano::instance::InstanceCreateFlags::ENUMERATE_PORTABILITY,
-                ..Default::default()
-            },
-        ) {
-            Ok(instance) => {
-                // Vulkan instance created with portability enumeration
-                instance
-            }
-            Err(_) => {
-                // Portability enumeration failed, trying standard Vulkan
-                // Fall back to standard Vulkan instance creation
-                Instance::new(
-                    library.clone(),
-                    InstanceCreateInfo {
-                        application_name: config.application_name,
-                        application_version: config.application_version,
-                        engine_name: config.engine_name,
-                        engine_version: config.engine_version,
-                        ..Default::default()
-                    },
-                )
-                .map_err(|e| {
-                    GammaVkError::InstanceCreation(format!(
-                        "Failed to create Vulkan instance: {}",
-                        e
-                    ))
-                })?
+        let physical_devices: Vec<_> = match instance.enumerate_physical_devices() {
+            Ok(devices) => devices.collect(),
+            Err(e) => {
+                return diagnostics.fail(
+                    DiagnosticStage::PhysicalDeviceEnumeration,
+                    format!("Failed to enumerate physical devices: {}", e),
+                );
             }
         };
+        if physical_devices.is_empty() {
+            return diagnostics.fail(
+                DiagnosticStage::PhysicalDeviceEnumeration,
+                "No physical devices found",
+            );
+        }
+        diagnostics.pass(
+            DiagnosticStage::PhysicalDeviceEnumeration,
+            format!("Found {} physical device(s)", physical_devices.len()),
+        );
+
+        let graphics = physical_devices.into_iter().find_map(|device| {
+            device
+                .queue_family_properties()
+                .iter()
+                .position(|q| q.queue_flags.intersects(QueueFlags::GRAPHICS))
+                .map(|index| (device.clone(), index as u32))
+        });
+        let Some((physical_device, queue_family_index)) = graphics else {
+            return diagnostics.fail(
+                DiagnosticStage::GraphicsQueue,
+                "No physical device has a graphics queue family",
+            );
+        };
+        let device_name = physical_device.properties().device_name.clone();
+        diagnostics.pass(
+            DiagnosticStage::GraphicsQueue,
+            format!(
+                "{} has graphics queue family {}",
+                device_name, queue_family_index
+            ),
+        );
+
+        let created = Device::new(
+            physical_device,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo {
+                    queue_family_index,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        );
+        if let Err(e) = created {
+            return diagnostics.fail(
+                DiagnosticStage::DeviceCreation,
+                format!("Failed to create device on {}: {}", device_name, e),
+            );
+        }
+        diagnostics.pass(
+            DiagnosticStage::DeviceCreation,
+            format!("Created logical device on {}", device_name),
+        );
+
+        diagnostics
+    }
+
+    /// Create a new VulkanContext with a specific configuration
+    fn new_with_config(
+        config: VulkanContextBuilder,
+        create_surface: Option<SurfaceFactory<'_>>,
+    ) -> Result<Self> {
+        if !(0.0..=1.0).contains(&config.queue_priority) {
+            return Err(GammaVkError::initialization(format!(
+                "Queue priority must be within [0.0, 1.0], got {}",
+                config.queue_priority
+            )));
+        }
+
+        // Load the Vulkan library
+        let library = VulkanLibrary::new().map_err(GammaVkError::LibraryLoad)?;
+
+        if let Some(version) = config.api_version
+            && version > library.api_version()
+        {
+            return Err(GammaVkError::InstanceCreation(format!(
+                "Requested Vulkan API version {} exceeds library version {}",
+                version,
+                library.api_version()
+            )));
+        }
 
-        // Select a physical device
-        let physical_device = instance
+        let retries = config.creation_retries;
+        let instance = Self::create_instance(&library, &config)?;
+        let surface = create_surface
+            .map(|create_surface| create_surface(instance.clone()))
+            .transpose()?;
+
+        // Select the first physical device supporting the required device extensions
+        let mut physical_devices: Vec<_> = instance
             .enumerate_physical_devices()
             .map_err(|e| {
                 GammaVkError::initialization(format!("Failed to enumerate physical devices: {}", e))
             })?
-            .next()
-            .ok_or_else(|| GammaVkError::initialization("No physical devices found"))?;
+            .collect();
 
-        // Find a graphics queue family
-        let queue_family_index = physical_device
-            .queue_family_properties()
-            .iter()
-            .enumerate()
-            .position(|(_, q)| {
-                q.queue_flags
-                    .intersects(vulkano::device::QueueFlags::GRAPHICS)
+        if physical_devices.is_empty() {
+            return Err(GammaVkError::initialization("No physical devices found"));
+        }
+
+        if let Some(uuid) = config.forced_device_uuid {
+            physical_devices.retain(|device| device.properties().device_uuid == Some(uuid));
+            if physical_devices.is_empty() {
+                return Err(GammaVkError::initialization(format!(
+                    "No physical device matches forced UUID {:02x?}",
+                    uuid
+                )));
+            }
+        }
+
+        if let Some(min_bytes) = config.min_device_local_memory {
+            let candidates: Vec<String> = physical_devices
+                .iter()
+                .map(|device| {
+                    format!(
+                        "{} ({} bytes)",
+                        device.properties().device_name,
+                        largest_device_local_heap(device)
+                    )
+                })
+                .collect();
+            physical_devices.retain(|device| largest_device_local_heap(device) >= min_bytes);
+            if physical_devices.is_empty() {
+                return Err(GammaVkError::initialization(format!(
+                    "No physical device with at least {} bytes of device-local memory; candidates: {}",
+                    min_bytes,
+                    candidates.join(", ")
+                )));
+            }
+        }
+
+        let physical_device = physical_devices
+            .into_iter()
+            .find(|device| {
+                device
+                    .supported_extensions()
+                    .contains(&config.device_extensions)
+                    && (!config.dynamic_rendering || device.supported_features().dynamic_rendering)
+                    && surface.as_deref().is_none_or(|surface| {
+                        select_queue_families(device, Some(surface)).is_some()
+                    })
             })
-            .ok_or_else(|| GammaVkError::initialization("No graphics queue family found"))?;
+            .ok_or_else(|| {
+                if surface.is_some() {
+                    GammaVkError::initialization(
+                        "No physical device supports the required device extensions and presentation to the surface",
+                    )
+                } else if config.dynamic_rendering {
+                    GammaVkError::initialization(
+                        "No physical device supports the required device extensions and dynamic rendering",
+                    )
+                } else {
+                    GammaVkError::initialization(
+                        "No physical device supports the required device extensions",
+                    )
+                }
+            })?;
+
+        // Dynamic rendering is core in 1.3; older devices need the KHR extension
+        let mut device_extensions = config.device_extensions;
+        if config.dynamic_rendering
+            && physical_device.api_version().min(instance.api_version()) < Version::V1_3
+        {
+            device_extensions.khr_dynamic_rendering = true;
+        }
+
+        // Find a graphics queue family, and a present family when targeting a surface
+        let (queue_family_index, present_family_index) =
+            select_queue_families(&physical_device, surface.as_deref())
+                .ok_or_else(|| GammaVkError::initialization("No graphics queue family found"))?;
+        drop(surface);
+
+        let mut queue_create_infos = vec![QueueCreateInfo {
+            queue_family_index,
+            queues: vec![config.queue_priority],
+            ..Default::default()
+        }];
+        if let Some(present_family_index) = present_family_index
+            && present_family_index != queue_family_index
+        {
+            queue_create_infos.push(QueueCreateInfo {
+                queue_family_index: present_family_index,
+                queues: vec![config.queue_priority],
+                ..Default::default()
+            });
+        }
 
         // Create the logical device
-        let (device, mut queues) = Device::new(
-            physical_device.clone(),
-            DeviceCreateInfo {
-                queue_create_infos: vec![QueueCreateInfo {
-                    queue_family_index: queue_family_index as u32,
+        let (device, mut queues) = with_retries(retries, || {
+            Device::new(
+                physical_device.clone(),
+                DeviceCreateInfo {
+                    queue_create_infos: queue_create_infos.clone(),
+                    enabled_extensions: device_extensions,
+                    enabled_features: DeviceFeatures {
+                        dynamic_rendering: config.dynamic_rendering,
+                        sampler_anisotropy: physical_device.supported_features().sampler_anisotropy,
+                        ..DeviceFeatures::empty()
+                    },
                     ..Default::default()
-                }],
-                ..Default::default()
-            },
-        )
+                },
+            )
+        })
         .map_err(|e| GammaVkError::initialization(format!("Failed to create device: {}", e)))?;
 
         // Get the graphics queue
         let graphics_queue = queues
             .next()
             .ok_or_else(|| GammaVkError::initialization("Failed to get graphics queue"))?;
+        let present_queue = match present_family_index {
+            Some(index) if index != queue_family_index => Some(
+                queues
+                    .next()
+                    .ok_or_else(|| GammaVkError::initialization("Failed to get present queue"))?,
+            ),
+            Some(_) => Some(graphics_queue.clone()),
+            None => None,
+        };
 
         // Create the memory allocator
-        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        if config.allocator_block_size == Some(0) {
+            return Err(GammaVkError::initialization(
+                "Allocator block size must be greater than 0",
+            ));
+        }
+        let memory_allocator = Arc::new(Self::create_memory_allocator(
+            &device,
+            config.allocator_block_size,
+            config.allocator_memory_type_bits,
+        ));
+
+        // Create the command buffer allocator
+        let command_buffer_allocator = Arc::new(StandardCommandBufferAllocator::new(
+            device.clone(),
+            Default::default(),
+        ));
+
+        // Create the descriptor set allocator
+        let descriptor_set_allocator = Arc::new(StandardDescriptorSetAllocator::new(
+            device.clone(),
+            Default::default(),
+        ));
 
         Ok(VulkanContext {
             instance,
@@ -296,11 +925,134 @@ impl VulkanContext {
             device,
             physical_device,
             graphics_queue,
-            graphics_queue_family_index: queue_family_index as u32,
+            graphics_queue_family_index: queue_family_index,
+            queue_priority: config.queue_priority,
+            present_queue,
             memory_allocator,
+            allocator_block_size: config.allocator_block_size,
+            allocator_memory_type_bits: config.allocator_memory_type_bits,
+            command_buffer_allocator,
+            descriptor_set_allocator,
+            staging_buffer: Mutex::new(None),
         })
     }
 
+    /// Create the Vulkan instance, trying portability enumeration first when enabled
+    fn create_instance(
+        library: &Arc<VulkanLibrary>,
+        config: &VulkanContextBuilder,
+    ) -> Result<Arc<Instance>> {
+        // Note: Vulkano's extension system is compile-time based
+        // Dynamic extension loading would require a different approach
+        // For now, we just support the basic extensions needed
+
+        let retries = config.creation_retries;
+
+        // Try with portability enumeration for MoltenVK first, when enabled
+        let portability_instance = if config.portability.is_enabled() {
+            with_retries(retries, || {
+                Instance::new(
+                    library.clone(),
+                    InstanceCreateInfo {
+                        application_name: config.application_name.clone(),
+                        application_version: config.application_version,
+                        engine_name: config.engine_name.clone(),
+                        engine_version: config.engine_version,
+                        enabled_extensions: config.instance_extensions.union(&InstanceExtensions {
+                            khr_portability_enumeration: true,
+                            ..InstanceExtensions::empty()
+                        }),
+                        flags: vulkano::instance::InstanceCreateFlags::ENUMERATE_PORTABILITY,
+                        max_api_version: config.api_version,
+                        ..Default::default()
+                    },
+                )
+            })
+            .ok()
+        } else {
+            None
+        };
+
+        let instance = match portability_instance {
+            Some(instance) => {
+                // Vulkan instance created with portability enumeration
+                instance
+            }
+            None => {
+                // Portability enumeration disabled or failed, use standard Vulkan
+                with_retries(retries, || {
+                    Instance::new(
+                        library.clone(),
+                        InstanceCreateInfo {
+                            application_name: config.application_name.clone(),
+                            application_version: config.application_version,
+                            engine_name: config.engine_name.clone(),
+                            engine_version: config.engine_version,
+                            enabled_extensions: config.instance_extensions,
+                            max_api_version: config.api_version,
+                            ..Default::default()
+                        },
+                    )
+                })
+                .map_err(|e| {
+                    GammaVkError::InstanceCreation(format!(
+                        "Failed to create Vulkan instance: {}",
+                        e
+                    ))
+                })?
+            }
+        };
+
+        Ok(instance)
+    }
+
+    /// Create the memory allocator from the builder's allocator settings
+    ///
+    /// Mirrors `StandardMemoryAllocator::new_default`, substituting the configured
+    /// block size and memory type mask when provided.
+    fn create_memory_allocator(
+        device: &Arc<Device>,
+        block_size: Option<DeviceSize>,
+        memory_type_bits: u32,
+    ) -> StandardMemoryAllocator {
+        if block_size.is_none() && memory_type_bits == u32::MAX {
+            return StandardMemoryAllocator::new_default(device.clone());
+        }
+
+        let memory_properties = device.physical_device().memory_properties();
+        let mut block_sizes = Vec::with_capacity(memory_properties.memory_types.len());
+        let mut allowed_memory_types = memory_type_bits;
+
+        for (index, memory_type) in memory_properties.memory_types.iter().enumerate() {
+            let heap_size = memory_properties.memory_heaps[memory_type.heap_index as usize].size;
+            let default_block_size = if heap_size >= 1024 * 1024 * 1024 {
+                256 * 1024 * 1024
+            } else {
+                64 * 1024 * 1024
+            };
+            block_sizes.push(block_size.unwrap_or(default_block_size));
+
+            // These memory types cannot be suballocated safely
+            if memory_type.property_flags.intersects(
+                MemoryPropertyFlags::LAZILY_ALLOCATED
+                    | MemoryPropertyFlags::PROTECTED
+                    | MemoryPropertyFlags::DEVICE_COHERENT
+                    | MemoryPropertyFlags::RDMA_CAPABLE,
+            ) {
+                allowed_memory_types &= !(1 << index);
+            }
+        }
+
+        StandardMemoryAllocator::new(
+            device.clone(),
+            GenericMemoryAllocatorCreateInfo {
+                block_sizes: &block_sizes,
+                memory_type_bits: allowed_memory_types,
+                ..Default::default()
+            },
+        )
+    }
+
     /// Get information about enabled Vulkan layers
     pub fn enabled_layers(&self) -> &[String] {
         self.instance.enabled_layers()
@@ -321,6 +1073,39 @@ impl VulkanContext {
         self.physical_device.clone()
     }
 
+    /// Get the human-readable name of the physical device
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gamma_vk::VulkanContext;
+    ///
+    /// let context = VulkanContext::new()?;
+    /// println!("Running on {} ({:?})", context.device_name(), context.device_type());
+    /// # Ok::<(), gamma_vk::GammaVkError>(())
+    /// ```
+    pub fn device_name(&self) -> &str {
+        &self.physical_device.properties().device_name
+    }
+
+    /// Get the PCI vendor ID of the physical device
+    pub fn vendor_id(&self) -> u32 {
+        self.physical_device.properties().vendor_id
+    }
+
+    /// Get the driver version, decoded using the Vulkan version encoding
+    ///
+    /// Some vendors (notably NVIDIA) use their own encoding, so treat the result
+    /// as an identifier for bug reports rather than comparing components.
+    pub fn driver_version(&self) -> Version {
+        Version::from(self.physical_device.properties().driver_version)
+    }
+
+    /// Get the type of the physical device (discrete, integrated, ...)
+    pub fn device_type(&self) -> PhysicalDeviceType {
+        self.physical_device.properties().device_type
+    }
+
     /// Get a reference to the graphics queue
     ///
     /// This queue supports graphics operations and is used for command submission.
@@ -358,6 +1143,87 @@ impl VulkanContext {
         self.graphics_queue_family_index
     }
 
+    /// Get the queue for presenting to the surface the context was built for
+    ///
+    /// This is the graphics queue when one family supports both, which is the
+    /// common case. It is a queue from a separate family on GPUs where no graphics
+    /// family can present, so swapchain images then need queue family ownership
+    /// transfers or concurrent sharing. Returns `None` for contexts not built with
+    /// `for_window` or `build_with_surface`, which need the `windowing` feature.
+    pub fn present_queue(&self) -> Option<Arc<Queue>> {
+        self.present_queue.clone()
+    }
+
+    /// Get the present queue family index, if the context was built for a surface
+    pub fn present_queue_family_index(&self) -> Option<u32> {
+        self.present_queue
+            .as_ref()
+            .map(|queue| queue.queue_family_index())
+    }
+
+    /// Query the formats, present modes and image limits `surface` supports
+    ///
+    /// Use this to choose swapchain parameters that work on the current driver;
+    /// [`SurfaceInfo`] also suggests defaults. The surface must belong to this
+    /// context's instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device cannot present to `surface` or the query
+    /// fails, for example because the surface was lost.
+    pub fn surface_capabilities(&self, surface: &Surface) -> Result<SurfaceInfo> {
+        let query_error = |e: Validated<VulkanError>| {
+            GammaVkError::initialization(format!("Failed to query surface support: {}", e))
+        };
+
+        let capabilities = self
+            .physical_device
+            .surface_capabilities(surface, VulkanoSurfaceInfo::default())
+            .map_err(query_error)?;
+        let formats = self
+            .physical_device
+            .surface_formats(surface, VulkanoSurfaceInfo::default())
+            .map_err(query_error)?;
+        let present_modes = self
+            .physical_device
+            .surface_present_modes(surface, VulkanoSurfaceInfo::default())
+            .map_err(query_error)?;
+
+        Ok(SurfaceInfo {
+            formats,
+            present_modes,
+            min_image_count: capabilities.min_image_count,
+            max_image_count: capabilities.max_image_count,
+            current_extent: capabilities.current_extent,
+            min_image_extent: capabilities.min_image_extent,
+            max_image_extent: capabilities.max_image_extent,
+        })
+    }
+
+    /// Get the priority the graphics queue was created with
+    ///
+    /// See [`VulkanContextBuilder::queue_priority`].
+    pub fn queue_priority(&self) -> f32 {
+        self.queue_priority
+    }
+
+    /// Report every queue family on the physical device and its capabilities
+    ///
+    /// This is informational only; the context itself always uses the family
+    /// returned by [`graphics_queue_family_index`](Self::graphics_queue_family_index).
+    pub fn queue_families(&self) -> Vec<QueueFamilyInfo> {
+        self.physical_device
+            .queue_family_properties()
+            .iter()
+            .enumerate()
+            .map(|(index, properties)| QueueFamilyInfo {
+                index: index as u32,
+                queue_count: properties.queue_count,
+                queue_flags: properties.queue_flags,
+            })
+            .collect()
+    }
+
     /// Get a reference to the memory allocator
     ///
     /// The memory allocator is used for all GPU memory allocations in the engine.
@@ -376,6 +1242,511 @@ impl VulkanContext {
     pub fn memory_allocator(&self) -> Arc<StandardMemoryAllocator> {
         self.memory_allocator.clone()
     }
+
+    /// Report the live allocations held by the context's memory allocator
+    ///
+    /// Counts suballocations in the allocator's memory blocks. Dedicated
+    /// allocations, which the allocator uses for resources too large to share a
+    /// block, are not included, nor are allocators from
+    /// [`create_allocator`](Self::create_allocator). The staging buffer used by
+    /// [`with_staging`](Self::with_staging) stays allocated once created.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gamma_vk::VulkanContext;
+    ///
+    /// let context = VulkanContext::new()?;
+    /// let baseline = context.allocation_stats().live_allocations;
+    /// // ... render a frame ...
+    /// if context.allocation_stats().live_allocations > baseline {
+    ///     eprintln!("Buffers are leaking");
+    /// }
+    /// # Ok::<(), gamma_vk::GammaVkError>(())
+    /// ```
+    pub fn allocation_stats(&self) -> AllocationStats {
+        let mut stats = AllocationStats::default();
+        for pool in self.memory_allocator.pools() {
+            for block in pool.blocks() {
+                let block_size = block.device_memory().allocation_size();
+                stats.live_allocations += block.allocation_count();
+                stats.allocated_bytes += block_size - block.suballocator().free_size();
+                stats.reserved_bytes += block_size;
+            }
+        }
+        stats
+    }
+
+    /// Create an additional memory allocator bound to the same device
+    ///
+    /// The new allocator uses the same block size and memory type settings as
+    /// [`memory_allocator`](Self::memory_allocator) but owns its own memory blocks.
+    /// Giving each worker thread its own allocator reduces lock contention when
+    /// many threads allocate at once, at the cost of less sharing: every allocator
+    /// reserves whole blocks, so total memory use grows with the number of allocators.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gamma_vk::{Buffer, VulkanContext};
+    /// use vulkano::buffer::BufferUsage;
+    ///
+    /// let context = VulkanContext::new()?;
+    /// let worker_allocator = context.create_allocator();
+    /// let buffer = Buffer::new_host_visible(
+    ///     &context.device(),
+    ///     &worker_allocator,
+    ///     1024,
+    ///     BufferUsage::VERTEX_BUFFER,
+    /// )?;
+    /// # Ok::<(), gamma_vk::GammaVkError>(())
+    /// ```
+    pub fn create_allocator(&self) -> Arc<StandardMemoryAllocator> {
+        Arc::new(Self::create_memory_allocator(
+            &self.device,
+            self.allocator_block_size,
+            self.allocator_memory_type_bits,
+        ))
+    }
+
+    /// Query the physical device's properties for an image format
+    ///
+    /// Check these before creating textures or render targets to make sure the
+    /// GPU supports the format for the intended usage.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the format requires an extension or API version the
+    /// physical device does not support.
+    pub fn format_properties(&self, format: Format) -> Result<FormatProperties> {
+        self.physical_device.format_properties(format).map_err(|e| {
+            GammaVkError::initialization(format!(
+                "Failed to query format properties for {:?}: {}",
+                format, e
+            ))
+        })
+    }
+
+    /// Check if a format can be used as a color attachment with optimal tiling
+    pub fn supports_color_attachment(&self, format: Format) -> bool {
+        self.supports_optimal_features(format, FormatFeatures::COLOR_ATTACHMENT)
+    }
+
+    /// Check if a format can be sampled in shaders with optimal tiling
+    pub fn supports_sampling(&self, format: Format) -> bool {
+        self.supports_optimal_features(format, FormatFeatures::SAMPLED_IMAGE)
+    }
+
+    /// Check if the device supports anisotropic texture filtering
+    ///
+    /// The feature is enabled automatically when supported, so samplers can use
+    /// anisotropy whenever this returns `true`.
+    pub fn supports_anisotropy(&self) -> bool {
+        self.physical_device.supported_features().sampler_anisotropy
+    }
+
+    /// Check if the device supports geometry shaders
+    pub fn supports_geometry_shader(&self) -> bool {
+        self.physical_device.supported_features().geometry_shader
+    }
+
+    /// Check if the device supports line widths other than 1.0
+    pub fn supports_wide_lines(&self) -> bool {
+        self.physical_device.supported_features().wide_lines
+    }
+
+    /// Check if the device supports wireframe and point polygon fill modes
+    pub fn supports_fill_mode_non_solid(&self) -> bool {
+        self.physical_device
+            .supported_features()
+            .fill_mode_non_solid
+    }
+
+    /// Get the sample counts usable for framebuffers with both color and depth attachments
+    ///
+    /// This is the intersection of the device's `framebufferColorSampleCounts` and
+    /// `framebufferDepthSampleCounts` limits, and always includes `SAMPLE_1`.
+    pub fn supported_sample_counts(&self) -> SampleCounts {
+        let properties = self.physical_device.properties();
+        properties
+            .framebuffer_color_sample_counts
+            .intersection(properties.framebuffer_depth_sample_counts)
+    }
+
+    /// Get the highest MSAA sample count usable for both color and depth attachments
+    ///
+    /// Offer counts up to this one when configuring multisampling; render targets
+    /// and pipelines created with a higher count fail validation.
+    pub fn max_usable_sample_count(&self) -> SampleCount {
+        self.supported_sample_counts().max_count()
+    }
+
+    /// Check if dynamic rendering was enabled when the device was created
+    ///
+    /// See [`VulkanContextBuilder::enable_dynamic_rendering`].
+    pub fn dynamic_rendering_enabled(&self) -> bool {
+        self.device.enabled_features().dynamic_rendering
+    }
+
+    fn supports_optimal_features(&self, format: Format, features: FormatFeatures) -> bool {
+        self.format_properties(format)
+            .map(|properties| properties.optimal_tiling_features.contains(features))
+            .unwrap_or(false)
+    }
+
+    /// Get a reference to the command buffer allocator
+    ///
+    /// The command buffer allocator is used by [`CommandRecorder`](crate::CommandRecorder)
+    /// to allocate command buffers from thread-local pools. It is created once with
+    /// the context, like [`memory_allocator`](Self::memory_allocator), so code that
+    /// records command buffers directly should share it rather than constructing its
+    /// own. Allocate from it for [`graphics_queue_family_index`](Self::graphics_queue_family_index).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gamma_vk::VulkanContext;
+    /// use vulkano::command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage};
+    ///
+    /// let context = VulkanContext::new()?;
+    /// let builder = AutoCommandBufferBuilder::primary(
+    ///     context.command_buffer_allocator(),
+    ///     context.graphics_queue_family_index(),
+    ///     CommandBufferUsage::OneTimeSubmit,
+    /// )?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn command_buffer_allocator(&self) -> Arc<StandardCommandBufferAllocator> {
+        self.command_buffer_allocator.clone()
+    }
+
+    /// Get a reference to the descriptor set allocator
+    ///
+    /// Created once with the context, so all descriptor sets can share its pools
+    /// instead of each caller constructing a duplicate allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gamma_vk::{UniformBuffer, VulkanContext};
+    /// use std::sync::Arc;
+    /// use vulkano::descriptor_set::{DescriptorSet, WriteDescriptorSet, layout::DescriptorSetLayout};
+    ///
+    /// # fn example(context: &VulkanContext, layout: Arc<DescriptorSetLayout>, camera: &UniformBuffer) -> Result<(), Box<dyn std::error::Error>> {
+    /// let set = DescriptorSet::new(
+    ///     context.descriptor_set_allocator(),
+    ///     layout,
+    ///     [WriteDescriptorSet::buffer(0, camera.buffer().inner().clone())],
+    ///     [],
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn descriptor_set_allocator(&self) -> Arc<StandardDescriptorSetAllocator> {
+        self.descriptor_set_allocator.clone()
+    }
+
+    /// Create a descriptor set that binds whole buffers to uniform or storage bindings
+    ///
+    /// Each `(binding, buffer)` pair is checked against the descriptor type the
+    /// layout declares for that binding, so a buffer without the matching
+    /// `UNIFORM_BUFFER` or `STORAGE_BUFFER` usage is rejected here rather than by
+    /// the validation layers at draw time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a binding is missing from the layout or is not a uniform
+    /// or storage buffer binding, if a buffer lacks the usage its binding requires,
+    /// or if descriptor set allocation fails.
+    pub fn create_buffer_descriptor_set(
+        &self,
+        layout: &Arc<DescriptorSetLayout>,
+        buffers: &[(u32, &Buffer)],
+    ) -> Result<Arc<DescriptorSet>> {
+        let mut writes = Vec::with_capacity(buffers.len());
+        for &(binding, buffer) in buffers {
+            let descriptor_type = layout
+                .bindings()
+                .get(&binding)
+                .ok_or_else(|| {
+                    GammaVkError::buffer_creation(format!(
+                        "Binding {} is not in the descriptor set layout",
+                        binding
+                    ))
+                })?
+                .descriptor_type;
+            let required = match descriptor_type {
+                DescriptorType::UniformBuffer | DescriptorType::UniformBufferDynamic => {
+                    BufferUsage::UNIFORM_BUFFER
+                }
+                DescriptorType::StorageBuffer | DescriptorType::StorageBufferDynamic => {
+                    BufferUsage::STORAGE_BUFFER
+                }
+                other => {
+                    return Err(GammaVkError::buffer_creation(format!(
+                        "Binding {} is a {:?} descriptor, not a uniform or storage buffer",
+                        binding, other
+                    )));
+                }
+            };
+            buffer.assert_usage(required)?;
+            writes.push(WriteDescriptorSet::buffer(binding, buffer.inner().clone()));
+        }
+
+        DescriptorSet::new(
+            self.descriptor_set_allocator.clone(),
+            layout.clone(),
+            writes,
+            [],
+        )
+        .map_err(|e| {
+            GammaVkError::buffer_creation(format!("Failed to create descriptor set: {}", e))
+        })
+    }
+
+    /// Read the GPU durations of every scope recorded into `queries`
+    ///
+    /// Blocks until the timestamps are available, so call this after the submission
+    /// containing the scopes has completed. Ticks are converted to nanoseconds with
+    /// the device's `timestampPeriod`. Returns an empty list when timestamps are
+    /// unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query results cannot be read.
+    pub fn resolve_timestamps(&self, queries: &QueryPool) -> Result<Vec<(String, Duration)>> {
+        let period = self.physical_device.properties().timestamp_period as f64;
+
+        Ok(queries
+            .read_ticks()?
+            .into_iter()
+            .map(|(label, ticks)| {
+                let nanoseconds = (ticks as f64 * period).round() as u64;
+                (label, Duration::from_nanos(nanoseconds))
+            })
+            .collect())
+    }
+
+    /// Submit several command buffers to `queue` in a single queue submission
+    ///
+    /// One `vkQueueSubmit` replaces one per command buffer, which adds up in scenes
+    /// with many small passes. The command buffers execute in order but without
+    /// barriers between them, so they should not depend on each other's results.
+    ///
+    /// The submission waits on `wait` first, if given, such as the future of an
+    /// earlier batch or a swapchain image acquisition; vulkano turns that
+    /// dependency into semaphores where needed. The returned future signals a
+    /// fence on completion, and later work chained onto it waits for this batch.
+    /// An empty batch still submits, so its fence signals as soon as `wait`
+    /// completes, or immediately without one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a command buffer cannot be executed on `queue`, for
+    /// example because a resource it uses is in use by another submission, or if
+    /// the submission fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gamma_vk::{Buffer, CommandRecorder, VulkanContext};
+    /// use vulkano::buffer::BufferUsage;
+    ///
+    /// let context = VulkanContext::new()?;
+    /// let allocator = context.memory_allocator();
+    /// let a = Buffer::new_device_local(&context.device(), &allocator, 256, BufferUsage::TRANSFER_DST)?;
+    /// let b = Buffer::new_device_local(&context.device(), &allocator, 256, BufferUsage::TRANSFER_DST)?;
+    ///
+    /// let mut batch = Vec::new();
+    /// for buffer in [&a, &b] {
+    ///     let mut recorder = CommandRecorder::new(&context)?;
+    ///     recorder.fill_buffer(buffer, 0)?;
+    ///     batch.push(recorder.build()?);
+    /// }
+    ///
+    /// let future = context.submit_batch(&context.graphics_queue(), batch, None)?;
+    /// future.wait(None).expect("batch failed");
+    /// # Ok::<(), gamma_vk::GammaVkError>(())
+    /// ```
+    pub fn submit_batch(
+        &self,
+        queue: &Arc<Queue>,
+        command_buffers: Vec<Arc<PrimaryAutoCommandBuffer>>,
+        wait: Option<Box<dyn GpuFuture>>,
+    ) -> Result<FenceSignalFuture<Box<dyn GpuFuture>>> {
+        profile_scope!("Batch submission");
+        let mut future = wait.unwrap_or_else(|| sync::now(self.device.clone()).boxed());
+
+        // Consecutive executions on one queue are merged into a single submission
+        for (index, command_buffer) in command_buffers.into_iter().enumerate() {
+            future = future
+                .then_execute(queue.clone(), command_buffer)
+                .map_err(|e| {
+                    GammaVkError::command_execution(format!(
+                        "Failed to add command buffer {} to batch: {}",
+                        index, e
+                    ))
+                })?
+                .boxed();
+        }
+
+        future
+            .then_signal_fence_and_flush()
+            .map_err(|e| GammaVkError::command_execution(format!("Failed to submit batch: {}", e)))
+    }
+
+    /// Run `f` with the context's reusable staging buffer, at least `size` bytes large
+    ///
+    /// The staging buffer is host-visible with `TRANSFER_SRC` usage. It is allocated
+    /// on first use and only reallocated when a larger size is requested, so frequent
+    /// small uploads do not churn the allocator. It can therefore be larger than
+    /// `size` and hold bytes from an earlier upload, so copy only the bytes written.
+    /// Concurrent callers are serialized; calling `with_staging` again from inside
+    /// `f` deadlocks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `size` is zero, the staging buffer cannot be allocated, or
+    /// `f` returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use gamma_vk::{CommandRecorder, VulkanContext};
+    /// use vulkano::command_buffer::BufferCopy;
+    /// # fn example(context: &VulkanContext, target: &gamma_vk::Buffer) -> gamma_vk::Result<()> {
+    /// let data = [0u8; 256];
+    /// context.with_staging(data.len() as u64, |staging| {
+    ///     staging.write_data(&data)?;
+    ///     let mut recorder = CommandRecorder::new(context)?;
+    ///     // The staging buffer may be larger than `data`; copy only what was written
+    ///     recorder.copy_buffer_regions(
+    ///         staging,
+    ///         target,
+    ///         &[BufferCopy {
+    ///             size: data.len() as u64,
+    ///             ..Default::default()
+    ///         }],
+    ///     )?;
+    ///     recorder.submit_and_wait(&context.graphics_queue())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_staging<R>(&self, size: u64, f: impl FnOnce(&Buffer) -> Result<R>) -> Result<R> {
+        profile_scope!("Staging upload");
+        if size == 0 {
+            return Err(GammaVkError::buffer_creation(
+                "Staging size must be greater than zero",
+            ));
+        }
+
+        let mut staging = self
+            .staging_buffer
+            .lock()
+            .map_err(|_| GammaVkError::buffer_creation("Staging buffer lock poisoned"))?;
+
+        if staging.as_ref().is_none_or(|buffer| buffer.size() < size) {
+            // Release the old buffer before allocating its replacement
+            *staging = None;
+            *staging = Some(Buffer::new_host_visible(
+                &self.device,
+                &self.memory_allocator,
+                size,
+                BufferUsage::TRANSFER_SRC,
+            )?);
+        }
+
+        f(staging.as_ref().expect("staging buffer was just allocated"))
+    }
+
+    /// Create `count` recyclable frame slots for N-buffered rendering
+    ///
+    /// See [`FrameContext`] for the begin/end frame protocol.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `count` is zero or a fence cannot be created.
+    pub fn frames(&self, count: usize) -> Result<FrameContext> {
+        FrameContext::new(self, count)
+    }
+
+    /// Get the current size of the reusable staging buffer, or 0 if not yet allocated
+    pub fn staging_capacity(&self) -> u64 {
+        self.staging_buffer
+            .lock()
+            .ok()
+            .and_then(|staging| staging.as_ref().map(Buffer::size))
+            .unwrap_or(0)
+    }
+}
+
+/// Creates the surface a context is built for, once its instance exists
+type SurfaceFactory<'a> = Box<dyn FnOnce(Arc<Instance>) -> Result<Arc<Surface>> + 'a>;
+
+/// Pick a graphics queue family, plus a present family when a surface is given
+///
+/// A family supporting both graphics and presentation is preferred over separate
+/// families. Returns `None` if no graphics family exists, or if a surface is
+/// given and no family can present to it.
+fn select_queue_families(
+    device: &PhysicalDevice,
+    surface: Option<&Surface>,
+) -> Option<(u32, Option<u32>)> {
+    let families = device.queue_family_properties();
+    let graphics_families: Vec<u32> = (0..families.len() as u32)
+        .filter(|&index| {
+            families[index as usize]
+                .queue_flags
+                .intersects(QueueFlags::GRAPHICS)
+        })
+        .collect();
+    let first_graphics = *graphics_families.first()?;
+
+    let Some(surface) = surface else {
+        return Some((first_graphics, None));
+    };
+    let can_present = |index: u32| device.surface_support(index, surface).unwrap_or(false);
+
+    if let Some(&combined) = graphics_families.iter().find(|&&index| can_present(index)) {
+        return Some((combined, Some(combined)));
+    }
+    let present = (0..families.len() as u32).find(|&index| can_present(index))?;
+    Some((first_graphics, Some(present)))
+}
+
+/// Size in bytes of the largest device-local memory heap, or 0 if there is none
+fn largest_device_local_heap(device: &PhysicalDevice) -> DeviceSize {
+    device
+        .memory_properties()
+        .memory_heaps
+        .iter()
+        .filter(|heap| heap.flags.intersects(MemoryHeapFlags::DEVICE_LOCAL))
+        .map(|heap| heap.size)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Run a Vulkan creation call, retrying up to `retries` extra times on transient errors
+fn with_retries<T>(
+    retries: u32,
+    mut create: impl FnMut() -> std::result::Result<T, Validated<VulkanError>>,
+) -> std::result::Result<T, Validated<VulkanError>> {
+    let mut attempts_left = retries;
+    loop {
+        match create() {
+            Err(error) if attempts_left > 0 && is_transient(&error) => attempts_left -= 1,
+            result => return result,
+        }
+    }
+}
+
+/// Whether a creation error may succeed when simply attempted again
+fn is_transient(error: &Validated<VulkanError>) -> bool {
+    matches!(
+        error,
+        Validated::Error(VulkanError::DeviceLost | VulkanError::InitializationFailed)
+    )
 }
 
 impl Drop for VulkanContext {
@@ -390,3 +1761,79 @@ impl Drop for VulkanContext {
         // when their reference counts reach zero
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transient_failure_is_retried() {
+        let mut attempts = 0;
+        let result = with_retries(1, || {
+            attempts += 1;
+            if attempts == 1 {
+                Err(Validated::Error(VulkanError::DeviceLost))
+            } else {
+                Ok("context")
+            }
+        });
+
+        assert_eq!(result.unwrap(), "context");
+        assert_eq!(attempts, 2);
+    }
+
+    #[test]
+    fn test_retries_are_bounded() {
+        let mut attempts = 0;
+        let result: std::result::Result<(), _> = with_retries(2, || {
+            attempts += 1;
+            Err(Validated::Error(VulkanError::InitializationFailed))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn test_non_transient_failure_is_not_retried() {
+        let mut attempts = 0;
+        let result: std::result::Result<(), _> = with_retries(3, || {
+            attempts += 1;
+            Err(Validated::Error(VulkanError::ExtensionNotPresent))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_surface_info_prefers_srgb_bgra_and_extra_image() {
+        let mut info = SurfaceInfo {
+            formats: vec![
+                (Format::R8G8B8A8_UNORM, ColorSpace::SrgbNonLinear),
+                (Format::B8G8R8A8_SRGB, ColorSpace::SrgbNonLinear),
+            ],
+            present_modes: vec![PresentMode::Mailbox, PresentMode::Fifo],
+            min_image_count: 2,
+            max_image_count: Some(2),
+            current_extent: Some([640, 480]),
+            min_image_extent: [1, 1],
+            max_image_extent: [4096, 4096],
+        };
+
+        assert_eq!(
+            info.preferred_format(),
+            Some((Format::B8G8R8A8_SRGB, ColorSpace::SrgbNonLinear))
+        );
+        assert_eq!(info.preferred_present_mode(), PresentMode::Fifo);
+        assert_eq!(info.preferred_image_count(), 2);
+
+        info.formats.remove(1);
+        info.max_image_count = None;
+        assert_eq!(
+            info.preferred_format(),
+            Some((Format::R8G8B8A8_UNORM, ColorSpace::SrgbNonLinear))
+        );
+        assert_eq!(info.preferred_image_count(), 3);
+    }
+}
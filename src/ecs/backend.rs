@@ -3,8 +3,8 @@
 //! This trait allows different storage strategies (sparse set, archetype, etc.)
 //! to be used interchangeably while maintaining the same public API.
 
-use crate::{Component, Entity, GammaVkError};
-use std::any::TypeId;
+use crate::GammaVkError;
+use super::{Component, Entity, StorageKind};
 
 /// Trait for ECS storage backends.
 /// 
@@ -15,21 +15,78 @@ pub trait EcsBackend: Send + Sync + Default {
     /// Creates a new entity and returns its ID.
     fn create_entity(&mut self) -> Entity;
     
+    /// Creates one entity per component, inserting each component as it goes.
+    /// Returns the new entities in the same order as the components.
+    fn spawn_batch<C: Component>(&mut self, components: impl IntoIterator<Item = C>) -> Vec<Entity>;
+    
+    /// Creates exactly `entity`, preserving its index and generation.
+    /// Fails if a live entity already occupies that index, or if the index is
+    /// so far past the allocated entities that growing to it would be wasteful.
+    fn create_entity_at(&mut self, entity: Entity) -> Result<(), GammaVkError>;
+    
+    /// Creates every entity in `entities` as `create_entity_at` would, in time
+    /// linear in the number of entities. Stops at the first entity that fails.
+    /// The entities are expected to come from another world's `entities()`, so
+    /// no limit applies to how far past the allocated entities they reach.
+    fn create_entities_at(&mut self, entities: &[Entity]) -> Result<(), GammaVkError>;
+    
+    /// Returns every live entity in ascending index order.
+    fn entities(&self) -> Vec<Entity>;
+    
     /// Destroys an entity and all its components.
     fn destroy_entity(&mut self, entity: Entity) -> Result<(), GammaVkError>;
     
+    /// Destroys every live entity in `entities`, skipping dead or unknown ones.
+    /// Returns the entities that were destroyed, each once.
+    fn despawn_batch(&mut self, entities: impl IntoIterator<Item = Entity>) -> Vec<Entity>;
+    
+    /// Reserves capacity for at least `additional_entities` more entities.
+    fn reserve(&mut self, additional_entities: usize);
+    
+    /// Reserves capacity for at least `additional` more components of type C.
+    fn reserve_component<C: Component>(&mut self, additional: usize);
+    
+    /// Selects the storage layout for component type C.
+    /// Components already stored are moved into the new layout.
+    fn register_component<C: Component>(&mut self, kind: StorageKind);
+    
     /// Checks if an entity is alive.
     fn is_alive(&self, entity: Entity) -> bool;
     
     /// Adds a component to an entity.
     fn add_component<C: Component>(&mut self, entity: Entity, component: C) -> Result<(), GammaVkError>;
     
+    /// Adds or replaces a component on an entity.
+    /// Returns the component the entity previously had, if any.
+    fn replace_component<C: Component>(&mut self, entity: Entity, component: C) -> Result<Option<C>, GammaVkError>;
+    
     /// Gets a component for an entity.
     fn get_component<C: Component>(&self, entity: Entity) -> Option<&C>;
     
     /// Gets a mutable component for an entity.
     fn get_component_mut<C: Component>(&mut self, entity: Entity) -> Option<&mut C>;
     
+    /// Gets a mutable component for an entity, inserting `f()` first if absent.
+    /// Returns None only if the entity is dead.
+    fn get_or_insert_with<C: Component>(&mut self, entity: Entity, f: impl FnOnce() -> C) -> Option<&mut C>;
+    
+    /// Gets mutable references to the same component type on two entities.
+    /// Returns None if the entities are equal, either is dead, or either lacks C.
+    fn get_component_pair_mut<C: Component>(&mut self, a: Entity, b: Entity) -> Option<(&mut C, &mut C)>;
+    
+    /// Gets mutable references to two components of one entity at once.
+    /// Returns None if the entity is dead or lacks either component.
+    /// Panics if A and B are the same type.
+    fn get_components2_mut<A: Component, B: Component>(&mut self, entity: Entity) -> Option<(&mut A, &mut B)>;
+    
+    /// Removes and returns a component without running its `on_remove` hook.
+    /// Used to move components elsewhere rather than discard them.
+    fn take_component<C: Component>(&mut self, entity: Entity) -> Option<C>;
+    
+    /// Removes component C from every entity and drops its storage.
+    /// Returns the number of components removed.
+    fn clear_component<C: Component>(&mut self) -> usize;
+    
     /// Removes a component from an entity.
     fn remove_component<C: Component>(&mut self, entity: Entity) -> Result<(), GammaVkError>;
     
@@ -37,7 +94,30 @@ pub trait EcsBackend: Send + Sync + Default {
     /// Returns an iterator over (Entity, &Component) pairs.
     fn query_component<C: Component>(&self) -> Vec<(Entity, &C)>;
     
+    /// Queries for entities with a specific component type in ascending
+    /// entity-index order.
+    fn query_component_sorted<C: Component>(&self) -> Vec<(Entity, &C)>;
+    
     /// Queries for entities with a specific component type (mutable).
     /// Returns an iterator over (Entity, &mut Component) pairs.
     fn query_component_mut<C: Component>(&mut self) -> Vec<(Entity, &mut C)>;
+    
+    /// Calls `f` on every component of type C, in dense storage order.
+    fn map_component<C: Component>(&mut self, f: impl FnMut(&mut C));
+    
+    /// Lazily iterates entities that have both component types.
+    /// Returns an iterator over (Entity, &A, &B) triples without collecting.
+    fn iter_components2<A: Component, B: Component>(&self) -> impl Iterator<Item = (Entity, &A, &B)>;
+    
+    /// Calls `f` for every entity having both components, with A mutable.
+    /// Panics if A and B are the same type.
+    fn for_each_mut<A: Component, B: Component>(&mut self, f: impl FnMut(Entity, &mut A, &B));
+    
+    /// Returns the type names of all components the entity currently has.
+    /// Dead entities have no components.
+    fn component_names(&self, entity: Entity) -> Vec<&'static str>;
+    
+    /// Reports the approximate bytes used by each component storage.
+    /// Returns (component type name, bytes) pairs.
+    fn storage_memory_report(&self) -> Vec<(&'static str, usize)>;
 }
\ No newline at end of file
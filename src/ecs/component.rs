@@ -3,15 +3,16 @@
 //! Components are plain data types that can be attached to entities.
 //! They must be Send + Sync for thread safety and 'static for type erasure.
 
-use std::any::TypeId;
+use super::Entity;
 
 /// Trait that all components must implement.
 /// 
 /// Components are data containers that can be attached to entities.
-/// This is a marker trait with supertraits for thread safety.
+/// Supertraits provide thread safety; the lifecycle hook is optional.
 /// 
 /// # Example
 /// ```
+/// # use gamma_vk::ecs::Component;
 /// #[derive(Debug, Clone)]
 /// struct Position {
 ///     x: f32,
@@ -20,7 +21,36 @@ use std::any::TypeId;
 /// 
 /// impl Component for Position {}
 /// ```
-pub trait Component: Send + Sync + 'static {}
+/// 
+/// With the `derive` feature, `#[derive(Component)]` generates the empty impl.
+pub trait Component: Send + Sync + 'static {
+    /// Called when the component is removed or its entity destroyed, just
+    /// before the value is dropped.
+    /// 
+    /// Use this for teardown beyond `Drop`, such as notifying another system.
+    /// The world cannot be accessed from here, which rules out re-entrant
+    /// mutation; `hooks` carries the context that is available. Replacing a
+    /// component with `add_component` does not call the hook.
+    fn on_remove(&mut self, _entity: Entity, _hooks: &WorldHooks) {}
+}
+
+/// Restricted context passed to component lifecycle hooks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorldHooks {
+    entity_destroyed: bool,
+}
+
+impl WorldHooks {
+    pub(crate) fn new(entity_destroyed: bool) -> Self {
+        Self { entity_destroyed }
+    }
+    
+    /// Returns true if the hook fires because the entity is being destroyed,
+    /// false for an explicit component removal.
+    pub fn is_entity_destroyed(&self) -> bool {
+        self.entity_destroyed
+    }
+}
 
 /// Internal trait for type-erased component storage.
 /// 
@@ -28,13 +58,22 @@ pub trait Component: Send + Sync + 'static {}
 /// while maintaining type safety through the public API.
 pub(crate) trait ComponentStorage: Send + Sync {
     /// Removes a component for the given entity.
-    fn remove(&mut self, entity: crate::Entity) -> bool;
+    fn remove(&mut self, entity: Entity) -> bool;
     
     /// Clears all components for an entity (used during entity destruction).
-    fn clear_for_entity(&mut self, entity: crate::Entity);
+    fn clear_for_entity(&mut self, entity: Entity);
+    
+    /// Checks if the entity has a component in this storage.
+    fn contains(&self, entity: Entity) -> bool;
+    
+    /// Returns the number of components stored.
+    fn len(&self) -> usize;
+    
+    /// Returns the type name of the components stored, for diagnostics.
+    fn type_name(&self) -> &'static str;
     
-    /// Returns the type ID of the components stored.
-    fn type_id(&self) -> TypeId;
+    /// Returns the approximate number of bytes allocated by this storage.
+    fn memory_usage(&self) -> usize;
     
     /// Converts to Any for downcasting.
     fn as_any(&self) -> &dyn std::any::Any;
@@ -46,6 +85,7 @@ pub(crate) trait ComponentStorage: Send + Sync {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::any::TypeId;
 
     #[derive(Debug, Clone)]
     struct TestComponent {
@@ -58,6 +98,7 @@ mod tests {
     fn test_component_impl() {
         // This test just verifies that our Component trait can be implemented
         let component = TestComponent { value: 42 };
+        assert_eq!(component.value, 42);
         
         // Component should be Send + Sync
         fn assert_send_sync<T: Send + Sync>() {}
@@ -33,20 +33,46 @@ impl Entity {
         ((self.generation as u64) << 32) | (self.id as u64)
     }
     
+    /// Reconstructs an entity from the packed value returned by [`Entity::id`].
+    /// 
+    /// This is a raw reconstruction like `from_raw_parts` and validates nothing.
+    /// The result is only meaningful within the world the entity came from.
+    pub fn from_bits(bits: u64) -> Self {
+        Self {
+            id: bits as u32,
+            generation: (bits >> 32) as u32,
+        }
+    }
+    
     /// Returns the entity's index (without generation).
-    pub(crate) fn index(&self) -> u32 {
+    /// 
+    /// Indices are reused after an entity is destroyed, so pair this with
+    /// [`Entity::generation`] to tell recycled entities apart. Like all entity
+    /// identifiers, it is only stable within the world the entity came from.
+    pub fn index(&self) -> u32 {
         self.id
     }
     
     /// Returns the entity's generation.
-    pub(crate) fn generation(&self) -> u32 {
+    /// 
+    /// The generation increments each time the index is reused. It is only
+    /// meaningful within the world the entity came from.
+    pub fn generation(&self) -> u32 {
         self.generation
     }
 }
 
+/// Formats as `Entity(1, gen: 2)`, or as the compact token `e1v2` with `{:#}`.
+/// 
+/// The compact form has no spaces or punctuation, so log pipelines can match it
+/// as a single word.
 impl fmt::Display for Entity {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Entity({}, gen: {})", self.id, self.generation)
+        if f.alternate() {
+            write!(f, "e{}v{}", self.id, self.generation)
+        } else {
+            write!(f, "Entity({}, gen: {})", self.id, self.generation)
+        }
     }
 }
 
@@ -74,6 +100,21 @@ mod tests {
         assert_ne!(entity.id(), entity2.id());
     }
 
+    #[test]
+    fn test_entity_from_bits_round_trip() {
+        let entities = [
+            Entity::from_raw_parts(0, 0),
+            Entity::from_raw_parts(100, 5),
+            Entity::from_raw_parts(u32::MAX, 1),
+            Entity::from_raw_parts(7, u32::MAX),
+            Entity::from_raw_parts(u32::MAX, u32::MAX),
+        ];
+        
+        for entity in entities {
+            assert_eq!(Entity::from_bits(entity.id()), entity);
+        }
+    }
+
     #[test]
     fn test_entity_equality() {
         let e1 = Entity::from_raw_parts(1, 1);
@@ -85,4 +126,24 @@ mod tests {
         assert_ne!(e1, e3); // Different generation
         assert_ne!(e1, e4); // Different ID
     }
+
+    #[test]
+    fn test_raw_parts_round_trip_through_public_accessors() {
+        for (index, generation) in [(0, 0), (42, 1), (u32::MAX, 7), (3, u32::MAX)] {
+            let entity = Entity::from_raw_parts(index, generation);
+            assert_eq!(entity.index(), index);
+            assert_eq!(entity.generation(), generation);
+            assert_eq!(Entity::from_raw_parts(entity.index(), entity.generation()), entity);
+        }
+    }
+
+    #[test]
+    fn test_display_verbose_and_compact_forms() {
+        let entity = Entity::from_raw_parts(1, 2);
+        assert_eq!(format!("{}", entity), "Entity(1, gen: 2)");
+        assert_eq!(format!("{:#}", entity), "e1v2");
+        
+        let max = Entity::from_raw_parts(u32::MAX, u32::MAX);
+        assert_eq!(format!("{:#}", max), format!("e{}v{}", u32::MAX, u32::MAX));
+    }
 }
\ No newline at end of file
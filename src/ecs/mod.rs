@@ -10,7 +10,7 @@
 //! struct Position { x: f32, y: f32 }
 //! impl Component for Position {}
 //! 
-//! let mut world = World::new()?;
+//! let mut world: World = World::new()?;
 //! 
 //! let entity = world.spawn()
 //!     .with(Position { x: 0.0, y: 0.0 })
@@ -19,6 +19,7 @@
 //! if let Some(pos) = world.get_mut::<Position>(entity) {
 //!     pos.x += 1.0;
 //! }
+//! # Ok::<(), gamma_vk::GammaVkError>(())
 //! ```
 
 mod entity;
@@ -26,37 +27,19 @@ mod component;
 mod backend;
 mod sparse_set;
 mod sparse_set_backend;
+mod storage;
+mod registry;
+mod system;
 mod world;
 
 // Re-exports
 pub use entity::Entity;
-pub use component::Component;
+pub use component::{Component, WorldHooks};
+#[cfg(feature = "derive")]
+pub use gamma_vk_derive::Component;
 pub use backend::EcsBackend;
 pub use sparse_set_backend::SparseSetBackend;
-pub use world::{World, EntityBuilder};
-
-// For testing - temporary error type
-pub use crate::error::GammaVkError;
-
-// Module structure for organized development
-pub mod error {
-    // Placeholder for ECS errors that will be added to main GammaVkError
-    use crate::Entity;
-    
-    #[derive(Debug)]
-    pub enum GammaVkError {
-        EntityNotFound(Entity),
-        ComponentNotFound(Entity),
-    }
-    
-    impl std::fmt::Display for GammaVkError {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            match self {
-                Self::EntityNotFound(e) => write!(f, "Entity not found: {:?}", e),
-                Self::ComponentNotFound(e) => write!(f, "Component not found for entity: {:?}", e),
-            }
-        }
-    }
-    
-    impl std::error::Error for GammaVkError {}
-}
\ No newline at end of file
+pub use storage::StorageKind;
+pub use registry::ComponentRegistry;
+pub use system::System;
+pub use world::{World, WorldSnapshot, EntityBuilder, AnyOf, Query};
\ No newline at end of file
@@ -0,0 +1,150 @@
+//! Component registry for moving entities between backends
+//! 
+//! Backends store components type-erased, so copying a world into a backend of
+//! another type, or cloning it into a snapshot, needs the concrete component
+//! types spelled out up front.
+
+use super::{backend::EcsBackend, Component, Entity};
+use std::any::{Any, TypeId};
+
+/// Moves one component of type C from the source backend to the destination.
+type Transfer<S, D> = fn(&mut S, &mut D, Entity);
+
+/// Clones every component of one type out of a backend, type-erased.
+type Capture<S> = Box<dyn Fn(&S) -> CapturedComponents<S> + Send + Sync>;
+
+/// Components of one type cloned out of a backend, with the function that puts
+/// them back.
+pub(crate) struct CapturedComponents<S> {
+    components: Box<dyn Any + Send + Sync>,
+    restore: fn(&mut S, Box<dyn Any + Send + Sync>),
+}
+
+impl<S: EcsBackend> CapturedComponents<S> {
+    /// Inserts the captured components into `backend`, whose entities must be alive.
+    pub(crate) fn restore(self, backend: &mut S) {
+        (self.restore)(backend, self.components);
+    }
+}
+
+/// The component types to carry over when migrating a world between backends.
+/// 
+/// Components of unregistered types are dropped with the source world.
+/// Types registered with a clone function are also captured by `World::snapshot`.
+/// 
+/// # Example
+/// ```
+/// # use gamma_vk::ecs::{Component, ComponentRegistry, SparseSetBackend, World};
+/// # struct Position;
+/// # impl Component for Position {}
+/// # struct Velocity;
+/// # impl Component for Velocity {}
+/// # let prototype_world: World = World::new()?;
+/// let registry = ComponentRegistry::new()
+///     .register::<Position>()
+///     .register::<Velocity>();
+/// 
+/// let world: World<SparseSetBackend> = prototype_world.migrate_into(&registry);
+/// # Ok::<(), gamma_vk::GammaVkError>(())
+/// ```
+pub struct ComponentRegistry<S: EcsBackend, D: EcsBackend = S> {
+    transfers: Vec<(TypeId, Transfer<S, D>)>,
+    
+    /// Snapshot capture for types registered with a clone function
+    captures: Vec<(TypeId, Capture<S>)>,
+}
+
+impl<S: EcsBackend, D: EcsBackend> ComponentRegistry<S, D> {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self {
+            transfers: Vec::new(),
+            captures: Vec::new(),
+        }
+    }
+    
+    /// Registers a component type; registering the same type twice is a no-op.
+    pub fn register<C: Component>(mut self) -> Self {
+        let type_id = TypeId::of::<C>();
+        if !self.transfers.iter().any(|(registered, _)| *registered == type_id) {
+            self.transfers.push((type_id, transfer::<S, D, C>));
+        }
+        self
+    }
+    
+    /// Registers a cloneable component type, so snapshots capture it too.
+    pub fn register_cloned<C: Component + Clone>(self) -> Self {
+        self.register_with_clone::<C>(C::clone)
+    }
+    
+    /// Registers a component type that snapshots copy with `clone`.
+    /// 
+    /// Use this for components that do not implement `Clone`, such as ones
+    /// holding GPU handles that should be shared rather than duplicated.
+    pub fn register_with_clone<C: Component>(mut self, clone: impl Fn(&C) -> C + Send + Sync + 'static) -> Self {
+        let type_id = TypeId::of::<C>();
+        if !self.captures.iter().any(|(registered, _)| *registered == type_id) {
+            let capture = move |source: &S| CapturedComponents {
+                components: Box::new(
+                    source
+                        .query_component::<C>()
+                        .into_iter()
+                        .map(|(entity, component)| (entity, clone(component)))
+                        .collect::<Vec<_>>(),
+                ) as Box<dyn Any + Send + Sync>,
+                restore: restore::<S, C>,
+            };
+            self.captures.push((type_id, Box::new(capture)));
+        }
+        self.register::<C>()
+    }
+    
+    /// Returns the number of registered component types.
+    pub fn len(&self) -> usize {
+        self.transfers.len()
+    }
+    
+    /// Returns true if no component types are registered.
+    pub fn is_empty(&self) -> bool {
+        self.transfers.is_empty()
+    }
+    
+    /// Moves every registered component of `entity` from `source` to `destination`.
+    /// 
+    /// The entity must already be alive in `destination`.
+    pub(crate) fn transfer(&self, source: &mut S, destination: &mut D, entity: Entity) {
+        for (_, transfer) in &self.transfers {
+            transfer(source, destination, entity);
+        }
+    }
+    
+    /// Clones every component of the cloneable registered types out of `source`.
+    pub(crate) fn capture(&self, source: &S) -> Vec<CapturedComponents<S>> {
+        self.captures.iter().map(|(_, capture)| capture(source)).collect()
+    }
+}
+
+impl<S: EcsBackend, D: EcsBackend> Default for ComponentRegistry<S, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn transfer<S: EcsBackend, D: EcsBackend, C: Component>(source: &mut S, destination: &mut D, entity: Entity) {
+    if let Some(component) = source.take_component::<C>(entity) {
+        destination
+            .add_component(entity, component)
+            .expect("entity is alive in the destination");
+    }
+}
+
+fn restore<S: EcsBackend, C: Component>(destination: &mut S, components: Box<dyn Any + Send + Sync>) {
+    let components = components
+        .downcast::<Vec<(Entity, C)>>()
+        .expect("captured components match their restore function");
+    for (entity, component) in *components {
+        destination
+            .add_component(entity, component)
+            .expect("snapshot entities are alive in the destination");
+    }
+}
\ No newline at end of file
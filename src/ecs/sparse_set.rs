@@ -3,8 +3,8 @@
 //! Provides O(1) insertion, removal, and access at the cost of memory overhead
 //! and less cache-friendly iteration compared to archetype storage.
 
-use crate::{Component, ComponentStorage, Entity};
-use std::any::{Any, TypeId};
+use super::{Component, component::ComponentStorage, Entity, WorldHooks};
+use std::any::Any;
 
 /// A sparse set data structure for storing components.
 /// 
@@ -19,6 +19,9 @@ pub(crate) struct SparseSet<T: Component> {
     
     /// Dense array of components (parallel to entities)
     components: Vec<T>,
+    
+    /// Component type name, captured at creation for memory reports
+    type_name: &'static str,
 }
 
 impl<T: Component> SparseSet<T> {
@@ -28,11 +31,21 @@ impl<T: Component> SparseSet<T> {
             sparse: Vec::new(),
             entities: Vec::new(),
             components: Vec::new(),
+            type_name: std::any::type_name::<T>(),
         }
     }
     
+    /// Reserves capacity for at least `additional` more components.
+    pub fn reserve(&mut self, additional: usize) {
+        self.sparse.reserve(additional);
+        self.entities.reserve(additional);
+        self.components.reserve(additional);
+    }
+    
     /// Inserts a component for an entity.
-    pub fn insert(&mut self, entity: Entity, component: T) {
+    /// 
+    /// Returns the component the entity previously had, if any.
+    pub fn insert(&mut self, entity: Entity, component: T) -> Option<T> {
         let index = entity.index() as usize;
         
         // Grow sparse array if needed
@@ -43,14 +56,18 @@ impl<T: Component> SparseSet<T> {
         // Check if entity already has component
         if let Some(dense_index) = self.sparse[index] {
             // Update existing component
-            self.components[dense_index] = component;
-            self.entities[dense_index] = entity; // Update generation
+            let old = std::mem::replace(&mut self.components[dense_index], component);
+            let previous_owner = std::mem::replace(&mut self.entities[dense_index], entity); // Update generation
+            
+            // A stale generation's component did not belong to this entity
+            (previous_owner == entity).then_some(old)
         } else {
             // Add new component
             let dense_index = self.components.len();
             self.sparse[index] = Some(dense_index);
             self.entities.push(entity);
             self.components.push(component);
+            None
         }
     }
     
@@ -88,14 +105,76 @@ impl<T: Component> SparseSet<T> {
             })
     }
     
+    /// Gets mutable components for two different entities at once.
+    /// 
+    /// Returns None if the entities are the same or either lacks a component.
+    pub fn get_pair_mut(&mut self, a: Entity, b: Entity) -> Option<(&mut T, &mut T)> {
+        let dense_a = self.dense_index(a)?;
+        let dense_b = self.dense_index(b)?;
+        
+        // Distinct dense slots are distinct components
+        let [a, b] = self.components.get_disjoint_mut([dense_a, dense_b]).ok()?;
+        Some((a, b))
+    }
+    
+    /// Looks up the dense index of an entity's component, checking generation.
+    fn dense_index(&self, entity: Entity) -> Option<usize> {
+        let dense_index = (*self.sparse.get(entity.index() as usize)?)?;
+        (self.entities[dense_index] == entity).then_some(dense_index)
+    }
+    
+    /// Checks if an entity has a component in this set.
+    pub fn contains(&self, entity: Entity) -> bool {
+        let index = entity.index() as usize;
+        
+        matches!(
+            self.sparse.get(index),
+            Some(Some(dense_index)) if self.entities[*dense_index] == entity
+        )
+    }
+    
+    /// Returns the number of components stored.
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+    
     /// Removes a component for an entity.
     pub fn remove(&mut self, entity: Entity) -> bool {
+        self.remove_with_hooks(entity, WorldHooks::new(false))
+    }
+    
+    /// Removes a component for an entity, running its `on_remove` hook first.
+    fn remove_with_hooks(&mut self, entity: Entity, hooks: WorldHooks) -> bool {
+        match self.take(entity) {
+            Some(mut component) => {
+                component.on_remove(entity, &hooks);
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// Removes every component, running each `on_remove` hook.
+    /// 
+    /// Returns the number of components removed.
+    pub fn clear(&mut self) -> usize {
+        let hooks = WorldHooks::new(false);
+        let removed = self.components.len();
+        for (entity, mut component) in self.entities.drain(..).zip(self.components.drain(..)) {
+            component.on_remove(entity, &hooks);
+        }
+        self.sparse.clear();
+        removed
+    }
+    
+    /// Removes and returns a component for an entity without running its hook.
+    pub fn take(&mut self, entity: Entity) -> Option<T> {
         let index = entity.index() as usize;
         
         if let Some(Some(dense_index)) = self.sparse.get(index) {
             // Verify generation matches
             if self.entities[*dense_index] != entity {
-                return false;
+                return None;
             }
             
             // Swap remove from dense arrays
@@ -112,26 +191,88 @@ impl<T: Component> SparseSet<T> {
             
             // Remove last element
             self.entities.pop();
-            self.components.pop();
+            let component = self.components.pop().expect("dense arrays are parallel");
             self.sparse[index] = None;
             
-            true
+            Some(component)
         } else {
-            false
+            None
+        }
+    }
+    
+    /// Returns the approximate number of bytes allocated by this set.
+    /// 
+    /// Counts the capacity of the sparse array and both dense arrays; heap memory
+    /// owned by the components themselves is not included.
+    pub fn memory_usage(&self) -> usize {
+        self.sparse.capacity() * size_of::<Option<usize>>()
+            + self.entities.capacity() * size_of::<Entity>()
+            + self.components.capacity() * size_of::<T>()
+    }
+    
+    /// Retains only the components for which the predicate returns true.
+    /// 
+    /// Removed components are swap-removed, so the dense order is not preserved.
+    #[allow(dead_code)] // Not yet exposed through World
+    pub fn retain(&mut self, mut f: impl FnMut(Entity, &mut T) -> bool) {
+        let mut dense_index = 0;
+        
+        while dense_index < self.components.len() {
+            let entity = self.entities[dense_index];
+            
+            if f(entity, &mut self.components[dense_index]) {
+                dense_index += 1;
+                continue;
+            }
+            
+            // Swap remove; the element moved into this slot is visited next
+            self.entities.swap_remove(dense_index);
+            let mut component = self.components.swap_remove(dense_index);
+            self.sparse[entity.index() as usize] = None;
+            component.on_remove(entity, &WorldHooks::new(false));
+            
+            if let Some(moved_entity) = self.entities.get(dense_index) {
+                self.sparse[moved_entity.index() as usize] = Some(dense_index);
+            }
         }
     }
     
+    /// Returns the dense array of entities that have a component.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+    
     /// Iterates over all entities and components.
     pub fn iter(&self) -> impl Iterator<Item = (Entity, &T)> {
         self.entities.iter().copied()
             .zip(self.components.iter())
     }
     
+    /// Iterates over all entities and components in ascending entity-index order.
+    /// 
+    /// Walks the sparse array, so the order is stable across removals.
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (Entity, &T)> {
+        self.sparse
+            .iter()
+            .filter_map(|&dense_index| dense_index)
+            .map(|dense_index| (self.entities[dense_index], &self.components[dense_index]))
+    }
+    
     /// Iterates over all entities and mutable components.
     pub fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, &mut T)> {
         self.entities.iter().copied()
             .zip(self.components.iter_mut())
     }
+    
+    /// Returns the dense array of components for direct mutation.
+    pub fn components_mut(&mut self) -> &mut [T] {
+        &mut self.components
+    }
+    
+    /// Consumes the set, yielding every entity and component in dense order.
+    pub fn into_entries(self) -> impl Iterator<Item = (Entity, T)> {
+        self.entities.into_iter().zip(self.components)
+    }
 }
 
 impl<T: Component> ComponentStorage for SparseSet<T> {
@@ -140,11 +281,23 @@ impl<T: Component> ComponentStorage for SparseSet<T> {
     }
     
     fn clear_for_entity(&mut self, entity: Entity) {
-        self.remove(entity);
+        self.remove_with_hooks(entity, WorldHooks::new(true));
     }
     
-    fn type_id(&self) -> TypeId {
-        TypeId::of::<T>()
+    fn contains(&self, entity: Entity) -> bool {
+        self.contains(entity)
+    }
+    
+    fn len(&self) -> usize {
+        self.len()
+    }
+    
+    fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+    
+    fn memory_usage(&self) -> usize {
+        self.memory_usage()
     }
     
     fn as_any(&self) -> &dyn Any {
@@ -179,8 +332,8 @@ mod tests {
         let mut storage = SparseSet::<TestComponent>::new();
         let entity = Entity::from_raw_parts(3, 1);
         
-        storage.insert(entity, TestComponent(10));
-        storage.insert(entity, TestComponent(20));
+        assert_eq!(storage.insert(entity, TestComponent(10)), None);
+        assert_eq!(storage.insert(entity, TestComponent(20)), Some(TestComponent(10)));
         
         assert_eq!(storage.get(entity), Some(&TestComponent(20)));
     }
@@ -227,4 +380,120 @@ mod tests {
         
         assert_eq!(results, vec![(e1, 1), (e2, 2), (e3, 3)]);
     }
+
+    #[test]
+    fn test_sparse_set_contains() {
+        let mut storage = SparseSet::<TestComponent>::new();
+        let present = Entity::from_raw_parts(4, 1);
+        let absent = Entity::from_raw_parts(7, 1);
+        let stale = Entity::from_raw_parts(4, 0);
+        
+        storage.insert(present, TestComponent(1));
+        
+        assert!(storage.contains(present));
+        assert!(!storage.contains(absent));
+        assert!(!storage.contains(stale)); // Same index, older generation
+    }
+
+    #[test]
+    fn test_sparse_set_len() {
+        let mut storage = SparseSet::<TestComponent>::new();
+        assert_eq!(storage.len(), 0);
+        
+        let e1 = Entity::from_raw_parts(0, 1);
+        let e2 = Entity::from_raw_parts(9, 1);
+        
+        storage.insert(e1, TestComponent(1));
+        storage.insert(e2, TestComponent(2));
+        storage.insert(e2, TestComponent(3)); // Update does not grow
+        assert_eq!(storage.len(), 2);
+        
+        storage.remove(e1);
+        assert_eq!(storage.len(), 1);
+        
+        storage.remove(e1); // Removing again is a no-op
+        assert_eq!(storage.len(), 1);
+    }
+
+    #[test]
+    fn test_sparse_set_retain() {
+        let mut storage = SparseSet::<TestComponent>::new();
+        let entities: Vec<_> = (0..10)
+            .map(|i| Entity::from_raw_parts(i, 1))
+            .collect();
+        
+        for (i, &entity) in entities.iter().enumerate() {
+            storage.insert(entity, TestComponent(i as i32));
+        }
+        
+        storage.retain(|_, c| c.0 % 2 == 0);
+        
+        // Dense arrays are compacted
+        assert_eq!(storage.len(), 5);
+        assert_eq!(storage.entities().len(), 5);
+        
+        // Sparse indices still resolve to the right components
+        for (i, &entity) in entities.iter().enumerate() {
+            if i % 2 == 0 {
+                assert_eq!(storage.get(entity), Some(&TestComponent(i as i32)));
+            } else {
+                assert!(!storage.contains(entity));
+            }
+        }
+        
+        for (&entity, (iter_entity, _)) in storage.entities().iter().zip(storage.iter()) {
+            assert_eq!(entity, iter_entity);
+        }
+    }
+
+    #[test]
+    fn test_sparse_set_retain_can_mutate() {
+        let mut storage = SparseSet::<TestComponent>::new();
+        let e1 = Entity::from_raw_parts(1, 1);
+        let e2 = Entity::from_raw_parts(2, 1);
+        
+        storage.insert(e1, TestComponent(1));
+        storage.insert(e2, TestComponent(2));
+        
+        storage.retain(|_, c| {
+            c.0 *= 10;
+            true
+        });
+        
+        assert_eq!(storage.get(e1), Some(&TestComponent(10)));
+        assert_eq!(storage.get(e2), Some(&TestComponent(20)));
+    }
+
+    #[test]
+    fn test_sparse_set_memory_usage() {
+        let mut storage = SparseSet::<TestComponent>::new();
+        assert_eq!(storage.memory_usage(), 0);
+        
+        for i in 0..100 {
+            storage.insert(Entity::from_raw_parts(i, 1), TestComponent(i as i32));
+        }
+        
+        let dense = 100 * (size_of::<Entity>() + size_of::<TestComponent>());
+        let sparse = 100 * size_of::<Option<usize>>();
+        assert!(storage.memory_usage() >= dense + sparse);
+        
+        let storage: &dyn ComponentStorage = &storage;
+        assert_eq!(storage.type_name(), std::any::type_name::<TestComponent>());
+    }
+
+    #[test]
+    fn test_sparse_set_reserve() {
+        let mut storage = SparseSet::<TestComponent>::new();
+        storage.reserve(1000);
+        
+        let capacity = storage.components.capacity();
+        assert!(capacity >= 1000);
+        
+        for i in 0..1000 {
+            storage.insert(Entity::from_raw_parts(i, 1), TestComponent(i as i32));
+        }
+        
+        assert_eq!(storage.components.capacity(), capacity);
+        assert!(storage.sparse.capacity() >= 1000);
+    }
 }
\ No newline at end of file
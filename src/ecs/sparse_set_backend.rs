@@ -5,7 +5,8 @@
 //! - O(1) component access
 //! - Less cache-friendly iteration compared to archetype storage
 
-use crate::{backend::EcsBackend, Component, ComponentStorage, Entity, GammaVkError, sparse_set::SparseSet};
+use crate::GammaVkError;
+use super::{backend::EcsBackend, Component, component::ComponentStorage, Entity, StorageKind, storage::{Column, EitherIter}};
 use std::any::TypeId;
 use std::collections::HashMap;
 
@@ -14,9 +15,14 @@ use std::collections::HashMap;
 struct EntityMeta {
     generation: u32,
     alive: bool,
+    
+    /// Highest generation ever issued for this index; reuse continues above it
+    /// so handles from before a `create_entity_at` never become valid again
+    max_generation: u32,
 }
 
 /// Sparse set backend for ECS storage.
+#[derive(Default)]
 pub struct SparseSetBackend {
     /// Entity metadata storage
     entities: Vec<EntityMeta>,
@@ -26,47 +32,85 @@ pub struct SparseSetBackend {
     
     /// Component storages by type
     storages: HashMap<TypeId, Box<dyn ComponentStorage>>,
+    
+    /// Storage layouts chosen with `register_component`; other types use sparse sets
+    storage_kinds: HashMap<TypeId, StorageKind>,
 }
 
-impl Default for SparseSetBackend {
-    fn default() -> Self {
-        Self {
-            entities: Vec::new(),
-            free_list: Vec::new(),
-            storages: HashMap::new(),
+impl SparseSetBackend {
+    /// How far past the allocated entity slots `create_entity_at` may reach.
+    /// 
+    /// Skipped slots are allocated as free ids, so a stray index near `u32::MAX`
+    /// would otherwise allocate billions of entries.
+    pub const MAX_INDEX_GAP: u32 = 1 << 20;
+    
+    /// Marks `entity` alive with its own generation, growing the slots if needed.
+    /// 
+    /// `allow_gap` skips the `MAX_INDEX_GAP` check, for indices taken from a
+    /// real world rather than from user input.
+    /// 
+    /// Returns whether an existing slot was reused; the caller must then remove
+    /// its index from the free list.
+    fn revive_at(&mut self, entity: Entity, allow_gap: bool) -> Result<bool, GammaVkError> {
+        let index = entity.index() as usize;
+        
+        if !allow_gap && index >= self.entities.len() + Self::MAX_INDEX_GAP as usize {
+            return Err(GammaVkError::EntityIndexOutOfRange(entity));
+        }
+        
+        // Indices skipped over become free slots for later spawns
+        let reused = index < self.entities.len();
+        if !reused {
+            let first_new = self.entities.len() as u32;
+            self.entities.resize(index + 1, EntityMeta {
+                generation: 0,
+                alive: false,
+                max_generation: 0,
+            });
+            self.free_list.extend(first_new..entity.index());
+        } else {
+            let meta = &self.entities[index];
+            if meta.alive {
+                return Err(GammaVkError::EntityAlreadyAlive(Entity::from_raw_parts(entity.index(), meta.generation)));
+            }
         }
+        
+        let meta = &mut self.entities[index];
+        meta.generation = entity.generation();
+        meta.alive = true;
+        meta.max_generation = meta.max_generation.max(entity.generation());
+        Ok(reused)
     }
-}
-
-impl SparseSetBackend {
+    
     /// Gets or creates a storage for a component type.
-    fn get_or_create_storage<C: Component>(&mut self) -> &mut SparseSet<C> {
+    fn get_or_create_storage<C: Component>(&mut self) -> &mut Column<C> {
         let type_id = TypeId::of::<C>();
+        let kind = self.storage_kinds.get(&type_id).copied().unwrap_or_default();
         
         self.storages
             .entry(type_id)
-            .or_insert_with(|| Box::new(SparseSet::<C>::new()))
+            .or_insert_with(|| Box::new(Column::<C>::new(kind)))
             .as_any_mut()
-            .downcast_mut::<SparseSet<C>>()
+            .downcast_mut::<Column<C>>()
             .expect("Storage type mismatch")
     }
     
     /// Gets a storage for a component type if it exists.
-    fn get_storage<C: Component>(&self) -> Option<&SparseSet<C>> {
+    fn get_storage<C: Component>(&self) -> Option<&Column<C>> {
         let type_id = TypeId::of::<C>();
         
         self.storages
             .get(&type_id)
-            .and_then(|storage| storage.as_any().downcast_ref::<SparseSet<C>>())
+            .and_then(|storage| storage.as_any().downcast_ref::<Column<C>>())
     }
     
     /// Gets a mutable storage for a component type if it exists.
-    fn get_storage_mut<C: Component>(&mut self) -> Option<&mut SparseSet<C>> {
+    fn get_storage_mut<C: Component>(&mut self) -> Option<&mut Column<C>> {
         let type_id = TypeId::of::<C>();
         
         self.storages
             .get_mut(&type_id)
-            .and_then(|storage| storage.as_any_mut().downcast_mut::<SparseSet<C>>())
+            .and_then(|storage| storage.as_any_mut().downcast_mut::<Column<C>>())
     }
 }
 
@@ -75,7 +119,8 @@ impl EcsBackend for SparseSetBackend {
         if let Some(id) = self.free_list.pop() {
             // Reuse ID with incremented generation
             let meta = &mut self.entities[id as usize];
-            meta.generation = meta.generation.wrapping_add(1);
+            meta.generation = meta.max_generation.wrapping_add(1);
+            meta.max_generation = meta.generation;
             meta.alive = true;
             Entity::from_raw_parts(id, meta.generation)
         } else {
@@ -84,11 +129,59 @@ impl EcsBackend for SparseSetBackend {
             self.entities.push(EntityMeta {
                 generation: 0,
                 alive: true,
+                max_generation: 0,
             });
             Entity::from_raw_parts(id, 0)
         }
     }
     
+    fn spawn_batch<C: Component>(&mut self, components: impl IntoIterator<Item = C>) -> Vec<Entity> {
+        let components: Vec<C> = components.into_iter().collect();
+        let entities: Vec<Entity> = (0..components.len())
+            .map(|_| self.create_entity())
+            .collect();
+        
+        // Fresh entities are alive, so insert directly into a single storage handle
+        let storage = self.get_or_create_storage::<C>();
+        storage.reserve(components.len());
+        for (&entity, component) in entities.iter().zip(components) {
+            storage.insert(entity, component);
+        }
+        
+        entities
+    }
+    
+    fn create_entity_at(&mut self, entity: Entity) -> Result<(), GammaVkError> {
+        if self.revive_at(entity, false)? {
+            self.free_list.retain(|&free| free != entity.index());
+        }
+        Ok(())
+    }
+    
+    fn create_entities_at(&mut self, entities: &[Entity]) -> Result<(), GammaVkError> {
+        let mut reused = false;
+        let result = entities.iter().try_for_each(|&entity| {
+            reused |= self.revive_at(entity, true)?;
+            Ok(())
+        });
+        
+        // Drop the revived slots from the free list in one pass rather than per entity
+        if reused {
+            let metas = &self.entities;
+            self.free_list.retain(|&free| !metas[free as usize].alive);
+        }
+        result
+    }
+    
+    fn entities(&self) -> Vec<Entity> {
+        self.entities
+            .iter()
+            .enumerate()
+            .filter(|(_, meta)| meta.alive)
+            .map(|(index, meta)| Entity::from_raw_parts(index as u32, meta.generation))
+            .collect()
+    }
+    
     fn destroy_entity(&mut self, entity: Entity) -> Result<(), GammaVkError> {
         let index = entity.index() as usize;
         
@@ -116,6 +209,50 @@ impl EcsBackend for SparseSetBackend {
         Ok(())
     }
     
+    fn despawn_batch(&mut self, entities: impl IntoIterator<Item = Entity>) -> Vec<Entity> {
+        // Mark live entities dead first; duplicates are skipped as already dead
+        let mut despawned = Vec::new();
+        for entity in entities {
+            if let Some(meta) = self.entities.get_mut(entity.index() as usize)
+                && meta.alive
+                && meta.generation == entity.generation()
+            {
+                meta.alive = false;
+                despawned.push(entity);
+            }
+        }
+        
+        // Sweep each storage once for the whole batch
+        for storage in self.storages.values_mut() {
+            for &entity in &despawned {
+                storage.clear_for_entity(entity);
+            }
+        }
+        
+        self.free_list.extend(despawned.iter().map(|entity| entity.index()));
+        despawned
+    }
+    
+    fn reserve(&mut self, additional_entities: usize) {
+        // Freed IDs are reused before new metadata slots are pushed
+        let needed = additional_entities.saturating_sub(self.free_list.len());
+        self.entities.reserve(needed);
+    }
+    
+    fn reserve_component<C: Component>(&mut self, additional: usize) {
+        self.get_or_create_storage::<C>().reserve(additional);
+    }
+    
+    fn register_component<C: Component>(&mut self, kind: StorageKind) {
+        self.storage_kinds.insert(TypeId::of::<C>(), kind);
+        
+        // Move existing components over so registration order does not matter
+        if let Some(column) = self.get_storage_mut::<C>() {
+            let existing = std::mem::replace(column, Column::new(kind));
+            *column = existing.convert(kind);
+        }
+    }
+    
     fn is_alive(&self, entity: Entity) -> bool {
         let index = entity.index() as usize;
         
@@ -126,13 +263,16 @@ impl EcsBackend for SparseSetBackend {
     }
     
     fn add_component<C: Component>(&mut self, entity: Entity, component: C) -> Result<(), GammaVkError> {
+        self.replace_component(entity, component).map(|_| ())
+    }
+    
+    fn replace_component<C: Component>(&mut self, entity: Entity, component: C) -> Result<Option<C>, GammaVkError> {
         if !self.is_alive(entity) {
             return Err(GammaVkError::EntityNotFound(entity));
         }
         
         let storage = self.get_or_create_storage::<C>();
-        storage.insert(entity, component);
-        Ok(())
+        Ok(storage.insert(entity, component))
     }
     
     fn get_component<C: Component>(&self, entity: Entity) -> Option<&C> {
@@ -153,6 +293,63 @@ impl EcsBackend for SparseSetBackend {
             .and_then(|storage| storage.get_mut(entity))
     }
     
+    fn get_or_insert_with<C: Component>(&mut self, entity: Entity, f: impl FnOnce() -> C) -> Option<&mut C> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        
+        let storage = self.get_or_create_storage::<C>();
+        if !storage.contains(entity) {
+            storage.insert(entity, f());
+        }
+        storage.get_mut(entity)
+    }
+    
+    fn get_component_pair_mut<C: Component>(&mut self, a: Entity, b: Entity) -> Option<(&mut C, &mut C)> {
+        if !self.is_alive(a) || !self.is_alive(b) {
+            return None;
+        }
+        
+        self.get_storage_mut::<C>()?.get_pair_mut(a, b)
+    }
+    
+    fn get_components2_mut<A: Component, B: Component>(&mut self, entity: Entity) -> Option<(&mut A, &mut B)> {
+        let (type_a, type_b) = (TypeId::of::<A>(), TypeId::of::<B>());
+        assert_ne!(type_a, type_b, "get_components2_mut requires two distinct component types");
+        
+        if !self.is_alive(entity) {
+            return None;
+        }
+        
+        // Distinct types live in distinct storages, so both borrows are disjoint
+        let [Some(a), Some(b)] = self.storages.get_disjoint_mut([&type_a, &type_b]) else {
+            return None;
+        };
+        let a = a.as_any_mut().downcast_mut::<Column<A>>()?.get_mut(entity)?;
+        let b = b.as_any_mut().downcast_mut::<Column<B>>()?.get_mut(entity)?;
+        Some((a, b))
+    }
+    
+    fn take_component<C: Component>(&mut self, entity: Entity) -> Option<C> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        
+        self.get_storage_mut::<C>()?.take(entity)
+    }
+    
+    fn clear_component<C: Component>(&mut self) -> usize {
+        let Some(mut storage) = self.storages.remove(&TypeId::of::<C>()) else {
+            return 0;
+        };
+        
+        storage
+            .as_any_mut()
+            .downcast_mut::<Column<C>>()
+            .expect("Storage type mismatch")
+            .clear()
+    }
+    
     fn remove_component<C: Component>(&mut self, entity: Entity) -> Result<(), GammaVkError> {
         if !self.is_alive(entity) {
             return Err(GammaVkError::EntityNotFound(entity));
@@ -171,11 +368,76 @@ impl EcsBackend for SparseSetBackend {
             .unwrap_or_default()
     }
     
+    fn query_component_sorted<C: Component>(&self) -> Vec<(Entity, &C)> {
+        self.get_storage::<C>()
+            .map(|storage| storage.iter_sorted().collect())
+            .unwrap_or_default()
+    }
+    
     fn query_component_mut<C: Component>(&mut self) -> Vec<(Entity, &mut C)> {
         self.get_storage_mut::<C>()
             .map(|storage| storage.iter_mut().collect())
             .unwrap_or_default()
     }
+    
+    fn map_component<C: Component>(&mut self, f: impl FnMut(&mut C)) {
+        if let Some(storage) = self.get_storage_mut::<C>() {
+            storage.for_each_component_mut(f);
+        }
+    }
+    
+    fn iter_components2<A: Component, B: Component>(&self) -> impl Iterator<Item = (Entity, &A, &B)> {
+        self.get_storage::<A>()
+            .zip(self.get_storage::<B>())
+            .into_iter()
+            .flat_map(|(a, b)| {
+                // Drive iteration from the smaller storage and probe the other
+                let driver = if a.len() <= b.len() { EitherIter::Left(a.entities()) } else { EitherIter::Right(b.entities()) };
+                driver.filter_map(move |entity| Some((entity, a.get(entity)?, b.get(entity)?)))
+            })
+    }
+    
+    fn for_each_mut<A: Component, B: Component>(&mut self, mut f: impl FnMut(Entity, &mut A, &B)) {
+        let (type_a, type_b) = (TypeId::of::<A>(), TypeId::of::<B>());
+        assert_ne!(type_a, type_b, "for_each_mut requires two distinct component types");
+        
+        // Borrow both storages at once; A mutably, B shared
+        let [Some(a), Some(b)] = self.storages.get_disjoint_mut([&type_a, &type_b]) else {
+            return;
+        };
+        let (Some(a), Some(b)) = (
+            a.as_any_mut().downcast_mut::<Column<A>>(),
+            b.as_any().downcast_ref::<Column<B>>(),
+        ) else {
+            return;
+        };
+        
+        // Walk A's storage with a single probe into B per element
+        for (entity, component_a) in a.iter_mut() {
+            if let Some(component_b) = b.get(entity) {
+                f(entity, component_a, component_b);
+            }
+        }
+    }
+    
+    fn component_names(&self, entity: Entity) -> Vec<&'static str> {
+        if !self.is_alive(entity) {
+            return Vec::new();
+        }
+        
+        self.storages
+            .values()
+            .filter(|storage| storage.contains(entity))
+            .map(|storage| storage.type_name())
+            .collect()
+    }
+    
+    fn storage_memory_report(&self) -> Vec<(&'static str, usize)> {
+        self.storages
+            .values()
+            .map(|storage| (storage.type_name(), storage.memory_usage()))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -218,4 +480,43 @@ mod tests {
         assert_eq!(entity2.index(), id1);
         assert_ne!(entity2.generation(), entity1.generation());
     }
+
+    #[test]
+    fn test_reserve_prevents_regrowth() {
+        let mut backend = SparseSetBackend::default();
+        backend.reserve(10_000);
+        
+        let capacity = backend.entities.capacity();
+        assert!(capacity >= 10_000);
+        
+        for _ in 0..10_000 {
+            backend.create_entity();
+        }
+        
+        assert_eq!(backend.entities.capacity(), capacity);
+    }
+
+    #[test]
+    fn test_create_entities_at_removes_revived_slots_from_free_list() {
+        let mut backend = SparseSetBackend::default();
+        let entities: Vec<Entity> = (0..4).map(|_| backend.create_entity()).collect();
+        assert_eq!(backend.despawn_batch(entities.iter().copied()).len(), 4);
+        
+        // Revive two freed slots and grow past the end, leaving index 5 free
+        let revived = [entities[1], entities[3], Entity::from_raw_parts(6, 2)];
+        backend.create_entities_at(&revived).unwrap();
+        for &entity in &revived {
+            assert!(backend.is_alive(entity));
+        }
+        
+        let mut free = backend.free_list.clone();
+        free.sort_unstable();
+        assert_eq!(free, vec![0, 2, 4, 5]);
+        
+        assert!(matches!(
+            backend.create_entities_at(&[entities[0], entities[3]]),
+            Err(GammaVkError::EntityAlreadyAlive(_))
+        ));
+        assert!(!backend.free_list.contains(&0));
+    }
 }
\ No newline at end of file
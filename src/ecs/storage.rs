@@ -0,0 +1,480 @@
+//! Per-component-type storage layouts
+//! 
+//! Each component type lives in one column, laid out either as a sparse set
+//! (compact, good for rare components) or as a dense array indexed directly by
+//! entity (no indirection, good for components nearly every entity has).
+
+use super::{Component, component::ComponentStorage, Entity, WorldHooks, sparse_set::SparseSet};
+use std::any::Any;
+
+/// How a component type is laid out in memory.
+/// 
+/// Register a kind with `World::register_component`; unregistered types use
+/// `SparseSet`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StorageKind {
+    /// Packed components plus an entity-indexed lookup table.
+    /// 
+    /// Memory scales with the number of components, so this suits components
+    /// only a few entities have, such as a temporary status effect.
+    #[default]
+    SparseSet,
+    
+    /// One slot per entity index, holding the component or nothing.
+    /// 
+    /// Lookups skip the indirection of a sparse set, but memory scales with the
+    /// highest entity index, so this suits components nearly every entity has,
+    /// such as a transform.
+    Dense,
+}
+
+/// One of two iterator types with the same item, chosen at runtime.
+/// 
+/// Columns return this, sparse layout on the left and dense on the right, so
+/// iterating a column stays static and allocation-free rather than boxing.
+pub(crate) enum EitherIter<L, R> {
+    Left(L),
+    Right(R),
+}
+
+impl<L: Iterator, R: Iterator<Item = L::Item>> Iterator for EitherIter<L, R> {
+    type Item = L::Item;
+    
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Left(iter) => iter.next(),
+            Self::Right(iter) => iter.next(),
+        }
+    }
+    
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Left(iter) => iter.size_hint(),
+            Self::Right(iter) => iter.size_hint(),
+        }
+    }
+}
+
+/// Dense storage: slot `i` holds the component of the entity with index `i`.
+pub(crate) struct DenseStorage<T: Component> {
+    /// Entity-indexed slots; the entity records the generation that owns the slot
+    slots: Vec<Option<(Entity, T)>>,
+    
+    /// Number of occupied slots
+    len: usize,
+    
+    /// Component type name, captured at creation for memory reports
+    type_name: &'static str,
+}
+
+impl<T: Component> DenseStorage<T> {
+    /// Creates a new empty dense storage.
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            len: 0,
+            type_name: std::any::type_name::<T>(),
+        }
+    }
+    
+    /// Reserves slots for at least `additional` more entity indices.
+    pub fn reserve(&mut self, additional: usize) {
+        self.slots.reserve(additional);
+    }
+    
+    /// Inserts a component for an entity.
+    /// 
+    /// Returns the component the entity previously had, if any.
+    pub fn insert(&mut self, entity: Entity, component: T) -> Option<T> {
+        let index = entity.index() as usize;
+        
+        if index >= self.slots.len() {
+            self.slots.resize_with(index + 1, || None);
+        }
+        
+        match self.slots[index].replace((entity, component)) {
+            // A stale generation's component did not belong to this entity
+            Some((previous_owner, old)) => (previous_owner == entity).then_some(old),
+            None => {
+                self.len += 1;
+                None
+            }
+        }
+    }
+    
+    /// Gets a component for an entity.
+    pub fn get(&self, entity: Entity) -> Option<&T> {
+        match self.slots.get(entity.index() as usize)? {
+            Some((owner, component)) if *owner == entity => Some(component),
+            _ => None,
+        }
+    }
+    
+    /// Gets a mutable component for an entity.
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        match self.slots.get_mut(entity.index() as usize)? {
+            Some((owner, component)) if *owner == entity => Some(component),
+            _ => None,
+        }
+    }
+    
+    /// Gets mutable components for two different entities at once.
+    /// 
+    /// Returns None if the entities are the same or either lacks a component.
+    pub fn get_pair_mut(&mut self, a: Entity, b: Entity) -> Option<(&mut T, &mut T)> {
+        if !self.contains(a) || !self.contains(b) {
+            return None;
+        }
+        
+        // Distinct indices are distinct slots
+        let [Some((_, a)), Some((_, b))] = self
+            .slots
+            .get_disjoint_mut([a.index() as usize, b.index() as usize])
+            .ok()?
+        else {
+            return None;
+        };
+        Some((a, b))
+    }
+    
+    /// Checks if an entity has a component in this storage.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.get(entity).is_some()
+    }
+    
+    /// Returns the number of components stored.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    
+    /// Removes a component for an entity, running its `on_remove` hook first.
+    fn remove_with_hooks(&mut self, entity: Entity, hooks: WorldHooks) -> bool {
+        match self.take(entity) {
+            Some(mut component) => {
+                component.on_remove(entity, &hooks);
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// Removes and returns a component for an entity without running its hook.
+    pub fn take(&mut self, entity: Entity) -> Option<T> {
+        if !self.contains(entity) {
+            return None;
+        }
+        
+        let (_, component) = self.slots[entity.index() as usize].take()?;
+        self.len -= 1;
+        Some(component)
+    }
+    
+    /// Removes every component, running each `on_remove` hook.
+    /// 
+    /// Returns the number of components removed.
+    pub fn clear(&mut self) -> usize {
+        let hooks = WorldHooks::new(false);
+        let removed = self.len;
+        for (entity, mut component) in self.slots.drain(..).flatten() {
+            component.on_remove(entity, &hooks);
+        }
+        self.len = 0;
+        removed
+    }
+    
+    /// Returns the approximate number of bytes allocated by this storage.
+    pub fn memory_usage(&self) -> usize {
+        self.slots.capacity() * size_of::<Option<(Entity, T)>>()
+    }
+    
+    /// Iterates over all entities and components in ascending entity-index order.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &T)> {
+        self.slots
+            .iter()
+            .flatten()
+            .map(|(entity, component)| (*entity, component))
+    }
+    
+    /// Iterates over all entities and mutable components.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, &mut T)> {
+        self.slots
+            .iter_mut()
+            .flatten()
+            .map(|(entity, component)| (*entity, component))
+    }
+    
+    /// Consumes the storage, yielding every entity and component.
+    pub fn into_entries(self) -> impl Iterator<Item = (Entity, T)> {
+        self.slots.into_iter().flatten()
+    }
+}
+
+impl<T: Component> ComponentStorage for DenseStorage<T> {
+    fn remove(&mut self, entity: Entity) -> bool {
+        self.remove_with_hooks(entity, WorldHooks::new(false))
+    }
+    
+    fn clear_for_entity(&mut self, entity: Entity) {
+        self.remove_with_hooks(entity, WorldHooks::new(true));
+    }
+    
+    fn contains(&self, entity: Entity) -> bool {
+        self.contains(entity)
+    }
+    
+    fn len(&self) -> usize {
+        self.len()
+    }
+    
+    fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+    
+    fn memory_usage(&self) -> usize {
+        self.memory_usage()
+    }
+    
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// The storage of one component type, in whichever layout it was registered with.
+pub(crate) enum Column<T: Component> {
+    Sparse(SparseSet<T>),
+    Dense(DenseStorage<T>),
+}
+
+impl<T: Component> Column<T> {
+    /// Creates an empty column with the given layout.
+    pub fn new(kind: StorageKind) -> Self {
+        match kind {
+            StorageKind::SparseSet => Self::Sparse(SparseSet::new()),
+            StorageKind::Dense => Self::Dense(DenseStorage::new()),
+        }
+    }
+    
+    /// Returns the layout of this column.
+    pub fn kind(&self) -> StorageKind {
+        match self {
+            Self::Sparse(_) => StorageKind::SparseSet,
+            Self::Dense(_) => StorageKind::Dense,
+        }
+    }
+    
+    /// Moves every component into a new column with the given layout.
+    pub fn convert(self, kind: StorageKind) -> Self {
+        if self.kind() == kind {
+            return self;
+        }
+        
+        let mut column = Self::new(kind);
+        column.reserve(self.len());
+        let entries = match self {
+            Self::Sparse(set) => EitherIter::Left(set.into_entries()),
+            Self::Dense(dense) => EitherIter::Right(dense.into_entries()),
+        };
+        for (entity, component) in entries {
+            column.insert(entity, component);
+        }
+        column
+    }
+    
+    pub fn reserve(&mut self, additional: usize) {
+        match self {
+            Self::Sparse(set) => set.reserve(additional),
+            Self::Dense(dense) => dense.reserve(additional),
+        }
+    }
+    
+    pub fn insert(&mut self, entity: Entity, component: T) -> Option<T> {
+        match self {
+            Self::Sparse(set) => set.insert(entity, component),
+            Self::Dense(dense) => dense.insert(entity, component),
+        }
+    }
+    
+    pub fn get(&self, entity: Entity) -> Option<&T> {
+        match self {
+            Self::Sparse(set) => set.get(entity),
+            Self::Dense(dense) => dense.get(entity),
+        }
+    }
+    
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        match self {
+            Self::Sparse(set) => set.get_mut(entity),
+            Self::Dense(dense) => dense.get_mut(entity),
+        }
+    }
+    
+    pub fn get_pair_mut(&mut self, a: Entity, b: Entity) -> Option<(&mut T, &mut T)> {
+        match self {
+            Self::Sparse(set) => set.get_pair_mut(a, b),
+            Self::Dense(dense) => dense.get_pair_mut(a, b),
+        }
+    }
+    
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.storage().contains(entity)
+    }
+    
+    pub fn len(&self) -> usize {
+        self.storage().len()
+    }
+    
+    pub fn remove(&mut self, entity: Entity) -> bool {
+        self.storage_mut().remove(entity)
+    }
+    
+    pub fn take(&mut self, entity: Entity) -> Option<T> {
+        match self {
+            Self::Sparse(set) => set.take(entity),
+            Self::Dense(dense) => dense.take(entity),
+        }
+    }
+    
+    pub fn clear(&mut self) -> usize {
+        match self {
+            Self::Sparse(set) => set.clear(),
+            Self::Dense(dense) => dense.clear(),
+        }
+    }
+    
+    /// Iterates over the entities that have a component.
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        match self {
+            Self::Sparse(set) => EitherIter::Left(set.entities().iter().copied()),
+            Self::Dense(dense) => EitherIter::Right(dense.iter().map(|(entity, _)| entity)),
+        }
+    }
+    
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &T)> + '_ {
+        match self {
+            Self::Sparse(set) => EitherIter::Left(set.iter()),
+            Self::Dense(dense) => EitherIter::Right(dense.iter()),
+        }
+    }
+    
+    /// Iterates in ascending entity-index order; dense columns always do.
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (Entity, &T)> + '_ {
+        match self {
+            Self::Sparse(set) => EitherIter::Left(set.iter_sorted()),
+            Self::Dense(dense) => EitherIter::Right(dense.iter()),
+        }
+    }
+    
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, &mut T)> + '_ {
+        match self {
+            Self::Sparse(set) => EitherIter::Left(set.iter_mut()),
+            Self::Dense(dense) => EitherIter::Right(dense.iter_mut()),
+        }
+    }
+    
+    /// Calls `f` on every component without looking up entities.
+    pub fn for_each_component_mut(&mut self, f: impl FnMut(&mut T)) {
+        match self {
+            Self::Sparse(set) => set.components_mut().iter_mut().for_each(f),
+            Self::Dense(dense) => dense.iter_mut().map(|(_, component)| component).for_each(f),
+        }
+    }
+    
+    fn storage(&self) -> &dyn ComponentStorage {
+        match self {
+            Self::Sparse(set) => set,
+            Self::Dense(dense) => dense,
+        }
+    }
+    
+    fn storage_mut(&mut self) -> &mut dyn ComponentStorage {
+        match self {
+            Self::Sparse(set) => set,
+            Self::Dense(dense) => dense,
+        }
+    }
+}
+
+impl<T: Component> ComponentStorage for Column<T> {
+    fn remove(&mut self, entity: Entity) -> bool {
+        self.storage_mut().remove(entity)
+    }
+    
+    fn clear_for_entity(&mut self, entity: Entity) {
+        self.storage_mut().clear_for_entity(entity);
+    }
+    
+    fn contains(&self, entity: Entity) -> bool {
+        self.storage().contains(entity)
+    }
+    
+    fn len(&self) -> usize {
+        self.storage().len()
+    }
+    
+    fn type_name(&self) -> &'static str {
+        self.storage().type_name()
+    }
+    
+    fn memory_usage(&self) -> usize {
+        self.storage().memory_usage()
+    }
+    
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[derive(Debug, Clone, PartialEq)]
+    struct TestComponent(i32);
+    impl Component for TestComponent {}
+    
+    #[test]
+    fn test_dense_storage_insert_get_take() {
+        let mut storage = DenseStorage::<TestComponent>::new();
+        let a = Entity::from_raw_parts(3, 0);
+        let b = Entity::from_raw_parts(0, 0);
+        
+        assert_eq!(storage.insert(a, TestComponent(1)), None);
+        assert_eq!(storage.insert(b, TestComponent(2)), None);
+        assert_eq!(storage.insert(a, TestComponent(3)), Some(TestComponent(1)));
+        assert_eq!(storage.len(), 2);
+        assert_eq!(storage.get(a), Some(&TestComponent(3)));
+        
+        // A newer generation at the same index does not see the old component
+        let stale = Entity::from_raw_parts(3, 1);
+        assert_eq!(storage.get(stale), None);
+        assert_eq!(storage.take(stale), None);
+        
+        assert_eq!(storage.take(a), Some(TestComponent(3)));
+        assert_eq!(storage.len(), 1);
+        assert_eq!(storage.iter().map(|(entity, _)| entity).collect::<Vec<_>>(), vec![b]);
+    }
+    
+    #[test]
+    fn test_column_convert_keeps_components() {
+        let mut column = Column::<TestComponent>::new(StorageKind::SparseSet);
+        for i in 0..5 {
+            column.insert(Entity::from_raw_parts(i, 0), TestComponent(i as i32));
+        }
+        
+        let column = column.convert(StorageKind::Dense);
+        assert_eq!(column.kind(), StorageKind::Dense);
+        assert_eq!(column.len(), 5);
+        for i in 0..5 {
+            assert_eq!(column.get(Entity::from_raw_parts(i, 0)), Some(&TestComponent(i as i32)));
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+//! System trait for running logic over the ECS world
+//! 
+//! Systems are units of logic that read and mutate a World. They can be run
+//! directly with `World::run_system` until a scheduler exists.
+
+use super::{backend::EcsBackend, SparseSetBackend, World};
+
+/// Trait for logic that runs over a world.
+/// 
+/// Closures taking `&mut World<B>` implement this automatically; implement it
+/// on a struct when the system carries its own state between runs.
+/// 
+/// # Example
+/// ```
+/// # use gamma_vk::ecs::{Component, System, World};
+/// # struct Position;
+/// # impl Component for Position {}
+/// struct CountEntities { seen: usize }
+/// 
+/// impl System for CountEntities {
+///     fn run(&mut self, world: &mut World) {
+///         self.seen = world.query::<Position>().count();
+///     }
+/// }
+/// ```
+pub trait System<B: EcsBackend = SparseSetBackend> {
+    /// Runs the system once over the world.
+    fn run(&mut self, world: &mut World<B>);
+}
+
+impl<B: EcsBackend, F: FnMut(&mut World<B>)> System<B> for F {
+    fn run(&mut self, world: &mut World<B>) {
+        self(world)
+    }
+}
\ No newline at end of file
@@ -3,7 +3,10 @@
 //! World manages entities, components, and systems. It provides a type-safe
 //! API over the underlying ECS backend.
 
-use crate::{backend::EcsBackend, Component, Entity, GammaVkError, SparseSetBackend};
+use crate::GammaVkError;
+use super::{backend::EcsBackend, registry::CapturedComponents, Component, ComponentRegistry, Entity, SparseSetBackend, StorageKind, System};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
 
 /// The main ECS world that manages entities and components.
 /// 
@@ -11,6 +14,15 @@ use crate::{backend::EcsBackend, Component, Entity, GammaVkError, SparseSetBacke
 /// storage strategies to be used.
 pub struct World<B: EcsBackend = SparseSetBackend> {
     backend: B,
+    
+    /// Singleton resources keyed by type, shared by all systems
+    resources: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    
+    /// Number of structural changes made so far
+    tick: u64,
+    
+    /// Tick of the last structural change to each live entity
+    last_changed: HashMap<Entity, u64>,
 }
 
 impl<B: EcsBackend> World<B> {
@@ -18,21 +30,92 @@ impl<B: EcsBackend> World<B> {
     pub fn new() -> Result<Self, GammaVkError> {
         Ok(Self {
             backend: B::default(),
+            resources: HashMap::new(),
+            tick: 0,
+            last_changed: HashMap::new(),
         })
     }
     
     /// Creates a new entity using the builder pattern.
-    pub fn spawn(&mut self) -> EntityBuilder<B> {
+    pub fn spawn(&mut self) -> EntityBuilder<'_, B> {
         let entity = self.backend.create_entity();
+        self.mark_changed(entity);
         EntityBuilder {
             world: self,
             entity,
         }
     }
     
+    /// Creates exactly `entity`, keeping its id and generation, using the builder pattern.
+    /// 
+    /// Use this when loading a saved world so stored references, such as parent
+    /// links, stay valid. Ids skipped over are reused by later `spawn` calls.
+    /// 
+    /// # Errors
+    /// 
+    /// Returns `EntityAlreadyAlive` if a live entity already has that id, and
+    /// `EntityIndexOutOfRange` if the backend refuses to grow that far; the
+    /// sparse-set backend allows up to `SparseSetBackend::MAX_INDEX_GAP` slots
+    /// past those allocated so far.
+    pub fn spawn_at(&mut self, entity: Entity) -> Result<EntityBuilder<'_, B>, GammaVkError> {
+        self.backend.create_entity_at(entity)?;
+        self.mark_changed(entity);
+        Ok(EntityBuilder {
+            world: self,
+            entity,
+        })
+    }
+    
+    /// Spawns one entity per component in a single batch.
+    /// 
+    /// Much faster than calling `spawn().with(..)` in a loop for particle or
+    /// instance-heavy workloads, since the component storage is looked up once.
+    pub fn spawn_batch<C: Component>(&mut self, components: impl IntoIterator<Item = C>) -> Vec<Entity> {
+        let entities = self.backend.spawn_batch(components);
+        for &entity in &entities {
+            self.mark_changed(entity);
+        }
+        entities
+    }
+    
     /// Destroys an entity and all its components.
     pub fn destroy(&mut self, entity: Entity) -> Result<(), GammaVkError> {
-        self.backend.destroy_entity(entity)
+        self.backend.destroy_entity(entity)?;
+        self.mark_destroyed(entity);
+        Ok(())
+    }
+    
+    /// Destroys many entities and their components at once.
+    /// 
+    /// Dead or unknown entities are skipped without error. Returns how many
+    /// entities were alive and destroyed.
+    pub fn despawn_batch(&mut self, entities: impl IntoIterator<Item = Entity>) -> usize {
+        let despawned = self.backend.despawn_batch(entities);
+        for &entity in &despawned {
+            self.mark_destroyed(entity);
+        }
+        despawned.len()
+    }
+    
+    /// Reserves capacity for at least `additional_entities` more entities.
+    /// 
+    /// Call before bulk spawning at load time to avoid repeated reallocation.
+    pub fn reserve(&mut self, additional_entities: usize) {
+        self.backend.reserve(additional_entities);
+    }
+    
+    /// Reserves capacity for at least `additional` more components of type C.
+    pub fn reserve_component<C: Component>(&mut self, additional: usize) {
+        self.backend.reserve_component::<C>(additional);
+    }
+    
+    /// Selects how component type C is stored.
+    /// 
+    /// `StorageKind::Dense` suits components nearly every entity has;
+    /// unregistered types use `StorageKind::SparseSet`. Components already
+    /// added are moved into the new layout, so this can be called at any time.
+    pub fn register_component<C: Component>(&mut self, kind: StorageKind) {
+        self.backend.register_component::<C>(kind);
     }
     
     /// Checks if an entity is alive.
@@ -50,25 +133,314 @@ impl<B: EcsBackend> World<B> {
         self.backend.get_component_mut::<C>(entity)
     }
     
+    /// Gets the same component type on two different entities, both mutable.
+    /// 
+    /// Useful for pairwise interactions such as collision response. Returns None
+    /// if `a == b`, since the borrows would alias, or if either entity is dead or
+    /// lacks the component.
+    pub fn get_disjoint_mut<C: Component>(&mut self, a: Entity, b: Entity) -> Option<(&mut C, &mut C)> {
+        self.backend.get_component_pair_mut::<C>(a, b)
+    }
+    
+    /// Gets a mutable component for an entity, inserting `f()` first if absent.
+    /// 
+    /// Returns None only if the entity is dead.
+    pub fn get_or_insert_with<C: Component>(&mut self, entity: Entity, f: impl FnOnce() -> C) -> Option<&mut C> {
+        if self.is_alive(entity) && self.get::<C>(entity).is_none() {
+            self.mark_changed(entity);
+        }
+        self.backend.get_or_insert_with(entity, f)
+    }
+    
     /// Adds a component to an entity.
+    /// 
+    /// Overwriting an existing component is not a structural change and leaves
+    /// the entity's last-changed tick alone.
     pub fn add_component<C: Component>(&mut self, entity: Entity, component: C) -> Result<(), GammaVkError> {
-        self.backend.add_component(entity, component)
+        let had_component = self.get::<C>(entity).is_some();
+        self.backend.add_component(entity, component)?;
+        if !had_component {
+            self.mark_changed(entity);
+        }
+        Ok(())
+    }
+    
+    /// Adds or replaces a component on an entity, returning the previous value.
+    /// 
+    /// Useful for computing deltas or detecting changes when a component is
+    /// overwritten. Fails if the entity is dead.
+    pub fn replace_component<C: Component>(&mut self, entity: Entity, component: C) -> Result<Option<C>, GammaVkError> {
+        let previous = self.backend.replace_component(entity, component)?;
+        if previous.is_none() {
+            self.mark_changed(entity);
+        }
+        Ok(previous)
     }
     
     /// Removes a component from an entity.
     pub fn remove<C: Component>(&mut self, entity: Entity) -> Result<(), GammaVkError> {
-        self.backend.remove_component::<C>(entity)
+        let had_component = self.get::<C>(entity).is_some();
+        self.backend.remove_component::<C>(entity)?;
+        if had_component {
+            self.mark_changed(entity);
+        }
+        Ok(())
     }
     
-    /// Queries for all entities with a specific component.
-    pub fn query<C: Component>(&self) -> impl Iterator<Item = (Entity, &C)> {
-        self.backend.query_component::<C>().into_iter()
+    /// Removes component C from every entity at once.
+    /// 
+    /// Entities and their other components are untouched. Each removed
+    /// component's `on_remove` hook runs, as with `remove`. Returns the number of
+    /// components removed; a type that was never added is a no-op.
+    pub fn clear_component<C: Component>(&mut self) -> usize {
+        let holders: Vec<Entity> = self.query::<C>().map(|(entity, _)| entity).collect();
+        for &entity in &holders {
+            self.mark_changed(entity);
+        }
+        self.backend.clear_component::<C>()
+    }
+    
+    /// Queries for all entities matching `Q`.
+    /// 
+    /// `Q` is a component type, yielding `&C`, or `(&A, Option<&C>)`, yielding
+    /// each entity with A together with its C if it has one; see `Query`.
+    pub fn query<Q: Query>(&self) -> impl Iterator<Item = (Entity, Q::Item<'_>)> {
+        Q::fetch(self).into_iter()
+    }
+    
+    /// Queries for all entities with a specific component in ascending
+    /// entity-index order.
+    /// 
+    /// Unlike `query`, the order does not change when other entities lose the
+    /// component, which keeps replays and golden tests deterministic.
+    pub fn query_sorted<C: Component>(&self) -> impl Iterator<Item = (Entity, &C)> {
+        self.backend.query_component_sorted::<C>().into_iter()
     }
     
     /// Queries for all entities with a specific component (mutable).
     pub fn query_mut<C: Component>(&mut self) -> impl Iterator<Item = (Entity, &mut C)> {
         self.backend.query_component_mut::<C>().into_iter()
     }
+    
+    /// Applies `f` to every instance of component C in place.
+    /// 
+    /// Walks the component storage directly, without entity lookups or
+    /// allocation, which suits bulk updates such as rescaling every position on
+    /// a level reload. Use `query_mut` when the entity is needed.
+    pub fn map_component<C: Component>(&mut self, f: impl FnMut(&mut C)) {
+        self.backend.map_component::<C>(f);
+    }
+    
+    /// Lists the type names of the components an entity currently has.
+    /// 
+    /// Names are sorted for stable display. Dead entities return an empty list.
+    pub fn components_of(&self, entity: Entity) -> Vec<&'static str> {
+        let mut names = self.backend.component_names(entity);
+        names.sort_unstable();
+        names
+    }
+    
+    /// Runs a closure over the world once.
+    /// 
+    /// Handy for one-off setup logic before a scheduler is in place.
+    pub fn run<F: FnMut(&mut World<B>)>(&mut self, mut f: F) {
+        f(self);
+    }
+    
+    /// Runs a system over the world once.
+    pub fn run_system(&mut self, system: &mut impl System<B>) {
+        system.run(self);
+    }
+    
+    /// Moves every entity into a new world backed by a different backend.
+    /// 
+    /// Entity ids and generations are preserved, so handles held elsewhere stay
+    /// valid, and resources move across unchanged. Only component types in
+    /// `registry` are carried over; others are dropped with this world, without
+    /// running `on_remove` hooks. Handles to entities destroyed before migration
+    /// are not tracked and may match entities spawned afterwards.
+    pub fn migrate_into<D: EcsBackend>(mut self, registry: &ComponentRegistry<B, D>) -> World<D> {
+        let mut backend = D::default();
+        let entities = self.backend.entities();
+        backend.reserve(entities.len());
+        
+        // Live entities have distinct indices, so creating them in a fresh backend cannot fail
+        backend
+            .create_entities_at(&entities)
+            .expect("live entities of one world have distinct indices");
+        for entity in entities {
+            registry.transfer(&mut self.backend, &mut backend, entity);
+        }
+        
+        World {
+            backend,
+            resources: self.resources,
+            tick: self.tick,
+            last_changed: self.last_changed,
+        }
+    }
+    
+    /// Returns the current tick.
+    /// 
+    /// The tick starts at 0 and increases on every structural change: spawning
+    /// or destroying an entity, or adding or removing a component. Overwriting a
+    /// component's value is not structural.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+    
+    /// Returns the tick of the last structural change to an entity.
+    /// 
+    /// Compare against a tick saved earlier to find entities that changed since,
+    /// for example to rebuild only dirty render data. Dead entities return None.
+    pub fn entity_last_changed(&self, entity: Entity) -> Option<u64> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        self.last_changed.get(&entity).copied()
+    }
+    
+    /// Advances the tick and records it as the entity's last change.
+    fn mark_changed(&mut self, entity: Entity) {
+        self.tick += 1;
+        self.last_changed.insert(entity, self.tick);
+    }
+    
+    /// Advances the tick and forgets a destroyed entity.
+    fn mark_destroyed(&mut self, entity: Entity) {
+        self.tick += 1;
+        self.last_changed.remove(&entity);
+    }
+    
+    /// Copies every entity and its registered components into an in-memory snapshot.
+    /// 
+    /// Only component types registered with `register_cloned` or
+    /// `register_with_clone` are captured; resources are not. Pair with
+    /// `restore` to implement undo in an editor.
+    pub fn snapshot(&self, registry: &ComponentRegistry<B>) -> WorldSnapshot<B> {
+        WorldSnapshot {
+            entities: self.backend.entities(),
+            components: registry.capture(&self.backend),
+        }
+    }
+    
+    /// Returns the world to the state captured by `snapshot`.
+    /// 
+    /// Every current entity is destroyed, running `on_remove` hooks, then the
+    /// snapshot's entities are recreated with their original ids and
+    /// generations. Handles to entities spawned after the snapshot become dead.
+    /// Restoring counts as a structural change to every restored entity.
+    pub fn restore(&mut self, snapshot: WorldSnapshot<B>) {
+        let live = self.backend.entities();
+        self.despawn_batch(live);
+        
+        // Snapshot entities have distinct indices and no slot is live any more
+        self.backend
+            .create_entities_at(&snapshot.entities)
+            .expect("snapshot entities have distinct indices and none are alive");
+        for &entity in &snapshot.entities {
+            self.mark_changed(entity);
+        }
+        for components in snapshot.components {
+            components.restore(&mut self.backend);
+        }
+    }
+    
+    /// Reports the approximate bytes used by each component storage.
+    /// 
+    /// Returns (component type name, bytes) pairs, largest first.
+    pub fn storage_memory_report(&self) -> Vec<(&'static str, usize)> {
+        let mut report = self.backend.storage_memory_report();
+        report.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        report
+    }
+}
+
+// Resource API - singletons that are not attached to any entity
+impl<B: EcsBackend> World<B> {
+    /// Stores a resource, returning the previous resource of the same type.
+    /// 
+    /// Resources hold world-wide state such as the `VulkanContext`, so render
+    /// systems can reach the device, allocators and queues without globals.
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use gamma_vk::ecs::{Component, World};
+    /// use gamma_vk::{CommandRecorder, DrawDescriptor, VertexBuffer, VulkanContext};
+    /// # use std::sync::Arc;
+    /// # use vulkano::pipeline::GraphicsPipeline;
+    /// 
+    /// #[derive(Debug)]
+    /// struct Transform { x: f32, y: f32 }
+    /// impl Component for Transform {}
+    /// 
+    /// struct MeshHandle { vertices: VertexBuffer, vertex_stride: u32 }
+    /// impl Component for MeshHandle {}
+    /// 
+    /// # fn example(pipeline: Arc<GraphicsPipeline>) -> gamma_vk::Result<()> {
+    /// let mut world: World = World::new()?;
+    /// world.insert_resource(VulkanContext::new()?);
+    /// 
+    /// // `pipeline` was built for the target render pass
+    /// world.run(|world: &mut World| {
+    ///     let context = world.get_resource::<VulkanContext>().expect("context resource");
+    ///     let mut recorder = CommandRecorder::new(context).unwrap();
+    ///     // ... begin the render pass ...
+    ///     for (_, (_transform, mesh)) in world.query2::<Transform, MeshHandle>() {
+    ///         let draw = DrawDescriptor::new(&mesh.vertices, mesh.vertex_stride).unwrap();
+    ///         recorder.draw_mesh(&pipeline, None, &draw).unwrap();
+    ///     }
+    ///     // ... end the render pass and submit to context.graphics_queue() ...
+    /// });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn insert_resource<R: Any + Send + Sync>(&mut self, resource: R) -> Option<R> {
+        self.resources
+            .insert(TypeId::of::<R>(), Box::new(resource))
+            .and_then(|old| old.downcast::<R>().ok())
+            .map(|old| *old)
+    }
+    
+    /// Gets a resource by type.
+    pub fn get_resource<R: Any + Send + Sync>(&self) -> Option<&R> {
+        self.resources
+            .get(&TypeId::of::<R>())
+            .and_then(|resource| resource.downcast_ref::<R>())
+    }
+    
+    /// Gets a mutable resource by type.
+    pub fn get_resource_mut<R: Any + Send + Sync>(&mut self) -> Option<&mut R> {
+        self.resources
+            .get_mut(&TypeId::of::<R>())
+            .and_then(|resource| resource.downcast_mut::<R>())
+    }
+    
+    /// Removes a resource, returning it if it was present.
+    pub fn remove_resource<R: Any + Send + Sync>(&mut self) -> Option<R> {
+        self.resources
+            .remove(&TypeId::of::<R>())
+            .and_then(|resource| resource.downcast::<R>().ok())
+            .map(|resource| *resource)
+    }
+}
+
+/// A copy of a world's entities and registered components, taken by `World::snapshot`.
+/// 
+/// The snapshot lives in memory only and is consumed by `World::restore`.
+pub struct WorldSnapshot<B: EcsBackend = SparseSetBackend> {
+    /// Live entities, with their generations, at the time of the snapshot
+    entities: Vec<Entity>,
+    
+    /// One entry per cloneable registered component type
+    components: Vec<CapturedComponents<B>>,
+}
+
+impl<B: EcsBackend> WorldSnapshot<B> {
+    /// Returns the number of entities captured.
+    pub fn entity_count(&self) -> usize {
+        self.entities.len()
+    }
 }
 
 /// Builder for creating entities with components.
@@ -85,6 +457,17 @@ impl<'a, B: EcsBackend> EntityBuilder<'a, B> {
         self
     }
     
+    /// Adds a component to the entity being built, surfacing insertion failures.
+    /// 
+    /// On error the builder is handed back alongside the error, so the caller can
+    /// still finish or inspect the entity.
+    pub fn try_with<C: Component>(self, component: C) -> Result<Self, (Self, GammaVkError)> {
+        match self.world.add_component(self.entity, component) {
+            Ok(()) => Ok(self),
+            Err(error) => Err((self, error)),
+        }
+    }
+    
     /// Finishes building and returns the entity.
     pub fn build(self) -> Entity {
         self.entity
@@ -97,19 +480,151 @@ impl<B: EcsBackend> World<B> {
     /// 
     /// This is a simplified implementation for Phase 1.
     /// Phase 3 will add a more sophisticated query system.
-    pub fn query2<A: Component, B: Component>(&self) -> Vec<(Entity, (&A, &B))> {
+    pub fn query2<A: Component, C: Component>(&self) -> Vec<(Entity, (&A, &C))> {
         let mut results = Vec::new();
         
         // Get all entities with component A
         for (entity, a) in self.query::<A>() {
-            // Check if they also have component B
-            if let Some(b) = self.get::<B>(entity) {
-                results.push((entity, (a, b)));
+            // Check if they also have component C
+            if let Some(c) = self.get::<C>(entity) {
+                results.push((entity, (a, c)));
             }
         }
         
         results
     }
+    
+    /// Gets two components of a single entity together.
+    /// 
+    /// Returns None if the entity is dead or lacks either component.
+    pub fn query_one<A: Component, C: Component>(&self, entity: Entity) -> Option<(&A, &C)> {
+        Some((self.get::<A>(entity)?, self.get::<C>(entity)?))
+    }
+    
+    /// Gets two components of a single entity together, both mutable.
+    /// 
+    /// Returns None if the entity is dead or lacks either component.
+    /// Panics if A and C are the same type, since the borrows would alias.
+    pub fn query_one_mut<A: Component, C: Component>(&mut self, entity: Entity) -> Option<(&mut A, &mut C)> {
+        self.backend.get_components2_mut::<A, C>(entity)
+    }
+    
+    /// Lazily iterates entities that have both components, without allocating.
+    /// 
+    /// Iteration is driven by whichever component has fewer instances, so the
+    /// cost scales with the rarer component. Order is unspecified.
+    pub fn iter2<A: Component, C: Component>(&self) -> impl Iterator<Item = (Entity, &A, &C)> {
+        self.backend.iter_components2::<A, C>()
+    }
+    
+    /// Calls `f` for every entity having both components, with `A` mutable.
+    /// 
+    /// Iterates `A`'s dense storage directly and probes `C` once per element,
+    /// without collecting anything, which suits motion-integration style systems.
+    /// 
+    /// # Panics
+    /// Panics if `A` and `C` are the same type.
+    /// 
+    /// # Example
+    /// ```
+    /// # use gamma_vk::ecs::{Component, World};
+    /// # struct Position { x: f32, y: f32 }
+    /// # impl Component for Position {}
+    /// # struct Velocity { dx: f32, dy: f32 }
+    /// # impl Component for Velocity {}
+    /// # let mut world: World = World::new()?;
+    /// world.for_each2_mut::<Position, Velocity>(|_, pos, vel| {
+    ///     pos.x += vel.dx;
+    ///     pos.y += vel.dy;
+    /// });
+    /// # Ok::<(), gamma_vk::GammaVkError>(())
+    /// ```
+    pub fn for_each2_mut<A: Component, C: Component>(&mut self, f: impl FnMut(Entity, &mut A, &C)) {
+        self.backend.for_each_mut::<A, C>(f);
+    }
+    
+    /// Queries for entities with at least one of the components in tuple `Q`.
+    /// 
+    /// Each entity is yielded once, even if it has several of the components.
+    /// 
+    /// # Example
+    /// ```
+    /// # use gamma_vk::ecs::{Component, World};
+    /// # struct Mesh;
+    /// # impl Component for Mesh {}
+    /// # struct Sprite;
+    /// # impl Component for Sprite {}
+    /// # let world: World = World::new()?;
+    /// // Anything renderable
+    /// for entity in world.query_any::<(Mesh, Sprite)>() {
+    ///     // ...
+    /// }
+    /// # Ok::<(), gamma_vk::GammaVkError>(())
+    /// ```
+    pub fn query_any<Q: AnyOf>(&self) -> impl Iterator<Item = Entity> {
+        Q::entities(self).into_iter()
+    }
+}
+
+/// What `World::query` can fetch.
+/// 
+/// Implemented for every component type `C`, yielding `&C`, and for
+/// `(&A, Option<&C>)`, yielding `(&A, Option<&C>)` for every entity with A.
+/// The optional query is driven by A's storage; C is probed per entity and is
+/// `None` for entities that lack it.
+pub trait Query {
+    /// The borrowed data yielded for each matching entity.
+    type Item<'w>;
+    
+    /// Returns every matching entity with its data.
+    fn fetch<B: EcsBackend>(world: &World<B>) -> Vec<(Entity, Self::Item<'_>)>;
+}
+
+impl<C: Component> Query for C {
+    type Item<'w> = &'w C;
+    
+    fn fetch<B: EcsBackend>(world: &World<B>) -> Vec<(Entity, &C)> {
+        world.backend.query_component::<C>()
+    }
+}
+
+impl<A: Component, C: Component> Query for (&A, Option<&C>) {
+    type Item<'w> = (&'w A, Option<&'w C>);
+    
+    fn fetch<B: EcsBackend>(world: &World<B>) -> Vec<(Entity, (&A, Option<&C>))> {
+        world.query::<A>()
+            .map(|(entity, a)| (entity, (a, world.get::<C>(entity))))
+            .collect()
+    }
+}
+
+/// A tuple of component types queried with OR semantics.
+/// 
+/// Implemented for tuples of two and three components; see `World::query_any`.
+pub trait AnyOf {
+    /// Returns the entities having at least one of the components, each once.
+    fn entities<B: EcsBackend>(world: &World<B>) -> Vec<Entity>;
+}
+
+impl<A: Component, C: Component> AnyOf for (A, C) {
+    fn entities<B: EcsBackend>(world: &World<B>) -> Vec<Entity> {
+        // Entities with A, then entities with C that were not already yielded
+        let mut entities: Vec<Entity> = world.query::<A>().map(|(entity, _)| entity).collect();
+        entities.extend(world.query::<C>()
+            .map(|(entity, _)| entity)
+            .filter(|&entity| world.get::<A>(entity).is_none()));
+        entities
+    }
+}
+
+impl<A: Component, C: Component, D: Component> AnyOf for (A, C, D) {
+    fn entities<B: EcsBackend>(world: &World<B>) -> Vec<Entity> {
+        let mut entities = <(A, C)>::entities(world);
+        entities.extend(world.query::<D>()
+            .map(|(entity, _)| entity)
+            .filter(|&entity| world.get::<A>(entity).is_none() && world.get::<C>(entity).is_none()));
+        entities
+    }
 }
 
 #[cfg(test)]
@@ -124,6 +639,42 @@ mod tests {
     struct Velocity { dx: f32, dy: f32 }
     impl Component for Velocity {}
 
+    /// A second backend type, delegating to the sparse set, for migrating across backends
+    #[derive(Default)]
+    struct DelegatingBackend(SparseSetBackend);
+
+    impl EcsBackend for DelegatingBackend {
+        fn create_entity(&mut self) -> Entity { self.0.create_entity() }
+        fn spawn_batch<C: Component>(&mut self, components: impl IntoIterator<Item = C>) -> Vec<Entity> { self.0.spawn_batch(components) }
+        fn create_entity_at(&mut self, entity: Entity) -> Result<(), GammaVkError> { self.0.create_entity_at(entity) }
+        fn create_entities_at(&mut self, entities: &[Entity]) -> Result<(), GammaVkError> { self.0.create_entities_at(entities) }
+        fn entities(&self) -> Vec<Entity> { self.0.entities() }
+        fn destroy_entity(&mut self, entity: Entity) -> Result<(), GammaVkError> { self.0.destroy_entity(entity) }
+        fn despawn_batch(&mut self, entities: impl IntoIterator<Item = Entity>) -> Vec<Entity> { self.0.despawn_batch(entities) }
+        fn reserve(&mut self, additional_entities: usize) { self.0.reserve(additional_entities) }
+        fn reserve_component<C: Component>(&mut self, additional: usize) { self.0.reserve_component::<C>(additional) }
+        fn register_component<C: Component>(&mut self, kind: StorageKind) { self.0.register_component::<C>(kind) }
+        fn is_alive(&self, entity: Entity) -> bool { self.0.is_alive(entity) }
+        fn add_component<C: Component>(&mut self, entity: Entity, component: C) -> Result<(), GammaVkError> { self.0.add_component(entity, component) }
+        fn replace_component<C: Component>(&mut self, entity: Entity, component: C) -> Result<Option<C>, GammaVkError> { self.0.replace_component(entity, component) }
+        fn get_component<C: Component>(&self, entity: Entity) -> Option<&C> { self.0.get_component(entity) }
+        fn get_component_mut<C: Component>(&mut self, entity: Entity) -> Option<&mut C> { self.0.get_component_mut(entity) }
+        fn get_or_insert_with<C: Component>(&mut self, entity: Entity, f: impl FnOnce() -> C) -> Option<&mut C> { self.0.get_or_insert_with(entity, f) }
+        fn get_component_pair_mut<C: Component>(&mut self, a: Entity, b: Entity) -> Option<(&mut C, &mut C)> { self.0.get_component_pair_mut(a, b) }
+        fn get_components2_mut<A: Component, B: Component>(&mut self, entity: Entity) -> Option<(&mut A, &mut B)> { self.0.get_components2_mut(entity) }
+        fn take_component<C: Component>(&mut self, entity: Entity) -> Option<C> { self.0.take_component(entity) }
+        fn clear_component<C: Component>(&mut self) -> usize { self.0.clear_component::<C>() }
+        fn remove_component<C: Component>(&mut self, entity: Entity) -> Result<(), GammaVkError> { self.0.remove_component::<C>(entity) }
+        fn query_component<C: Component>(&self) -> Vec<(Entity, &C)> { self.0.query_component() }
+        fn query_component_sorted<C: Component>(&self) -> Vec<(Entity, &C)> { self.0.query_component_sorted() }
+        fn query_component_mut<C: Component>(&mut self) -> Vec<(Entity, &mut C)> { self.0.query_component_mut() }
+        fn map_component<C: Component>(&mut self, f: impl FnMut(&mut C)) { self.0.map_component(f) }
+        fn iter_components2<A: Component, B: Component>(&self) -> impl Iterator<Item = (Entity, &A, &B)> { self.0.iter_components2() }
+        fn for_each_mut<A: Component, B: Component>(&mut self, f: impl FnMut(Entity, &mut A, &B)) { self.0.for_each_mut(f) }
+        fn component_names(&self, entity: Entity) -> Vec<&'static str> { self.0.component_names(entity) }
+        fn storage_memory_report(&self) -> Vec<(&'static str, usize)> { self.0.storage_memory_report() }
+    }
+
     #[test]
     fn test_world_creation() {
         let world = World::<SparseSetBackend>::new();
@@ -179,4 +730,801 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].0, e1);
     }
+
+    #[test]
+    fn test_storage_memory_report() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        
+        for i in 0..1000 {
+            world.spawn().with(Position { x: i as f32, y: 0.0 }).build();
+        }
+        world.spawn().with(Velocity { dx: 1.0, dy: 1.0 }).build();
+        
+        let report = world.storage_memory_report();
+        assert_eq!(report.len(), 2);
+        
+        let (name, bytes) = report[0];
+        assert_eq!(name, std::any::type_name::<Position>());
+        assert!(bytes >= 1000 * size_of::<Position>());
+        assert_eq!(report[1].0, std::any::type_name::<Velocity>());
+    }
+
+    #[test]
+    fn test_components_of() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        
+        let moving = world.spawn()
+            .with(Position { x: 0.0, y: 0.0 })
+            .with(Velocity { dx: 1.0, dy: 0.0 })
+            .build();
+        let empty = world.spawn().build();
+        
+        let mut expected = vec![
+            std::any::type_name::<Position>(),
+            std::any::type_name::<Velocity>(),
+        ];
+        expected.sort_unstable();
+        
+        assert_eq!(world.components_of(moving), expected);
+        assert!(world.components_of(empty).is_empty());
+        
+        world.destroy(moving).unwrap();
+        assert!(world.components_of(moving).is_empty());
+    }
+
+    #[test]
+    fn test_query_sorted_stable_across_removal() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        
+        let entities: Vec<_> = (0..5)
+            .map(|i| world.spawn().with(Position { x: i as f32, y: 0.0 }).build())
+            .collect();
+        
+        let sorted = |world: &World<SparseSetBackend>| -> Vec<Entity> {
+            world.query_sorted::<Position>().map(|(e, _)| e).collect()
+        };
+        assert_eq!(sorted(&world), entities);
+        
+        // Swap-removal moves the last entity into the removed slot
+        world.remove::<Position>(entities[1]).unwrap();
+        let unsorted: Vec<_> = world.query::<Position>().map(|(e, _)| e).collect();
+        assert_ne!(unsorted, vec![entities[0], entities[2], entities[3], entities[4]]);
+        
+        assert_eq!(sorted(&world), vec![entities[0], entities[2], entities[3], entities[4]]);
+    }
+
+    #[test]
+    fn test_spawn_batch() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        
+        let entities = world.spawn_batch((0..1000).map(|i| Position { x: i as f32, y: 0.0 }));
+        
+        assert_eq!(entities.len(), 1000);
+        for (i, &entity) in entities.iter().enumerate() {
+            assert!(world.is_alive(entity));
+            assert_eq!(world.get::<Position>(entity), Some(&Position { x: i as f32, y: 0.0 }));
+        }
+    }
+
+    #[test]
+    #[ignore = "wall-clock timing; run alone with --ignored"]
+    fn test_spawn_batch_not_slower_than_naive() {
+        use std::time::Instant;
+        
+        let mut naive_world = World::<SparseSetBackend>::new().unwrap();
+        let start = Instant::now();
+        for i in 0..1000 {
+            naive_world.spawn().with(Position { x: i as f32, y: 0.0 }).build();
+        }
+        let naive = start.elapsed();
+        
+        let mut batch_world = World::<SparseSetBackend>::new().unwrap();
+        let start = Instant::now();
+        batch_world.spawn_batch((0..1000).map(|i| Position { x: i as f32, y: 0.0 }));
+        let batch = start.elapsed();
+        
+        // Generous margin keeps this stable on noisy machines
+        assert!(batch <= naive * 4, "batch {:?} vs naive {:?}", batch, naive);
+    }
+
+    #[test]
+    fn test_get_or_insert_with_inserts_when_absent() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let entity = world.spawn().build();
+        
+        let velocity = world.get_or_insert_with(entity, || Velocity { dx: 0.0, dy: 0.0 });
+        velocity.unwrap().dx += 1.0;
+        
+        assert_eq!(world.get::<Velocity>(entity), Some(&Velocity { dx: 1.0, dy: 0.0 }));
+    }
+
+    #[test]
+    fn test_get_or_insert_with_returns_existing() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let entity = world.spawn().with(Velocity { dx: 2.0, dy: 3.0 }).build();
+        
+        let velocity = world.get_or_insert_with(entity, || -> Velocity {
+            panic!("closure must not be called when the component exists")
+        });
+        
+        assert_eq!(velocity, Some(&mut Velocity { dx: 2.0, dy: 3.0 }));
+    }
+
+    #[test]
+    fn test_get_or_insert_with_dead_entity() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let entity = world.spawn().build();
+        world.destroy(entity).unwrap();
+        
+        assert!(world.get_or_insert_with(entity, || Velocity { dx: 0.0, dy: 0.0 }).is_none());
+    }
+    
+    #[test]
+    fn test_despawn_batch_counts_only_live_entities() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        
+        let doomed = world.spawn_batch((0..4).map(|i| Position { x: i as f32, y: 0.0 }));
+        let survivor = world.spawn()
+            .with(Position { x: 10.0, y: 10.0 })
+            .with(Velocity { dx: 1.0, dy: 1.0 })
+            .build();
+        
+        world.destroy(doomed[0]).unwrap();
+        
+        // One already dead, one duplicate, three live
+        let despawned = world.despawn_batch(vec![doomed[0], doomed[1], doomed[2], doomed[2], doomed[3]]);
+        assert_eq!(despawned, 3);
+        
+        for &entity in &doomed {
+            assert!(!world.is_alive(entity));
+        }
+        assert!(world.is_alive(survivor));
+        assert_eq!(world.get::<Position>(survivor), Some(&Position { x: 10.0, y: 10.0 }));
+        assert_eq!(world.get::<Velocity>(survivor), Some(&Velocity { dx: 1.0, dy: 1.0 }));
+        assert_eq!(world.query::<Position>().count(), 1);
+        
+        assert_eq!(world.despawn_batch(doomed), 0);
+    }
+    
+    #[test]
+    fn test_iter2_matches_query2() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        
+        // Many positions, few velocities, so iteration is driven by Velocity
+        let positions = world.spawn_batch((0..10).map(|i| Position { x: i as f32, y: 0.0 }));
+        for &entity in positions.iter().step_by(3) {
+            world.add_component(entity, Velocity { dx: 1.0, dy: 2.0 }).unwrap();
+        }
+        world.spawn().with(Velocity { dx: 5.0, dy: 5.0 }).build();
+        world.destroy(positions[3]).unwrap();
+        
+        let mut expected: Vec<_> = world.query2::<Position, Velocity>()
+            .into_iter()
+            .map(|(entity, (p, v))| (entity, p.clone(), v.clone()))
+            .collect();
+        let mut actual: Vec<_> = world.iter2::<Position, Velocity>()
+            .map(|(entity, p, v)| (entity, p.clone(), v.clone()))
+            .collect();
+        expected.sort_by_key(|(entity, _, _)| entity.index());
+        actual.sort_by_key(|(entity, _, _)| entity.index());
+        
+        assert_eq!(actual.len(), 3);
+        assert_eq!(actual, expected);
+        
+        // Swapping the type order drives iteration from the larger storage
+        let swapped = world.iter2::<Velocity, Position>().count();
+        assert_eq!(swapped, 3);
+    }
+    
+    #[test]
+    fn test_run_closure_effects_persist() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let entity = world.spawn()
+            .with(Position { x: 0.0, y: 0.0 })
+            .with(Velocity { dx: 1.0, dy: 2.0 })
+            .build();
+        
+        world.run(|world| {
+            let velocities: Vec<_> = world.query2::<Position, Velocity>()
+                .into_iter()
+                .map(|(entity, (_, v))| (entity, v.clone()))
+                .collect();
+            for (entity, v) in velocities {
+                let pos = world.get_mut::<Position>(entity).unwrap();
+                pos.x += v.dx;
+                pos.y += v.dy;
+            }
+        });
+        
+        assert_eq!(world.get::<Position>(entity), Some(&Position { x: 1.0, y: 2.0 }));
+    }
+    
+    #[test]
+    fn test_run_system_keeps_system_state() {
+        struct CountRuns { runs: usize }
+        
+        impl System for CountRuns {
+            fn run(&mut self, world: &mut World) {
+                self.runs += 1;
+                world.spawn().with(Position { x: self.runs as f32, y: 0.0 }).build();
+            }
+        }
+        
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let mut system = CountRuns { runs: 0 };
+        world.run_system(&mut system);
+        world.run_system(&mut system);
+        
+        assert_eq!(system.runs, 2);
+        assert_eq!(world.query::<Position>().count(), 2);
+    }
+    
+    #[test]
+    fn test_query_any_unions_without_duplicates() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        
+        let only_position = world.spawn().with(Position { x: 0.0, y: 0.0 }).build();
+        let only_velocity = world.spawn().with(Velocity { dx: 1.0, dy: 1.0 }).build();
+        let both = world.spawn()
+            .with(Position { x: 2.0, y: 2.0 })
+            .with(Velocity { dx: 2.0, dy: 2.0 })
+            .build();
+        let neither = world.spawn().build();
+        
+        let entities: Vec<Entity> = world.query_any::<(Position, Velocity)>().collect();
+        assert_eq!(entities.len(), 3);
+        for entity in [only_position, only_velocity, both] {
+            assert_eq!(entities.iter().filter(|&&e| e == entity).count(), 1);
+        }
+        assert!(!entities.contains(&neither));
+        
+        // Order of the tuple does not change the set
+        assert_eq!(world.query_any::<(Velocity, Position)>().count(), 3);
+    }
+    
+    #[test]
+    fn test_on_remove_fires_on_remove_and_destroy() {
+        use std::sync::{Arc, Mutex};
+        
+        struct Tracked { log: Arc<Mutex<Vec<bool>>> }
+        impl Component for Tracked {
+            fn on_remove(&mut self, _entity: Entity, hooks: &crate::ecs::WorldHooks) {
+                self.log.lock().unwrap().push(hooks.is_entity_destroyed());
+            }
+        }
+        
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let removed = world.spawn().with(Tracked { log: log.clone() }).build();
+        let destroyed = world.spawn().with(Tracked { log: log.clone() }).build();
+        
+        world.remove::<Tracked>(removed).unwrap();
+        assert_eq!(*log.lock().unwrap(), vec![false]);
+        
+        world.destroy(destroyed).unwrap();
+        assert_eq!(*log.lock().unwrap(), vec![false, true]);
+        
+        // Entities without the component do not trigger the hook
+        let plain = world.spawn().with(Position { x: 0.0, y: 0.0 }).build();
+        world.destroy(plain).unwrap();
+        assert_eq!(log.lock().unwrap().len(), 2);
+    }
+    
+    #[test]
+    fn test_for_each2_mut_matches_naive_integration() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        
+        let entities = world.spawn_batch((0..500).map(|i| Position { x: i as f32, y: -(i as f32) }));
+        for (i, &entity) in entities.iter().enumerate() {
+            // Leave some entities without a velocity
+            if i % 7 != 0 {
+                world.add_component(entity, Velocity { dx: i as f32 * 0.5, dy: 1.0 }).unwrap();
+            }
+        }
+        
+        // Naive: look up each velocity per position
+        let expected: Vec<(Entity, Position)> = entities.iter()
+            .map(|&entity| {
+                let pos = world.get::<Position>(entity).unwrap();
+                match world.get::<Velocity>(entity) {
+                    Some(vel) => (entity, Position { x: pos.x + vel.dx, y: pos.y + vel.dy }),
+                    None => (entity, pos.clone()),
+                }
+            })
+            .collect();
+        
+        let mut visited = 0;
+        world.for_each2_mut::<Position, Velocity>(|_, pos, vel| {
+            pos.x += vel.dx;
+            pos.y += vel.dy;
+            visited += 1;
+        });
+        
+        assert_eq!(visited, entities.len() - entities.len().div_ceil(7));
+        for (entity, position) in expected {
+            assert_eq!(world.get::<Position>(entity), Some(&position));
+        }
+    }
+    
+    #[test]
+    fn test_replace_component_returns_previous_value() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let entity = world.spawn().build();
+        
+        let first = world.replace_component(entity, Velocity { dx: 1.0, dy: 2.0 }).unwrap();
+        assert_eq!(first, None);
+        
+        let old = world.replace_component(entity, Velocity { dx: 3.0, dy: 4.0 }).unwrap();
+        assert_eq!(old, Some(Velocity { dx: 1.0, dy: 2.0 }));
+        assert_eq!(world.get::<Velocity>(entity), Some(&Velocity { dx: 3.0, dy: 4.0 }));
+        
+        world.destroy(entity).unwrap();
+        assert!(world.replace_component(entity, Velocity { dx: 0.0, dy: 0.0 }).is_err());
+    }
+    
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derived_component_usable_in_spawn() {
+        #[derive(Debug, PartialEq, crate::ecs::Component)]
+        struct Health(u32);
+        
+        #[derive(Debug, PartialEq, crate::ecs::Component)]
+        struct Tagged<T> {
+            tag: T,
+        }
+        
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let entity = world.spawn()
+            .with(Health(100))
+            .with(Tagged { tag: 7u8 })
+            .build();
+        
+        assert_eq!(world.get::<Health>(entity), Some(&Health(100)));
+        assert_eq!(world.get::<Tagged<u8>>(entity), Some(&Tagged { tag: 7 }));
+    }
+    
+    #[test]
+    fn test_query_optional_yields_none_when_absent() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        
+        let still = world.spawn()
+            .with(Position { x: 1.0, y: 1.0 })
+            .build();
+        let moving = world.spawn()
+            .with(Position { x: 2.0, y: 2.0 })
+            .with(Velocity { dx: 0.5, dy: 0.5 })
+            .build();
+        let _velocity_only = world.spawn()
+            .with(Velocity { dx: 1.0, dy: 1.0 })
+            .build();
+        
+        let mut results: Vec<_> = world.query::<(&Position, Option<&Velocity>)>().collect();
+        results.sort_by_key(|(entity, _)| entity.index());
+        
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0], (still, (&Position { x: 1.0, y: 1.0 }, None)));
+        assert_eq!(results[1], (moving, (&Position { x: 2.0, y: 2.0 }, Some(&Velocity { dx: 0.5, dy: 0.5 }))));
+    }
+    
+    #[test]
+    fn test_try_with_surfaces_insertion_failures() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        
+        let entity = world.spawn()
+            .try_with(Position { x: 1.0, y: 2.0 })
+            .and_then(|builder| builder.try_with(Velocity { dx: 0.5, dy: 0.5 }))
+            .map_err(|(_, error)| error)
+            .unwrap()
+            .build();
+        assert_eq!(world.get::<Position>(entity), Some(&Position { x: 1.0, y: 2.0 }));
+        assert_eq!(world.get::<Velocity>(entity), Some(&Velocity { dx: 0.5, dy: 0.5 }));
+        
+        // A builder whose entity died reports the failure instead of swallowing it
+        world.destroy(entity).unwrap();
+        let builder = EntityBuilder { world: &mut world, entity };
+        let Err((builder, error)) = builder.try_with(Position { x: 0.0, y: 0.0 }) else {
+            panic!("adding to a dead entity should fail");
+        };
+        assert!(matches!(error, GammaVkError::EntityNotFound(e) if e == entity));
+        assert_eq!(builder.build(), entity);
+    }
+    
+    #[test]
+    fn test_system_reads_and_updates_resources() {
+        struct FrameCounter(u64);
+        
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        assert!(world.get_resource::<FrameCounter>().is_none());
+        assert!(world.insert_resource(FrameCounter(0)).is_none());
+        
+        let mut tick = |world: &mut World| {
+            world.get_resource_mut::<FrameCounter>().unwrap().0 += 1;
+        };
+        world.run_system(&mut tick);
+        world.run_system(&mut tick);
+        assert_eq!(world.get_resource::<FrameCounter>().unwrap().0, 2);
+        
+        let replaced = world.insert_resource(FrameCounter(10));
+        assert_eq!(replaced.map(|counter| counter.0), Some(2));
+        assert_eq!(world.remove_resource::<FrameCounter>().map(|counter| counter.0), Some(10));
+        assert!(world.get_resource::<FrameCounter>().is_none());
+    }
+    
+    #[test]
+    fn test_query_one_requires_every_component() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let player = world.spawn()
+            .with(Position { x: 1.0, y: 2.0 })
+            .with(Velocity { dx: 3.0, dy: 4.0 })
+            .build();
+        let statue = world.spawn()
+            .with(Position { x: 5.0, y: 5.0 })
+            .build();
+        
+        assert_eq!(
+            world.query_one::<Position, Velocity>(player),
+            Some((&Position { x: 1.0, y: 2.0 }, &Velocity { dx: 3.0, dy: 4.0 }))
+        );
+        assert_eq!(world.query_one::<Position, Velocity>(statue), None);
+        
+        world.destroy(player).unwrap();
+        assert_eq!(world.query_one::<Position, Velocity>(player), None);
+    }
+    
+    #[test]
+    fn test_query_one_mut_returns_disjoint_references() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let player = world.spawn()
+            .with(Position { x: 1.0, y: 2.0 })
+            .with(Velocity { dx: 3.0, dy: 4.0 })
+            .build();
+        let statue = world.spawn()
+            .with(Position { x: 5.0, y: 5.0 })
+            .build();
+        
+        // Both references are live at once
+        let (position, velocity) = world.query_one_mut::<Position, Velocity>(player).unwrap();
+        position.x += velocity.dx;
+        velocity.dx = 0.0;
+        
+        assert_eq!(world.get::<Position>(player), Some(&Position { x: 4.0, y: 2.0 }));
+        assert_eq!(world.get::<Velocity>(player), Some(&Velocity { dx: 0.0, dy: 4.0 }));
+        assert!(world.query_one_mut::<Position, Velocity>(statue).is_none());
+    }
+    
+    #[test]
+    fn test_migrate_into_preserves_entities_and_components() {
+        #[derive(Debug, PartialEq)]
+        struct Unregistered;
+        impl Component for Unregistered {}
+        
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let player = world.spawn()
+            .with(Position { x: 1.0, y: 2.0 })
+            .with(Velocity { dx: 3.0, dy: 4.0 })
+            .with(Unregistered)
+            .build();
+        let destroyed = world.spawn().with(Position { x: 0.0, y: 0.0 }).build();
+        world.destroy(destroyed).unwrap();
+        let recycled = world.spawn().with(Position { x: 5.0, y: 6.0 }).build();
+        let high = world.spawn().with(Velocity { dx: 7.0, dy: 8.0 }).build();
+        world.insert_resource(42u32);
+        
+        let registry = ComponentRegistry::new()
+            .register::<Position>()
+            .register::<Velocity>();
+        let mut migrated: World<DelegatingBackend> = world.migrate_into(&registry);
+        
+        assert!(migrated.is_alive(player));
+        assert!(migrated.is_alive(recycled));
+        assert!(migrated.is_alive(high));
+        assert!(!migrated.is_alive(destroyed));
+        assert_eq!(recycled.index(), destroyed.index());
+        
+        assert_eq!(migrated.get::<Position>(player), Some(&Position { x: 1.0, y: 2.0 }));
+        assert_eq!(migrated.get::<Velocity>(player), Some(&Velocity { dx: 3.0, dy: 4.0 }));
+        assert_eq!(migrated.get::<Unregistered>(player), None);
+        assert_eq!(migrated.get::<Position>(recycled), Some(&Position { x: 5.0, y: 6.0 }));
+        assert_eq!(migrated.get::<Velocity>(high), Some(&Velocity { dx: 7.0, dy: 8.0 }));
+        assert_eq!(migrated.get_resource::<u32>(), Some(&42));
+        
+        // New spawns do not collide with migrated ids
+        let spawned = migrated.spawn().build();
+        assert!(![player, recycled, high].iter().any(|e| e.index() == spawned.index()));
+    }
+    
+    #[test]
+    fn test_spawn_at_restores_exact_entity() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let first = world.spawn().build();
+        
+        let saved = Entity::from_raw_parts(1000, 7);
+        let restored = world.spawn_at(saved)
+            .unwrap()
+            .with(Position { x: 1.0, y: 2.0 })
+            .build();
+        
+        assert_eq!(restored, saved);
+        assert!(world.is_alive(saved));
+        assert!(!world.is_alive(Entity::from_raw_parts(1000, 6)));
+        assert_eq!(world.get::<Position>(saved), Some(&Position { x: 1.0, y: 2.0 }));
+        assert_eq!(world.query::<Position>().map(|(e, _)| e).collect::<Vec<_>>(), vec![saved]);
+        
+        // Later spawns fill the skipped ids without colliding
+        let later = world.spawn().build();
+        assert!(later.index() < 1000);
+        assert_ne!(later.index(), first.index());
+    }
+    
+    #[test]
+    fn test_spawn_at_live_id_errors() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let entity = world.spawn().with(Position { x: 1.0, y: 1.0 }).build();
+        
+        assert!(matches!(world.spawn_at(entity), Err(GammaVkError::EntityAlreadyAlive(e)) if e == entity));
+        
+        // A different generation at a live index would alias it, so it is refused too
+        let other = Entity::from_raw_parts(entity.index(), entity.generation() + 1);
+        assert!(world.spawn_at(other).is_err());
+        assert_eq!(world.get::<Position>(entity), Some(&Position { x: 1.0, y: 1.0 }));
+        
+        // Once destroyed, the id can be restored
+        world.destroy(entity).unwrap();
+        assert!(world.spawn_at(other).is_ok());
+        assert!(world.is_alive(other));
+    }
+    
+    #[test]
+    fn test_spawn_at_rejects_index_far_past_allocated() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        world.spawn().build();
+        
+        let stray = Entity::from_raw_parts(u32::MAX - 1, 0);
+        assert!(matches!(world.spawn_at(stray), Err(GammaVkError::EntityIndexOutOfRange(e)) if e == stray));
+        assert!(!world.is_alive(stray));
+        
+        // The last index within the allowed gap is still accepted
+        let furthest = Entity::from_raw_parts(SparseSetBackend::MAX_INDEX_GAP, 0);
+        assert!(world.spawn_at(furthest).is_ok());
+        assert!(world.is_alive(furthest));
+    }
+    
+    #[test]
+    fn test_migrate_and_restore_accept_gaps_wider_than_max_index_gap() {
+        let gap = SparseSetBackend::MAX_INDEX_GAP;
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let low = world.spawn_at(Entity::from_raw_parts(gap - 1, 0)).unwrap().build();
+        let high = world.spawn_at(Entity::from_raw_parts(2 * gap - 2, 0)).unwrap()
+            .with(Position { x: 1.0, y: 2.0 })
+            .build();
+        world.destroy(low).unwrap();
+        
+        // The only live index is further than MAX_INDEX_GAP from an empty backend
+        let registry = ComponentRegistry::new().register_cloned::<Position>();
+        let snapshot = world.snapshot(&registry);
+        let mut restored = World::<SparseSetBackend>::new().unwrap();
+        restored.restore(snapshot);
+        assert!(restored.is_alive(high));
+        assert_eq!(restored.get::<Position>(high), Some(&Position { x: 1.0, y: 2.0 }));
+        
+        let registry = ComponentRegistry::new().register::<Position>();
+        let migrated: World<SparseSetBackend> = world.migrate_into(&registry);
+        assert!(migrated.is_alive(high));
+        assert!(!migrated.is_alive(low));
+        assert_eq!(migrated.get::<Position>(high), Some(&Position { x: 1.0, y: 2.0 }));
+    }
+    
+    #[test]
+    fn test_get_disjoint_mut_swaps_components() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let a = world.spawn().with(Velocity { dx: 1.0, dy: 0.0 }).build();
+        let b = world.spawn().with(Velocity { dx: -1.0, dy: 2.0 }).build();
+        
+        let (va, vb) = world.get_disjoint_mut::<Velocity>(a, b).unwrap();
+        std::mem::swap(va, vb);
+        
+        assert_eq!(world.get::<Velocity>(a), Some(&Velocity { dx: -1.0, dy: 2.0 }));
+        assert_eq!(world.get::<Velocity>(b), Some(&Velocity { dx: 1.0, dy: 0.0 }));
+    }
+    
+    #[test]
+    fn test_get_disjoint_mut_rejects_aliasing_and_missing() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let a = world.spawn().with(Velocity { dx: 1.0, dy: 0.0 }).build();
+        let b = world.spawn().with(Position { x: 0.0, y: 0.0 }).build();
+        let c = world.spawn().with(Velocity { dx: 3.0, dy: 0.0 }).build();
+        
+        assert!(world.get_disjoint_mut::<Velocity>(a, a).is_none());
+        assert!(world.get_disjoint_mut::<Velocity>(a, b).is_none());
+        
+        world.destroy(c).unwrap();
+        assert!(world.get_disjoint_mut::<Velocity>(a, c).is_none());
+    }
+    
+    #[test]
+    fn test_clear_component_removes_only_that_type() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let entities: Vec<Entity> = (0..50)
+            .map(|i| {
+                world
+                    .spawn()
+                    .with(Position { x: i as f32, y: 0.0 })
+                    .with(Velocity { dx: 1.0, dy: 0.0 })
+                    .build()
+            })
+            .collect();
+        
+        assert_eq!(world.clear_component::<Velocity>(), 50);
+        
+        assert_eq!(world.query::<Velocity>().count(), 0);
+        for (i, &entity) in entities.iter().enumerate() {
+            assert!(world.is_alive(entity));
+            assert!(world.get::<Velocity>(entity).is_none());
+            assert_eq!(world.get::<Position>(entity), Some(&Position { x: i as f32, y: 0.0 }));
+        }
+        
+        // Nothing left to clear, and the type can be added again afterwards
+        assert_eq!(world.clear_component::<Velocity>(), 0);
+        world.add_component(entities[0], Velocity { dx: 2.0, dy: 0.0 }).unwrap();
+        assert_eq!(world.get::<Velocity>(entities[0]), Some(&Velocity { dx: 2.0, dy: 0.0 }));
+    }
+    
+    #[test]
+    fn test_map_component_updates_only_holders() {
+        #[derive(Debug, PartialEq)]
+        struct Scale(f32);
+        impl Component for Scale {}
+        
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let scaled: Vec<Entity> = (1..=3)
+            .map(|i| world.spawn().with(Scale(i as f32)).build())
+            .collect();
+        let plain = world.spawn().with(Position { x: 1.0, y: 1.0 }).build();
+        
+        world.map_component::<Scale>(|scale| scale.0 *= 2.0);
+        
+        for (i, &entity) in scaled.iter().enumerate() {
+            assert_eq!(world.get::<Scale>(entity), Some(&Scale((i + 1) as f32 * 2.0)));
+        }
+        assert!(world.get::<Scale>(plain).is_none());
+        assert_eq!(world.get::<Position>(plain), Some(&Position { x: 1.0, y: 1.0 }));
+    }
+    
+    #[test]
+    fn test_dense_component_behaves_like_sparse() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        world.register_component::<Position>(StorageKind::Dense);
+        
+        let entities: Vec<Entity> = (0..5)
+            .map(|i| world.spawn().with(Position { x: i as f32, y: 0.0 }).build())
+            .collect();
+        let moving = world.spawn().with(Velocity { dx: 1.0, dy: 0.0 }).build();
+        world.add_component(moving, Position { x: 9.0, y: 0.0 }).unwrap();
+        
+        // Read, write and replace
+        assert_eq!(world.get::<Position>(entities[2]), Some(&Position { x: 2.0, y: 0.0 }));
+        world.get_mut::<Position>(entities[2]).unwrap().y = 5.0;
+        assert_eq!(world.get::<Position>(entities[2]), Some(&Position { x: 2.0, y: 5.0 }));
+        let old = world.replace_component(entities[2], Position { x: 7.0, y: 7.0 }).unwrap();
+        assert_eq!(old, Some(Position { x: 2.0, y: 5.0 }));
+        
+        // Disjoint borrows and joins with a sparse component
+        let (a, b) = world.get_disjoint_mut::<Position>(entities[0], entities[1]).unwrap();
+        std::mem::swap(a, b);
+        assert_eq!(world.get::<Position>(entities[0]), Some(&Position { x: 1.0, y: 0.0 }));
+        let joined: Vec<Entity> = world.iter2::<Position, Velocity>().map(|(e, _, _)| e).collect();
+        assert_eq!(joined, vec![moving]);
+        
+        // Removal and destruction leave the slot empty; recycled ids start fresh
+        world.remove::<Position>(entities[3]).unwrap();
+        world.destroy(entities[4]).unwrap();
+        assert!(world.get::<Position>(entities[3]).is_none());
+        let recycled = world.spawn().build();
+        assert_eq!(recycled.index(), entities[4].index());
+        assert!(world.get::<Position>(recycled).is_none());
+        
+        let sorted: Vec<Entity> = world.query_sorted::<Position>().map(|(e, _)| e).collect();
+        assert_eq!(sorted, vec![entities[0], entities[1], entities[2], moving]);
+        assert_eq!(world.query::<Position>().count(), 4);
+        
+        world.map_component::<Position>(|position| position.x += 10.0);
+        assert_eq!(world.get::<Position>(moving), Some(&Position { x: 19.0, y: 0.0 }));
+        
+        assert_eq!(world.clear_component::<Position>(), 4);
+        assert_eq!(world.query::<Position>().count(), 0);
+    }
+    
+    #[test]
+    fn test_register_component_moves_existing_components() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let entities: Vec<Entity> = (0..3)
+            .map(|i| world.spawn().with(Position { x: i as f32, y: 0.0 }).build())
+            .collect();
+        
+        world.register_component::<Position>(StorageKind::Dense);
+        for (i, &entity) in entities.iter().enumerate() {
+            assert_eq!(world.get::<Position>(entity), Some(&Position { x: i as f32, y: 0.0 }));
+        }
+        
+        world.register_component::<Position>(StorageKind::SparseSet);
+        assert_eq!(world.query::<Position>().count(), 3);
+    }
+    
+    #[test]
+    fn test_entity_last_changed_tracks_structural_changes() {
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let moved = world.spawn().with(Position { x: 0.0, y: 0.0 }).build();
+        let untouched = world.spawn().with(Position { x: 1.0, y: 1.0 }).build();
+        let untouched_tick = world.entity_last_changed(untouched).unwrap();
+        
+        world.add_component(moved, Velocity { dx: 1.0, dy: 0.0 }).unwrap();
+        let moved_tick = world.entity_last_changed(moved).unwrap();
+        assert_eq!(moved_tick, world.tick());
+        assert!(untouched_tick < moved_tick);
+        
+        // Writing a value is not structural
+        world.get_mut::<Position>(moved).unwrap().x = 5.0;
+        world.replace_component(moved, Velocity { dx: 2.0, dy: 0.0 }).unwrap();
+        world.add_component(moved, Velocity { dx: 3.0, dy: 0.0 }).unwrap();
+        assert_eq!(world.entity_last_changed(moved), Some(moved_tick));
+        assert_eq!(world.tick(), moved_tick);
+        
+        world.remove::<Velocity>(moved).unwrap();
+        assert!(world.entity_last_changed(moved).unwrap() > moved_tick);
+        assert_eq!(world.entity_last_changed(untouched), Some(untouched_tick));
+        
+        world.destroy(moved).unwrap();
+        assert_eq!(world.entity_last_changed(moved), None);
+        
+        // Despawning dead entities changes nothing
+        let tick = world.tick();
+        assert_eq!(world.despawn_batch(vec![moved, moved, moved]), 0);
+        assert_eq!(world.tick(), tick);
+    }
+    
+    #[test]
+    fn test_restore_reverts_changes_since_snapshot() {
+        #[derive(Debug, PartialEq)]
+        struct Handle(u32);
+        impl Component for Handle {}
+        
+        let mut world = World::<SparseSetBackend>::new().unwrap();
+        let player = world.spawn()
+            .with(Position { x: 1.0, y: 2.0 })
+            .with(Handle(7))
+            .build();
+        let doomed = world.spawn().with(Velocity { dx: 3.0, dy: 4.0 }).build();
+        
+        let registry = ComponentRegistry::new()
+            .register_cloned::<Position>()
+            .register_cloned::<Velocity>()
+            .register_with_clone::<Handle>(|handle| Handle(handle.0));
+        let snapshot = world.snapshot(&registry);
+        assert_eq!(snapshot.entity_count(), 2);
+        
+        world.get_mut::<Position>(player).unwrap().x = 100.0;
+        world.remove::<Handle>(player).unwrap();
+        world.add_component(player, Velocity { dx: 0.0, dy: 0.0 }).unwrap();
+        world.destroy(doomed).unwrap();
+        let spawned = world.spawn().with(Position { x: 9.0, y: 9.0 }).build();
+        assert_eq!(spawned.index(), doomed.index());
+        
+        world.restore(snapshot);
+        
+        assert_eq!(world.get::<Position>(player), Some(&Position { x: 1.0, y: 2.0 }));
+        assert_eq!(world.get::<Handle>(player), Some(&Handle(7)));
+        assert_eq!(world.get::<Velocity>(player), None);
+        assert!(world.is_alive(doomed));
+        assert_eq!(world.get::<Velocity>(doomed), Some(&Velocity { dx: 3.0, dy: 4.0 }));
+        assert!(!world.is_alive(spawned));
+        assert_eq!(world.query::<Position>().count(), 1);
+        
+        // Reusing the slot must not revive the handle issued before the restore
+        world.destroy(doomed).unwrap();
+        let reissued = world.spawn().build();
+        assert_eq!(reissued.index(), doomed.index());
+        assert_ne!(reissued, spawned);
+        assert!(!world.is_alive(spawned));
+        assert!(world.get::<Position>(spawned).is_none());
+    }
 }
\ No newline at end of file
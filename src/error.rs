@@ -5,6 +5,8 @@
 
 use thiserror::Error;
 
+use crate::ecs::Entity;
+
 /// Main error type for Gamma-VK operations
 ///
 /// This enum covers all possible errors that can occur during Gamma-VK operations,
@@ -31,9 +33,68 @@ pub enum GammaVkError {
     #[error("Buffer operation failed: {message}")]
     BufferCreation { message: String },
 
+    /// The driver ran out of memory while allocating a buffer
+    ///
+    /// Unlike other buffer errors this is recoverable: free or evict resources and
+    /// retry the allocation.
+    #[error(
+        "Out of memory allocating {requested} bytes of {} memory",
+        if *device_local { "device-local" } else { "host-visible" }
+    )]
+    OutOfMemory {
+        /// Whether the allocation targeted device-local memory
+        device_local: bool,
+        /// The requested allocation size in bytes
+        requested: u64,
+    },
+
+    /// A CPU access targeted buffer memory that is not host-visible
+    ///
+    /// Retrying cannot succeed; upload with `Buffer::upload_staged` instead.
+    #[error(
+        "Buffer is device-local and cannot be accessed from the CPU; \
+         upload with Buffer::upload_staged instead"
+    )]
+    BufferNotHostVisible,
+
+    /// A host-visible buffer is locked by another CPU or GPU access
+    ///
+    /// This is transient: retry once the other access has finished.
+    #[error("Buffer is busy: {message}")]
+    BufferBusy { message: String },
+
+    /// Image and image view creation errors
+    #[error("Image creation failed: {message}")]
+    ImageCreation { message: String },
+
     /// Shader compilation and loading errors
     #[error("Shader compilation failed: {message}")]
     ShaderCompilation { message: String },
+
+    /// Graphics pipeline creation errors
+    #[error("Pipeline creation failed: {message}")]
+    PipelineCreation { message: String },
+
+    /// Command buffer recording and submission errors
+    #[error("Command execution failed: {message}")]
+    CommandExecution { message: String },
+
+    /// An ECS operation targeted an entity that is not alive
+    #[error("Entity not found: {0}")]
+    EntityNotFound(Entity),
+
+    /// An ECS operation expected a component the entity does not have
+    #[error("Component not found for entity: {0}")]
+    ComponentNotFound(Entity),
+
+    /// An entity could not be spawned because its slot is occupied by a live entity
+    #[error("Entity already alive: {0}")]
+    EntityAlreadyAlive(Entity),
+
+    /// An entity could not be spawned because its index is too far past the
+    /// entities allocated so far
+    #[error("Entity index out of range: {0}")]
+    EntityIndexOutOfRange(Entity),
 }
 
 impl GammaVkError {
@@ -51,12 +112,33 @@ impl GammaVkError {
         }
     }
 
+    /// Create a new image creation error with a custom message
+    pub fn image_creation<S: Into<String>>(message: S) -> Self {
+        Self::ImageCreation {
+            message: message.into(),
+        }
+    }
+
     /// Create a new shader compilation error with a custom message
     pub fn shader_compilation<S: Into<String>>(message: S) -> Self {
         Self::ShaderCompilation {
             message: message.into(),
         }
     }
+
+    /// Create a new pipeline creation error with a custom message
+    pub fn pipeline_creation<S: Into<String>>(message: S) -> Self {
+        Self::PipelineCreation {
+            message: message.into(),
+        }
+    }
+
+    /// Create a new command execution error with a custom message
+    pub fn command_execution<S: Into<String>>(message: S) -> Self {
+        Self::CommandExecution {
+            message: message.into(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -81,4 +163,30 @@ mod tests {
         assert!(error_string.contains("Initialization failed"));
         assert!(error_string.contains("display test"));
     }
+
+    #[test]
+    fn test_out_of_memory_error_fields_and_display() {
+        let error = GammaVkError::OutOfMemory {
+            device_local: true,
+            requested: 1 << 30,
+        };
+        match error {
+            GammaVkError::OutOfMemory {
+                device_local,
+                requested,
+            } => {
+                assert!(device_local);
+                assert_eq!(requested, 1 << 30);
+            }
+            _ => panic!("Expected out of memory error"),
+        }
+
+        let error_string = GammaVkError::OutOfMemory {
+            device_local: false,
+            requested: 4096,
+        }
+        .to_string();
+        assert!(error_string.contains("4096 bytes"));
+        assert!(error_string.contains("host-visible"));
+    }
 }
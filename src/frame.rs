@@ -0,0 +1,213 @@
+//! Per-frame fence recycling for Gamma-VK
+//!
+//! This module provides the N-buffered submission pattern used by render loops:
+//! a fixed set of fences, one per frame slot, reused round-robin so the CPU can
+//! record frame `N + 1` while the GPU is still executing frame `N`.
+
+use std::sync::Arc;
+use vulkano::{
+    device::Queue,
+    sync::fence::{Fence, FenceCreateInfo},
+};
+
+use crate::{GammaVkError, Result, VulkanContext};
+
+/// A ring of frame slots, each guarded by a reusable fence
+///
+/// Created with [`VulkanContext::frames`]. Call [`begin_frame`](Self::begin_frame)
+/// before touching a slot's resources, submit the frame's work to the graphics
+/// queue, then call [`end_frame`](Self::end_frame) to signal the slot's fence once
+/// that work completes. Fences are allocated once and reused for every frame.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gamma_vk::{CommandRecorder, VulkanContext};
+///
+/// let context = VulkanContext::new()?;
+/// let mut frames = context.frames(2)?;
+///
+/// for _ in 0..3 {
+///     let frame = frames.begin_frame()?;
+///     // Per-frame resources indexed by `frame.index()` are free to reuse here
+///     let recorder = CommandRecorder::new(&context)?;
+///     recorder.submit_and_wait(&context.graphics_queue())?;
+///     frames.end_frame(frame)?;
+/// }
+/// # Ok::<(), gamma_vk::GammaVkError>(())
+/// ```
+pub struct FrameContext {
+    /// The queue frame work is submitted to
+    queue: Arc<Queue>,
+    /// One fence per frame slot
+    fences: Vec<Arc<Fence>>,
+    /// Whether each slot's fence has a pending signal
+    in_flight: Vec<bool>,
+    /// The slot the next frame uses
+    current: usize,
+}
+
+impl FrameContext {
+    /// Create `count` frame slots on the context's graphics queue
+    pub(crate) fn new(context: &VulkanContext, count: usize) -> Result<Self> {
+        if count == 0 {
+            return Err(GammaVkError::command_execution(
+                "Frame count must be greater than 0",
+            ));
+        }
+
+        let device = context.device();
+        let fences = (0..count)
+            .map(|_| {
+                Fence::new(device.clone(), FenceCreateInfo::default())
+                    .map(Arc::new)
+                    .map_err(|e| {
+                        GammaVkError::command_execution(format!(
+                            "Failed to create frame fence: {}",
+                            e
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            queue: context.graphics_queue(),
+            fences,
+            in_flight: vec![false; count],
+            current: 0,
+        })
+    }
+
+    /// Get the number of frame slots
+    pub fn frame_count(&self) -> usize {
+        self.fences.len()
+    }
+
+    /// Get the slot index the next frame will use
+    pub fn current_frame(&self) -> usize {
+        self.current
+    }
+
+    /// Start the next frame, blocking until the GPU has finished with its slot
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if waiting on or resetting the slot's fence fails.
+    pub fn begin_frame(&mut self) -> Result<FrameGuard> {
+        let index = self.current;
+        let fence = &self.fences[index];
+
+        if self.in_flight[index] {
+            fence.wait(None).map_err(|e| {
+                GammaVkError::command_execution(format!(
+                    "Failed to wait for frame {}: {}",
+                    index, e
+                ))
+            })?;
+            // Safety: the wait above guarantees the device no longer uses the fence
+            unsafe { fence.reset() }.map_err(|e| {
+                GammaVkError::command_execution(format!(
+                    "Failed to reset frame {} fence: {}",
+                    index, e
+                ))
+            })?;
+            self.in_flight[index] = false;
+        }
+
+        Ok(FrameGuard {
+            index,
+            fence: fence.clone(),
+        })
+    }
+
+    /// Finish a frame, signaling its fence once all work submitted so far completes
+    ///
+    /// Submit the frame's command buffers before calling this. The next
+    /// [`begin_frame`](Self::begin_frame) moves on to the following slot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `frame` is not the frame most recently begun, or if the
+    /// fence signal cannot be submitted.
+    pub fn end_frame(&mut self, frame: FrameGuard) -> Result<()> {
+        if frame.index != self.current {
+            return Err(GammaVkError::command_execution(format!(
+                "Ending frame {} but frame {} is current",
+                frame.index, self.current
+            )));
+        }
+
+        // An empty submission signals the fence after all prior work on the queue
+        // Safety: the fence was reset in begin_frame and is kept alive by self
+        self.queue
+            .with(|mut queue| unsafe { queue.submit(&[], Some(&frame.fence)) })
+            .map_err(|e| {
+                GammaVkError::command_execution(format!(
+                    "Failed to signal frame {} fence: {}",
+                    frame.index, e
+                ))
+            })?;
+
+        self.in_flight[frame.index] = true;
+        self.current = (self.current + 1) % self.fences.len();
+        Ok(())
+    }
+
+    /// Block until every in-flight frame has finished on the GPU
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if waiting on a fence fails.
+    pub fn wait_idle(&mut self) -> Result<()> {
+        for (index, fence) in self.fences.iter().enumerate() {
+            if self.in_flight[index] {
+                fence.wait(None).map_err(|e| {
+                    GammaVkError::command_execution(format!(
+                        "Failed to wait for frame {}: {}",
+                        index, e
+                    ))
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for FrameContext {
+    /// Wait for in-flight frames so no fence is destroyed while the GPU uses it
+    fn drop(&mut self) {
+        let _ = self.wait_idle();
+    }
+}
+
+impl std::fmt::Debug for FrameContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FrameContext")
+            .field("frame_count", &self.frame_count())
+            .field("current", &self.current)
+            .field("in_flight", &self.in_flight)
+            .finish()
+    }
+}
+
+/// A frame slot handed out by [`FrameContext::begin_frame`]
+///
+/// Pass it back to [`FrameContext::end_frame`] after submitting the frame's work.
+#[must_use = "pass the frame to FrameContext::end_frame to signal its fence"]
+#[derive(Debug)]
+pub struct FrameGuard {
+    index: usize,
+    fence: Arc<Fence>,
+}
+
+impl FrameGuard {
+    /// Get the slot index, for selecting per-frame resources
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Get the fence guarding this slot
+    pub fn fence(&self) -> &Arc<Fence> {
+        &self.fence
+    }
+}
@@ -0,0 +1,238 @@
+//! Image management for Gamma-VK
+//!
+//! This module provides RAII-managed images paired with a default view, ready to
+//! be used as attachments or sampled in shaders, and the samplers used to read
+//! them.
+
+use std::sync::Arc;
+use vulkano::{
+    format::Format,
+    image::{
+        Image as VulkanoImage, ImageCreateInfo, ImageType, ImageUsage,
+        sampler::{
+            Filter, Sampler as VulkanoSampler, SamplerAddressMode, SamplerCreateInfo,
+            SamplerMipmapMode,
+        },
+        view::ImageView,
+    },
+    memory::allocator::{AllocationCreateInfo, MemoryTypeFilter},
+};
+
+use crate::{GammaVkError, Result, VulkanContext};
+
+/// A 2D device-local image with a view covering all of it
+///
+/// The image and view are released automatically when the last reference to
+/// them is dropped, including references held by framebuffers and command
+/// buffers still in flight.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gamma_vk::{Image, VulkanContext, render::{Framebuffer, RenderPass}};
+/// use vulkano::format::Format;
+///
+/// let context = VulkanContext::new()?;
+/// let target = Image::new_render_target(&context, Format::R8G8B8A8_UNORM, [1280, 720])?;
+/// let render_pass = RenderPass::new(&context, [Format::R8G8B8A8_UNORM], None)?;
+/// let framebuffer = Framebuffer::new(&render_pass, &[target.view().clone()])?;
+/// # Ok::<(), gamma_vk::GammaVkError>(())
+/// ```
+pub struct Image {
+    /// The underlying Vulkano image
+    image: Arc<VulkanoImage>,
+    /// The default view over the whole image
+    view: Arc<ImageView>,
+}
+
+impl Image {
+    /// Create an offscreen color target that can later be sampled
+    ///
+    /// The image has `COLOR_ATTACHMENT | SAMPLED` usage, so it can be rendered
+    /// to through a [`Framebuffer`](crate::render::Framebuffer) or dynamic
+    /// rendering, then read by a later pass.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either extent dimension is zero, the format cannot be
+    /// used as a color attachment on this device, or allocation fails.
+    pub fn new_render_target(
+        context: &VulkanContext,
+        format: Format,
+        extent: [u32; 2],
+    ) -> Result<Self> {
+        if extent.contains(&0) {
+            return Err(GammaVkError::image_creation(format!(
+                "Render target extent must be non-zero, got {}x{}",
+                extent[0], extent[1]
+            )));
+        }
+        if !context.supports_color_attachment(format) {
+            return Err(GammaVkError::image_creation(format!(
+                "Format {:?} cannot be used as a color attachment on this device",
+                format
+            )));
+        }
+
+        profile_scope!("Image allocation");
+        let image = VulkanoImage::new(
+            context.memory_allocator(),
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format,
+                extent: [extent[0], extent[1], 1],
+                usage: ImageUsage::COLOR_ATTACHMENT | ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE,
+                ..Default::default()
+            },
+        )
+        .map_err(|e| {
+            GammaVkError::image_creation(format!("Failed to allocate render target: {}", e))
+        })?;
+
+        let view = ImageView::new_default(image.clone()).map_err(|e| {
+            GammaVkError::image_creation(format!("Failed to create image view: {}", e))
+        })?;
+
+        Ok(Self { image, view })
+    }
+
+    /// Get the image format
+    pub fn format(&self) -> Format {
+        self.image.format()
+    }
+
+    /// Get the width and height in pixels
+    pub fn extent(&self) -> [u32; 2] {
+        let [width, height, _] = self.image.extent();
+        [width, height]
+    }
+
+    /// Get the image usage flags
+    pub fn usage(&self) -> ImageUsage {
+        self.image.usage()
+    }
+
+    /// Get the view covering the whole image, for attachments and descriptor sets
+    pub fn view(&self) -> &Arc<ImageView> {
+        &self.view
+    }
+
+    /// Get the underlying Vulkano image
+    pub fn inner(&self) -> &Arc<VulkanoImage> {
+        &self.image
+    }
+}
+
+impl std::fmt::Debug for Image {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Image")
+            .field("format", &self.format())
+            .field("extent", &self.extent())
+            .field("usage", &self.usage())
+            .finish()
+    }
+}
+
+/// A sampler describing how shaders filter and address an image
+///
+/// The sampler is destroyed when the last reference to it is dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gamma_vk::{VulkanContext, image::Sampler};
+///
+/// let context = VulkanContext::new()?;
+/// let sampler = Sampler::anisotropic(&context, 16.0)?;
+/// # Ok::<(), gamma_vk::GammaVkError>(())
+/// ```
+#[derive(Clone)]
+pub struct Sampler {
+    inner: Arc<VulkanoSampler>,
+}
+
+impl Sampler {
+    /// Create a sampler from a full Vulkano create info
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if Vulkan rejects the create info, for example when it
+    /// requests anisotropy above `maxSamplerAnisotropy`.
+    pub fn new(context: &VulkanContext, create_info: SamplerCreateInfo) -> Result<Self> {
+        let inner = VulkanoSampler::new(context.device(), create_info).map_err(|e| {
+            GammaVkError::image_creation(format!("Failed to create sampler: {}", e))
+        })?;
+        Ok(Self { inner })
+    }
+
+    /// Create a sampler with linear filtering that repeats outside `[0, 1]`
+    pub fn linear_repeat(context: &VulkanContext) -> Result<Self> {
+        Self::new(context, SamplerCreateInfo::simple_repeat_linear())
+    }
+
+    /// Create a sampler with nearest filtering that clamps to the edge
+    ///
+    /// Suited to pixel art and to reading back render targets texel for texel.
+    pub fn nearest_clamp(context: &VulkanContext) -> Result<Self> {
+        Self::new(
+            context,
+            SamplerCreateInfo {
+                mag_filter: Filter::Nearest,
+                min_filter: Filter::Nearest,
+                mipmap_mode: SamplerMipmapMode::Nearest,
+                address_mode: [SamplerAddressMode::ClampToEdge; 3],
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Create a linear repeating sampler with anisotropic filtering
+    ///
+    /// `max_anisotropy` is clamped to the device's `maxSamplerAnisotropy`. On
+    /// devices without anisotropy support this returns the same sampler as
+    /// [`Sampler::linear_repeat`] instead of failing.
+    pub fn anisotropic(context: &VulkanContext, max_anisotropy: f32) -> Result<Self> {
+        let anisotropy = if context.supports_anisotropy() && max_anisotropy > 1.0 {
+            let limit = context
+                .physical_device()
+                .properties()
+                .max_sampler_anisotropy;
+            Some(max_anisotropy.min(limit))
+        } else {
+            None
+        };
+
+        Self::new(
+            context,
+            SamplerCreateInfo {
+                anisotropy,
+                ..SamplerCreateInfo::simple_repeat_linear()
+            },
+        )
+    }
+
+    /// Get the maximum anisotropy, or `None` if anisotropic filtering is disabled
+    pub fn anisotropy(&self) -> Option<f32> {
+        self.inner.anisotropy()
+    }
+
+    /// Get the underlying Vulkano sampler
+    pub fn inner(&self) -> &Arc<VulkanoSampler> {
+        &self.inner
+    }
+}
+
+impl std::fmt::Debug for Sampler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sampler")
+            .field("mag_filter", &self.inner.mag_filter())
+            .field("min_filter", &self.inner.min_filter())
+            .field("address_mode", &self.inner.address_mode())
+            .field("anisotropy", &self.inner.anisotropy())
+            .finish()
+    }
+}
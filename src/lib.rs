@@ -3,16 +3,94 @@
 //! This library provides a high-level interface for Vulkan graphics programming
 //! with automatic resource management through RAII patterns.
 
+// Lets `#[derive(Component)]`, which names `::gamma_vk::ecs::Component`, expand
+// inside this crate as well
+extern crate self as gamma_vk;
+
+/// Open a profiling scope covering the rest of the enclosing block
+///
+/// Expands to `profiling::scope!` with the `profiling` feature and to nothing
+/// otherwise, so default builds carry no instrumentation.
+macro_rules! profile_scope {
+    ($name:expr) => {
+        #[cfg(feature = "profiling")]
+        profiling::scope!($name);
+    };
+}
+
+/// Return `Err($error)` from the enclosing function unless `$cond` holds
+///
+/// With the `checked` feature (on by default) this is a runtime check. Without
+/// it the condition is only asserted in debug builds, and release builds skip
+/// the check entirely, trusting the caller to pass valid input. Guarded calls
+/// document what they assume when the check is compiled out.
+macro_rules! ensure {
+    ($cond:expr, $error:expr) => {
+        #[cfg(feature = "checked")]
+        if !$cond {
+            return Err($error);
+        }
+        #[cfg(not(feature = "checked"))]
+        debug_assert!($cond, "{}", $error);
+    };
+}
+
 pub mod buffer;
+pub mod command;
 pub mod context;
+pub mod ecs;
 pub mod error;
+pub mod frame;
+pub mod image;
+pub mod pipeline;
+pub mod query;
+pub mod render;
 pub mod shader;
 
 // Re-export main types for easy library usage
-pub use buffer::{Buffer, IndexBuffer, UniformBuffer, VertexBuffer};
-pub use context::VulkanContext;
+pub use buffer::{
+    AccessPattern, Buffer, BufferSlice, IndexBuffer, PersistentMappedBuffer, SharedBuffer,
+    TypedUniformBuffer, UniformBuffer, VertexBuffer,
+};
+pub use command::{CommandRecorder, DrawCommand, DrawDescriptor, ReusableCommandBuffer};
+pub use context::{AllocationStats, ContextDiagnostics, VulkanContext};
 pub use error::GammaVkError;
-pub use shader::ShaderModule;
+pub use frame::{FrameContext, FrameGuard};
+pub use image::Image;
+pub use query::QueryPool;
+pub use shader::{ShaderModule, ShaderStage, SourceLanguage};
 
 /// Result type alias for convenient error handling throughout the library
 pub type Result<T> = std::result::Result<T, GammaVkError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guarded(value: u32) -> Result<u32> {
+        ensure!(
+            value < 10,
+            GammaVkError::initialization("value out of range")
+        );
+        Ok(value)
+    }
+
+    #[test]
+    fn test_ensure_passes_valid_input() {
+        assert_eq!(guarded(3).unwrap(), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "checked")]
+    fn test_ensure_rejects_invalid_input_when_checked() {
+        let error = guarded(10).unwrap_err();
+        assert!(error.to_string().contains("value out of range"));
+    }
+
+    #[test]
+    #[cfg(all(not(feature = "checked"), debug_assertions))]
+    #[should_panic(expected = "value out of range")]
+    fn test_ensure_asserts_in_debug_when_unchecked() {
+        let _ = guarded(10);
+    }
+}
@@ -0,0 +1,437 @@
+//! Graphics pipeline helpers for Gamma-VK
+//!
+//! This module provides ready-made pipelines for common rendering building blocks.
+
+use std::{collections::HashMap, sync::Arc};
+use vulkano::{
+    format::Format,
+    pipeline::{
+        DynamicState, GraphicsPipeline, PipelineLayout, PipelineShaderStageCreateInfo,
+        graphics::{
+            GraphicsPipelineCreateInfo,
+            color_blend::{ColorBlendAttachmentState, ColorBlendState},
+            depth_stencil::{DepthState, DepthStencilState},
+            input_assembly::InputAssemblyState,
+            multisample::MultisampleState,
+            rasterization::RasterizationState,
+            subpass::PipelineSubpassType,
+            vertex_input::{
+                VertexInputAttributeDescription, VertexInputBindingDescription, VertexInputRate,
+                VertexInputState,
+            },
+            viewport::ViewportState,
+        },
+        layout::PipelineDescriptorSetLayoutCreateInfo,
+    },
+    render_pass::{RenderPass, Subpass},
+};
+
+use crate::{
+    GammaVkError, Result, ShaderModule, ShaderStage, VulkanContext, render::DynamicRendering,
+};
+
+/// SPIR-V for the built-in fullscreen-triangle vertex shader (`shaders/fullscreen.vert`)
+///
+/// The shader writes texture coordinates covering `[0, 1]` to location 0.
+pub const FULLSCREEN_VERTEX_SPIRV: &[u8] = include_bytes!("../shaders/fullscreen.vert.spv");
+
+/// Build a pipeline that draws a fullscreen triangle with the given fragment shader
+///
+/// The vertex stage is built in and generates the triangle from `gl_VertexIndex`, so
+/// no vertex buffer is bound; record `draw(3, 1, 0, 0)`. The fragment shader may read
+/// texture coordinates from `layout(location = 0) in vec2`. The pipeline targets the
+/// first subpass of `render_pass` and uses a dynamic viewport. If the subpass has a
+/// depth attachment, the triangle is depth tested with `Less` and writes depth.
+///
+/// # Errors
+///
+/// Returns an error if the fragment shader is tagged with another stage, has no
+/// `main` entry point, writes a different number of color outputs than the
+/// subpass has color attachments, or needs more push-constant bytes than the
+/// device allows, if the render pass has no subpasses, or if pipeline creation
+/// fails.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gamma_vk::{ShaderModule, VulkanContext, pipeline};
+/// # use std::sync::Arc;
+/// # use vulkano::render_pass::RenderPass;
+///
+/// # fn example(context: &VulkanContext, render_pass: &Arc<RenderPass>) -> gamma_vk::Result<()> {
+/// let blit = ShaderModule::from_spirv_file(&context.device(), "shaders/blit.frag.spv")?;
+/// let pipeline = pipeline::fullscreen(context, &blit, render_pass)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn fullscreen(
+    context: &VulkanContext,
+    fragment_shader: &ShaderModule,
+    render_pass: &Arc<RenderPass>,
+) -> Result<Arc<GraphicsPipeline>> {
+    let subpass = Subpass::from(render_pass.clone(), 0)
+        .ok_or_else(|| GammaVkError::pipeline_creation("Render pass has no subpasses"))?;
+    let color_attachment_count = subpass.num_color_attachments();
+    let has_depth = subpass.subpass_desc().depth_stencil_attachment.is_some();
+
+    build_fullscreen(
+        context,
+        fragment_shader,
+        subpass.into(),
+        color_attachment_count,
+        has_depth,
+    )
+}
+
+/// Build a fullscreen-triangle pipeline for dynamic rendering
+///
+/// Like [`fullscreen`], but targets the attachment formats in `rendering` instead
+/// of a render pass. Record draws between
+/// [`CommandRecorder::begin_rendering`](crate::CommandRecorder::begin_rendering) and
+/// [`CommandRecorder::end_rendering`](crate::CommandRecorder::end_rendering).
+///
+/// # Errors
+///
+/// Returns an error if dynamic rendering is not enabled on the device, the
+/// fragment shader is tagged with another stage, has no `main` entry point,
+/// writes a different number of color outputs than `rendering` has color
+/// formats, or needs more push-constant bytes than the device allows, or if
+/// pipeline creation fails.
+pub fn fullscreen_dynamic(
+    context: &VulkanContext,
+    fragment_shader: &ShaderModule,
+    rendering: &DynamicRendering,
+) -> Result<Arc<GraphicsPipeline>> {
+    if !crate::render::is_enabled(&context.device()) {
+        return Err(GammaVkError::pipeline_creation(
+            "Dynamic rendering is not enabled; build the context with enable_dynamic_rendering()",
+        ));
+    }
+
+    build_fullscreen(
+        context,
+        fragment_shader,
+        PipelineSubpassType::BeginRendering(rendering.pipeline_rendering_info()),
+        rendering.color_formats().len() as u32,
+        rendering.depth_format().is_some(),
+    )
+}
+
+fn build_fullscreen(
+    context: &VulkanContext,
+    fragment_shader: &ShaderModule,
+    subpass: PipelineSubpassType,
+    color_attachment_count: u32,
+    has_depth: bool,
+) -> Result<Arc<GraphicsPipeline>> {
+    if let Some(stage) = fragment_shader.stage()
+        && stage != ShaderStage::Fragment
+    {
+        return Err(GammaVkError::pipeline_creation(format!(
+            "Expected a fragment shader, got a {:?} shader",
+            stage
+        )));
+    }
+
+    if let Some(output_count) = fragment_shader.color_output_count()
+        && output_count != color_attachment_count
+    {
+        return Err(GammaVkError::pipeline_creation(format!(
+            "Fragment shader writes {} color outputs but the target has {} color attachments",
+            output_count, color_attachment_count
+        )));
+    }
+
+    if let Some(required) = fragment_shader.push_constant_size() {
+        check_push_constant_size(
+            required,
+            context
+                .physical_device()
+                .properties()
+                .max_push_constants_size,
+        )?;
+    }
+
+    let device = context.device();
+    let vertex_shader = ShaderModule::from_spirv_bytes(&device, FULLSCREEN_VERTEX_SPIRV)?;
+
+    let entry_point = |shader: &ShaderModule, stage: &str| {
+        shader.vulkano_module().entry_point("main").ok_or_else(|| {
+            GammaVkError::pipeline_creation(format!("{} shader has no `main` entry point", stage))
+        })
+    };
+
+    let stages = [
+        PipelineShaderStageCreateInfo::new(entry_point(&vertex_shader, "Vertex")?),
+        PipelineShaderStageCreateInfo::new(entry_point(fragment_shader, "Fragment")?),
+    ];
+
+    let layout_info = PipelineDescriptorSetLayoutCreateInfo::from_stages(&stages)
+        .into_pipeline_layout_create_info(device.clone())
+        .map_err(|e| {
+            GammaVkError::pipeline_creation(format!("Failed to derive pipeline layout: {:?}", e))
+        })?;
+    let layout = PipelineLayout::new(device.clone(), layout_info).map_err(|e| {
+        GammaVkError::pipeline_creation(format!("Failed to create pipeline layout: {}", e))
+    })?;
+
+    GraphicsPipeline::new(
+        device.clone(),
+        None,
+        GraphicsPipelineCreateInfo {
+            stages: stages.into_iter().collect(),
+            vertex_input_state: Some(VertexInputState::default()),
+            input_assembly_state: Some(InputAssemblyState::default()),
+            viewport_state: Some(ViewportState::default()),
+            rasterization_state: Some(RasterizationState::default()),
+            multisample_state: Some(MultisampleState::default()),
+            // Required whenever the target has a depth attachment
+            depth_stencil_state: has_depth.then(|| DepthStencilState {
+                depth: Some(DepthState::simple()),
+                ..Default::default()
+            }),
+            color_blend_state: Some(ColorBlendState::with_attachment_states(
+                color_attachment_count,
+                ColorBlendAttachmentState::default(),
+            )),
+            dynamic_state: [DynamicState::Viewport].into_iter().collect(),
+            subpass: Some(subpass),
+            ..GraphicsPipelineCreateInfo::layout(layout)
+        },
+    )
+    .map_err(|e| {
+        GammaVkError::pipeline_creation(format!("Failed to create fullscreen pipeline: {}", e))
+    })
+}
+
+/// Reject shaders needing more push-constant bytes than `maxPushConstantsSize`
+///
+/// Desktop GPUs commonly allow 256 bytes, but the spec only guarantees 128, so
+/// this is a frequent failure when porting to mobile.
+fn check_push_constant_size(required: u32, available: u32) -> Result<()> {
+    if required > available {
+        return Err(GammaVkError::pipeline_creation(format!(
+            "Shader needs {} bytes of push constants but the device allows at most {} (maxPushConstantsSize)",
+            required, available
+        )));
+    }
+    Ok(())
+}
+
+/// Fluent builder for the vertex input layout of a graphics pipeline
+///
+/// Declare each vertex buffer binding with its stride and whether it advances per
+/// vertex or per instance, then the attributes read from it. [`build`](Self::build)
+/// validates the layout and produces the [`VertexInputState`] for
+/// [`GraphicsPipelineCreateInfo`].
+///
+/// # Examples
+///
+/// ```
+/// use gamma_vk::pipeline::VertexLayoutBuilder;
+/// use vulkano::format::Format;
+///
+/// // Positions per vertex from buffer 0, a 4x4 transform per instance from buffer 1
+/// let mut layout = VertexLayoutBuilder::new()
+///     .per_vertex(0, 12)
+///     .attribute(0, 0, Format::R32G32B32_SFLOAT, 0)
+///     .per_instance(1, 64);
+/// for row in 0..4 {
+///     layout = layout.attribute(1 + row, 1, Format::R32G32B32A32_SFLOAT, row * 16);
+/// }
+/// let vertex_input_state = layout.build()?;
+/// assert_eq!(vertex_input_state.bindings.len(), 2);
+/// # Ok::<(), gamma_vk::GammaVkError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct VertexLayoutBuilder {
+    bindings: Vec<(u32, VertexInputBindingDescription)>,
+    attributes: Vec<(u32, VertexInputAttributeDescription)>,
+}
+
+impl VertexLayoutBuilder {
+    /// Create a builder with no bindings or attributes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a binding whose elements advance once per vertex
+    pub fn per_vertex(self, binding: u32, stride: u32) -> Self {
+        self.binding(binding, stride, VertexInputRate::Vertex)
+    }
+
+    /// Add a binding whose elements advance once per instance
+    pub fn per_instance(self, binding: u32, stride: u32) -> Self {
+        self.binding(binding, stride, VertexInputRate::Instance { divisor: 1 })
+    }
+
+    /// Add an attribute at shader `location`, read from `binding` at byte `offset`
+    pub fn attribute(mut self, location: u32, binding: u32, format: Format, offset: u32) -> Self {
+        self.attributes.push((
+            location,
+            VertexInputAttributeDescription {
+                binding,
+                format,
+                offset,
+                ..Default::default()
+            },
+        ));
+        self
+    }
+
+    /// Validate the layout and build the vertex input state
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a binding or location is declared twice, an attribute
+    /// reads from an undeclared binding, or an attribute does not fit within its
+    /// binding's stride.
+    pub fn build(self) -> Result<VertexInputState> {
+        let mut bindings = HashMap::new();
+        for (binding, description) in self.bindings {
+            if bindings.insert(binding, description).is_some() {
+                return Err(GammaVkError::pipeline_creation(format!(
+                    "Vertex binding {} is declared more than once",
+                    binding
+                )));
+            }
+        }
+
+        let mut attributes = HashMap::new();
+        for (location, description) in self.attributes {
+            let stride = bindings
+                .get(&description.binding)
+                .map(|binding: &VertexInputBindingDescription| binding.stride)
+                .ok_or_else(|| {
+                    GammaVkError::pipeline_creation(format!(
+                        "Vertex attribute at location {} reads undeclared binding {}",
+                        location, description.binding
+                    ))
+                })?;
+
+            let end = u64::from(description.offset) + description.format.block_size();
+            if end > u64::from(stride) {
+                return Err(GammaVkError::pipeline_creation(format!(
+                    "Vertex attribute at location {} ends at byte {}, past binding {} stride {}",
+                    location, end, description.binding, stride
+                )));
+            }
+
+            if attributes.insert(location, description).is_some() {
+                return Err(GammaVkError::pipeline_creation(format!(
+                    "Vertex location {} is declared more than once",
+                    location
+                )));
+            }
+        }
+
+        Ok(VertexInputState::new()
+            .bindings(bindings)
+            .attributes(attributes))
+    }
+
+    fn binding(mut self, binding: u32, stride: u32, input_rate: VertexInputRate) -> Self {
+        self.bindings.push((
+            binding,
+            VertexInputBindingDescription {
+                stride,
+                input_rate,
+                ..Default::default()
+            },
+        ));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vulkano::shader::spirv::Spirv;
+
+    #[test]
+    fn test_fullscreen_vertex_spirv_is_valid() {
+        let words: Vec<u32> = FULLSCREEN_VERTEX_SPIRV
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+
+        assert!(Spirv::new(&words).is_ok());
+    }
+
+    #[test]
+    fn test_push_constants_over_device_limit_rejected() {
+        let push_constant_size = |spirv: &[u8]| {
+            let words: Vec<u32> = spirv
+                .chunks_exact(4)
+                .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                .collect();
+            crate::shader::push_constant_size_of(&words)
+        };
+        let required = push_constant_size(include_bytes!("../shaders/tinted.frag.spv"))
+            .expect("Shader declares a push-constant block");
+        assert_eq!(required, 192);
+
+        assert!(check_push_constant_size(required, 256).is_ok());
+        let message = check_push_constant_size(required, 128)
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("192"), "got: {}", message);
+        assert!(message.contains("128"), "got: {}", message);
+        assert!(message.contains("maxPushConstantsSize"), "got: {}", message);
+
+        assert_eq!(push_constant_size(FULLSCREEN_VERTEX_SPIRV), None);
+    }
+
+    #[test]
+    fn test_vertex_layout_with_per_instance_binding() {
+        let mut layout = VertexLayoutBuilder::new()
+            .per_vertex(0, 12)
+            .attribute(0, 0, Format::R32G32B32_SFLOAT, 0)
+            .per_instance(1, 64);
+        for row in 0..4 {
+            layout = layout.attribute(1 + row, 1, Format::R32G32B32A32_SFLOAT, row * 16);
+        }
+
+        let state = layout.build().expect("Layout should be valid");
+
+        assert_eq!(state.bindings.len(), 2);
+        assert_eq!(state.attributes.len(), 5);
+        assert_eq!(state.bindings[&0].input_rate, VertexInputRate::Vertex);
+        assert_eq!(
+            state.bindings[&1].input_rate,
+            VertexInputRate::Instance { divisor: 1 }
+        );
+        assert_eq!(state.attributes[&4].offset, 48);
+    }
+
+    #[test]
+    fn test_vertex_layout_rejects_attribute_past_stride() {
+        let result = VertexLayoutBuilder::new()
+            .per_vertex(0, 12)
+            .attribute(0, 0, Format::R32G32_SFLOAT, 8)
+            .build();
+
+        assert!(matches!(result, Err(GammaVkError::PipelineCreation { .. })));
+    }
+
+    #[test]
+    fn test_vertex_layout_rejects_undeclared_binding_and_duplicates() {
+        let undeclared = VertexLayoutBuilder::new()
+            .attribute(0, 3, Format::R32_SFLOAT, 0)
+            .build();
+        assert!(undeclared.is_err());
+
+        let duplicate_binding = VertexLayoutBuilder::new()
+            .per_vertex(0, 12)
+            .per_instance(0, 64)
+            .build();
+        assert!(duplicate_binding.is_err());
+
+        let duplicate_location = VertexLayoutBuilder::new()
+            .per_vertex(0, 12)
+            .attribute(0, 0, Format::R32_SFLOAT, 0)
+            .attribute(0, 0, Format::R32_SFLOAT, 4)
+            .build();
+        assert!(duplicate_location.is_err());
+    }
+}
@@ -0,0 +1,176 @@
+//! GPU timestamp queries for Gamma-VK
+//!
+//! This module provides a timestamp query pool used by
+//! [`CommandRecorder::time_scope`](crate::CommandRecorder::time_scope) to measure
+//! how long recorded work takes on the GPU.
+
+use std::sync::Arc;
+use vulkano::query::{
+    QueryPool as VulkanoQueryPool, QueryPoolCreateInfo, QueryResultFlags, QueryType,
+};
+
+use crate::{GammaVkError, Result, VulkanContext};
+
+/// A pool of GPU timestamp queries, two per timed scope
+///
+/// On devices whose graphics queue does not support timestamps the pool is
+/// created without a Vulkan query pool, and timed scopes record nothing.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gamma_vk::{CommandRecorder, QueryPool, VulkanContext};
+///
+/// # fn example(context: &VulkanContext, src: &gamma_vk::Buffer, dst: &gamma_vk::Buffer) -> gamma_vk::Result<()> {
+/// let mut queries = QueryPool::timestamps(context, 16)?;
+/// let mut recorder = CommandRecorder::new(context)?;
+/// recorder.time_scope(&mut queries, "copy", |rec| rec.copy_buffer(src, dst).map(|_| ()))?;
+/// recorder.submit_and_wait(&context.graphics_queue())?;
+///
+/// for (label, duration) in context.resolve_timestamps(&queries)? {
+///     println!("{label}: {duration:?}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct QueryPool {
+    /// The underlying Vulkano query pool, if timestamps are supported
+    pool: Option<Arc<VulkanoQueryPool>>,
+    /// Labels of the recorded scopes; scope `i` uses queries `2i` and `2i + 1`
+    labels: Vec<String>,
+    /// Maximum number of scopes the pool can hold
+    max_scopes: u32,
+    /// Number of meaningful bits in a timestamp value
+    valid_bits: u32,
+}
+
+impl QueryPool {
+    /// Create a timestamp query pool able to time up to `max_scopes` scopes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `max_scopes` is zero, needs more than `u32::MAX`
+    /// queries, or the query pool cannot be created.
+    pub fn timestamps(context: &VulkanContext, max_scopes: u32) -> Result<Self> {
+        if max_scopes == 0 {
+            return Err(GammaVkError::command_execution(
+                "Query pool must hold at least one scope",
+            ));
+        }
+        let query_count = max_scopes.checked_mul(2).ok_or_else(|| {
+            GammaVkError::command_execution(format!(
+                "Query pool cannot hold {} scopes: two queries per scope exceed u32::MAX",
+                max_scopes
+            ))
+        })?;
+
+        let valid_bits = context.physical_device().queue_family_properties()
+            [context.graphics_queue_family_index() as usize]
+            .timestamp_valid_bits;
+
+        let pool = match valid_bits {
+            Some(_) => Some(
+                VulkanoQueryPool::new(
+                    context.device(),
+                    QueryPoolCreateInfo {
+                        query_count,
+                        ..QueryPoolCreateInfo::query_type(QueryType::Timestamp)
+                    },
+                )
+                .map_err(|e| {
+                    GammaVkError::command_execution(format!(
+                        "Failed to create timestamp query pool: {}",
+                        e
+                    ))
+                })?,
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            pool,
+            labels: Vec::new(),
+            max_scopes,
+            valid_bits: valid_bits.unwrap_or(0),
+        })
+    }
+
+    /// Check whether timed scopes recorded into this pool produce timestamps
+    pub fn is_supported(&self) -> bool {
+        self.pool.is_some()
+    }
+
+    /// Forget all recorded scopes so the pool can be reused for a new submission
+    ///
+    /// Scopes recorded after this call reset and write the same queries again.
+    ///
+    /// # Safety
+    ///
+    /// Every submission that recorded scopes into this pool must have finished
+    /// executing, for example through
+    /// [`CommandRecorder::submit_and_wait`](crate::CommandRecorder::submit_and_wait)
+    /// or by waiting on its fence. Reusing the queries while such a submission
+    /// is still pending is a data race on the GPU.
+    pub unsafe fn clear(&mut self) {
+        self.labels.clear();
+    }
+
+    /// Reserve the next scope, returning the Vulkano pool and its first query index
+    pub(crate) fn begin_scope(
+        &mut self,
+        label: String,
+    ) -> Result<Option<(Arc<VulkanoQueryPool>, u32)>> {
+        let Some(pool) = self.pool.clone() else {
+            return Ok(None);
+        };
+
+        let scope = self.labels.len() as u32;
+        if scope >= self.max_scopes {
+            return Err(GammaVkError::command_execution(format!(
+                "Query pool is full: all {} scopes are in use",
+                self.max_scopes
+            )));
+        }
+
+        self.labels.push(label);
+        Ok(Some((pool, scope * 2)))
+    }
+
+    /// Wait for and read the elapsed ticks of every recorded scope
+    pub(crate) fn read_ticks(&self) -> Result<Vec<(String, u64)>> {
+        let Some(pool) = &self.pool else {
+            return Ok(Vec::new());
+        };
+        if self.labels.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut timestamps = vec![0u64; self.labels.len() * 2];
+        pool.get_results(
+            0..timestamps.len() as u32,
+            &mut timestamps,
+            QueryResultFlags::WAIT,
+        )
+        .map_err(|e| {
+            GammaVkError::command_execution(format!("Failed to read timestamp queries: {}", e))
+        })?;
+
+        let mask = u64::MAX >> (64 - self.valid_bits.min(64));
+        Ok(self
+            .labels
+            .iter()
+            .zip(timestamps.chunks_exact(2))
+            .map(|(label, pair)| (label.clone(), pair[1].wrapping_sub(pair[0]) & mask))
+            .collect())
+    }
+}
+
+impl std::fmt::Debug for QueryPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueryPool")
+            .field("supported", &self.is_supported())
+            .field("scopes", &self.labels.len())
+            .field("max_scopes", &self.max_scopes)
+            .finish()
+    }
+}
@@ -0,0 +1,325 @@
+//! Render target descriptions for Gamma-VK
+//!
+//! This module describes render targets either as single-subpass render passes or
+//! for Vulkan dynamic rendering, which lets pipelines and command buffers target
+//! attachments directly instead of going through render pass and framebuffer
+//! objects.
+
+use std::sync::Arc;
+use vulkano::{
+    device::Device,
+    format::Format,
+    image::{ImageLayout, SampleCount, view::ImageView},
+    pipeline::graphics::subpass::PipelineRenderingCreateInfo,
+    render_pass::{
+        AttachmentDescription, AttachmentLoadOp, AttachmentReference, AttachmentStoreOp,
+        Framebuffer as VulkanoFramebuffer, FramebufferCreateInfo, RenderPass as VulkanoRenderPass,
+        RenderPassCreateInfo, SubpassDescription,
+    },
+};
+
+use crate::{GammaVkError, Result, VulkanContext};
+
+/// A single-subpass render pass with any number of color attachments
+///
+/// Color attachments are cleared on load and stored, and are bound to the subpass
+/// in the given order, so fragment output `location = N` writes attachment `N`.
+/// The optional depth attachment is cleared and its contents discarded after the
+/// pass. Pass [`inner`](Self::inner) to [`crate::pipeline::fullscreen`], which
+/// checks the fragment shader writes one output per color attachment.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gamma_vk::{VulkanContext, render::RenderPass};
+/// use vulkano::format::Format;
+///
+/// let context = VulkanContext::new()?;
+/// // G-buffer: albedo, normal and position targets plus depth
+/// let gbuffer = RenderPass::new(
+///     &context,
+///     [
+///         Format::R8G8B8A8_UNORM,
+///         Format::R16G16B16A16_SFLOAT,
+///         Format::R32G32B32A32_SFLOAT,
+///     ],
+///     Some(Format::D32_SFLOAT),
+/// )?;
+/// assert_eq!(gbuffer.color_attachment_count(), 3);
+/// # Ok::<(), gamma_vk::GammaVkError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RenderPass {
+    inner: Arc<VulkanoRenderPass>,
+    color_formats: Vec<Format>,
+    depth_format: Option<Format>,
+}
+
+impl RenderPass {
+    /// Create a render pass drawing into the given color attachments and optional depth
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no color or depth attachment is given, or if Vulkan
+    /// rejects the render pass (for example, more color attachments than the
+    /// device's `max_color_attachments` limit).
+    pub fn new(
+        context: &VulkanContext,
+        color_formats: impl IntoIterator<Item = Format>,
+        depth_format: Option<Format>,
+    ) -> Result<Self> {
+        let color_formats: Vec<Format> = color_formats.into_iter().collect();
+        if color_formats.is_empty() && depth_format.is_none() {
+            return Err(GammaVkError::pipeline_creation(
+                "Render pass needs at least one color or depth attachment",
+            ));
+        }
+
+        let mut attachments: Vec<AttachmentDescription> = color_formats
+            .iter()
+            .map(|&format| AttachmentDescription {
+                format,
+                samples: SampleCount::Sample1,
+                load_op: AttachmentLoadOp::Clear,
+                store_op: AttachmentStoreOp::Store,
+                final_layout: ImageLayout::ColorAttachmentOptimal,
+                ..Default::default()
+            })
+            .collect();
+        let color_attachments = (0..color_formats.len() as u32)
+            .map(|attachment| {
+                Some(AttachmentReference {
+                    attachment,
+                    layout: ImageLayout::ColorAttachmentOptimal,
+                    ..Default::default()
+                })
+            })
+            .collect();
+
+        let depth_stencil_attachment = depth_format.map(|format| {
+            attachments.push(AttachmentDescription {
+                format,
+                samples: SampleCount::Sample1,
+                load_op: AttachmentLoadOp::Clear,
+                store_op: AttachmentStoreOp::DontCare,
+                final_layout: ImageLayout::DepthStencilAttachmentOptimal,
+                ..Default::default()
+            });
+            AttachmentReference {
+                attachment: color_formats.len() as u32,
+                layout: ImageLayout::DepthStencilAttachmentOptimal,
+                ..Default::default()
+            }
+        });
+
+        let inner = VulkanoRenderPass::new(
+            context.device(),
+            RenderPassCreateInfo {
+                attachments,
+                subpasses: vec![SubpassDescription {
+                    color_attachments,
+                    depth_stencil_attachment,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        )
+        .map_err(|e| {
+            GammaVkError::pipeline_creation(format!("Failed to create render pass: {}", e))
+        })?;
+
+        Ok(Self {
+            inner,
+            color_formats,
+            depth_format,
+        })
+    }
+
+    /// Get the color attachment formats, in attachment order
+    pub fn color_formats(&self) -> &[Format] {
+        &self.color_formats
+    }
+
+    /// Get the number of color attachments
+    pub fn color_attachment_count(&self) -> u32 {
+        self.color_formats.len() as u32
+    }
+
+    /// Get the depth attachment format, if any
+    pub fn depth_format(&self) -> Option<Format> {
+        self.depth_format
+    }
+
+    /// Get the underlying Vulkano render pass
+    pub fn inner(&self) -> &Arc<VulkanoRenderPass> {
+        &self.inner
+    }
+}
+
+/// The images a [`RenderPass`] draws into
+///
+/// Attachments are given in render pass order: the color attachments, then the
+/// depth attachment if the pass has one.
+///
+/// # Examples
+///
+/// See [`Image::new_render_target`](crate::Image::new_render_target).
+#[derive(Debug, Clone)]
+pub struct Framebuffer {
+    inner: Arc<VulkanoFramebuffer>,
+}
+
+impl Framebuffer {
+    /// Create a framebuffer binding `attachments` to `render_pass`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of attachments or any attachment's format
+    /// differs from the render pass, if the attachments differ in extent, or if
+    /// Vulkan rejects the framebuffer.
+    pub fn new(render_pass: &RenderPass, attachments: &[Arc<ImageView>]) -> Result<Self> {
+        let expected: Vec<Format> = render_pass
+            .color_formats()
+            .iter()
+            .copied()
+            .chain(render_pass.depth_format())
+            .collect();
+        if attachments.len() != expected.len() {
+            return Err(GammaVkError::pipeline_creation(format!(
+                "Render pass has {} attachments but {} were given",
+                expected.len(),
+                attachments.len()
+            )));
+        }
+
+        for (index, (view, &format)) in attachments.iter().zip(&expected).enumerate() {
+            if view.format() != format {
+                return Err(GammaVkError::pipeline_creation(format!(
+                    "Attachment {} has format {:?} but the render pass expects {:?}",
+                    index,
+                    view.format(),
+                    format
+                )));
+            }
+        }
+
+        let extent = |view: &ImageView| {
+            let [width, height, _] = view.image().extent();
+            [width, height]
+        };
+        if let Some(first) = attachments.first()
+            && let Some(mismatch) = attachments
+                .iter()
+                .position(|view| extent(view) != extent(first))
+        {
+            return Err(GammaVkError::pipeline_creation(format!(
+                "Attachment {} has extent {:?} but attachment 0 has extent {:?}",
+                mismatch,
+                extent(&attachments[mismatch]),
+                extent(first)
+            )));
+        }
+
+        let inner = VulkanoFramebuffer::new(
+            render_pass.inner().clone(),
+            FramebufferCreateInfo {
+                attachments: attachments.to_vec(),
+                ..Default::default()
+            },
+        )
+        .map_err(|e| {
+            GammaVkError::pipeline_creation(format!("Failed to create framebuffer: {}", e))
+        })?;
+
+        Ok(Self { inner })
+    }
+
+    /// Get the width and height shared by all attachments
+    pub fn extent(&self) -> [u32; 2] {
+        let [width, height] = self.inner.extent();
+        [width, height]
+    }
+
+    /// Get the underlying Vulkano framebuffer
+    pub fn inner(&self) -> &Arc<VulkanoFramebuffer> {
+        &self.inner
+    }
+}
+
+/// Attachment formats a dynamic-rendering pipeline draws into
+///
+/// Requires a context built with
+/// [`enable_dynamic_rendering`](crate::context::VulkanContextBuilder::enable_dynamic_rendering).
+/// Pass it to [`crate::pipeline::fullscreen_dynamic`] and record draws between
+/// [`crate::CommandRecorder::begin_rendering`] and
+/// [`crate::CommandRecorder::end_rendering`] with attachments of the same formats.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gamma_vk::{VulkanContext, render::DynamicRendering};
+/// use vulkano::format::Format;
+///
+/// let context = VulkanContext::builder().enable_dynamic_rendering().build()?;
+/// let rendering = DynamicRendering::new(&context, [Format::B8G8R8A8_SRGB])?
+///     .with_depth_format(Format::D32_SFLOAT);
+/// # Ok::<(), gamma_vk::GammaVkError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicRendering {
+    color_formats: Vec<Format>,
+    depth_format: Option<Format>,
+}
+
+impl DynamicRendering {
+    /// Describe render targets with the given color attachment formats
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if dynamic rendering is not enabled on the context's device.
+    pub fn new(
+        context: &VulkanContext,
+        color_formats: impl IntoIterator<Item = Format>,
+    ) -> Result<Self> {
+        if !is_enabled(&context.device()) {
+            return Err(GammaVkError::pipeline_creation(
+                "Dynamic rendering is not enabled; build the context with enable_dynamic_rendering()",
+            ));
+        }
+
+        Ok(Self {
+            color_formats: color_formats.into_iter().collect(),
+            depth_format: None,
+        })
+    }
+
+    /// Add a depth attachment of the given format
+    pub fn with_depth_format(mut self, format: Format) -> Self {
+        self.depth_format = Some(format);
+        self
+    }
+
+    /// Get the color attachment formats, in attachment order
+    pub fn color_formats(&self) -> &[Format] {
+        &self.color_formats
+    }
+
+    /// Get the depth attachment format, if any
+    pub fn depth_format(&self) -> Option<Format> {
+        self.depth_format
+    }
+
+    /// Build the Vulkano rendering info for pipeline creation
+    pub fn pipeline_rendering_info(&self) -> PipelineRenderingCreateInfo {
+        PipelineRenderingCreateInfo {
+            color_attachment_formats: self.color_formats.iter().copied().map(Some).collect(),
+            depth_attachment_format: self.depth_format,
+            ..Default::default()
+        }
+    }
+}
+
+/// Check whether dynamic rendering was enabled on `device`
+pub(crate) fn is_enabled(device: &Device) -> bool {
+    device.enabled_features().dynamic_rendering
+}
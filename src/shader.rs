@@ -3,14 +3,185 @@
 //! This module provides RAII-managed shader types with automatic resource cleanup
 //! and type-safe shader loading from SPIR-V bytecode.
 
-use std::{fs, path::Path, sync::Arc};
+use std::{fs, io::Read, path::Path, sync::Arc};
 use vulkano::{
     device::Device,
-    shader::{ShaderModule as VulkanoShaderModule, ShaderModuleCreateInfo},
+    shader::{
+        ShaderModule as VulkanoShaderModule, ShaderModuleCreateInfo, reflect,
+        spirv::{Decoration, ExecutionModel, Instruction, Spirv, StorageClass},
+    },
 };
 
 use crate::{GammaVkError, Result};
 
+/// Default maximum accepted SPIR-V size in bytes
+///
+/// Real shaders are far smaller; the cap stops corrupt or untrusted blobs from
+/// triggering huge allocations before Vulkan validates them.
+pub const DEFAULT_MAX_SPIRV_SIZE: usize = 8 * 1024 * 1024;
+
+/// The pipeline stage a shader module is written for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShaderStage {
+    /// Vertex shader
+    Vertex,
+    /// Fragment shader
+    Fragment,
+    /// Compute shader
+    Compute,
+}
+
+impl ShaderStage {
+    /// Map a SPIR-V execution model to a stage, if Gamma-VK supports it
+    fn from_execution_model(execution_model: ExecutionModel) -> Option<Self> {
+        match execution_model {
+            ExecutionModel::Vertex => Some(Self::Vertex),
+            ExecutionModel::Fragment => Some(Self::Fragment),
+            ExecutionModel::GLCompute => Some(Self::Compute),
+            _ => None,
+        }
+    }
+}
+
+/// The source language a SPIR-V module was compiled from, as recorded by `OpSource`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SourceLanguage {
+    /// OpenGL Shading Language
+    Glsl,
+    /// OpenGL ES Shading Language
+    Essl,
+    /// High-Level Shading Language; uses register spaces for descriptor bindings
+    Hlsl,
+    /// WebGPU Shading Language
+    Wgsl,
+    /// Slang
+    Slang,
+    /// OpenCL C
+    OpenClC,
+    /// Any other language, identified by its SPIR-V `SourceLanguage` value
+    Other(u32),
+}
+
+impl SourceLanguage {
+    /// Map a SPIR-V `SourceLanguage` value, treating `Unknown` (0) as absent
+    fn from_spirv(value: u32) -> Option<Self> {
+        match value {
+            0 => None,
+            1 => Some(Self::Essl),
+            2 => Some(Self::Glsl),
+            3 => Some(Self::OpenClC),
+            5 => Some(Self::Hlsl),
+            10 => Some(Self::Wgsl),
+            11 => Some(Self::Slang),
+            other => Some(Self::Other(other)),
+        }
+    }
+}
+
+/// Find the language declared by the first `OpSource` instruction, if any
+fn source_language_of(spirv_words: &[u32]) -> Option<SourceLanguage> {
+    const OP_SOURCE: u32 = 3;
+    const OP_FUNCTION: u32 = 54;
+
+    // Instructions follow the five-word header; debug info precedes all functions
+    let mut instructions = spirv_words.get(5..)?;
+    while let Some(&first_word) = instructions.first() {
+        let word_count = (first_word >> 16) as usize;
+        let opcode = first_word & 0xffff;
+        if word_count == 0 || opcode == OP_FUNCTION {
+            return None;
+        }
+        if opcode == OP_SOURCE {
+            return instructions
+                .get(1)
+                .and_then(|&language| SourceLanguage::from_spirv(language));
+        }
+        instructions = instructions.get(word_count..)?;
+    }
+    None
+}
+
+/// Count the color output locations written by a single fragment entry point
+///
+/// Built-in outputs such as `gl_FragDepth` are skipped; an output array counts
+/// one location per element.
+fn fragment_output_count_of(spirv_words: &[u32]) -> Option<u32> {
+    let spirv = Spirv::new(spirv_words).ok()?;
+    let [
+        Instruction::EntryPoint {
+            execution_model: ExecutionModel::Fragment,
+            interface,
+            ..
+        },
+    ] = spirv.entry_points()
+    else {
+        return None;
+    };
+
+    let mut count = 0;
+    for &id in interface {
+        let id_info = spirv.id(id);
+        let Instruction::Variable {
+            result_type_id,
+            storage_class: StorageClass::Output,
+            ..
+        } = *id_info.instruction()
+        else {
+            continue;
+        };
+        let is_builtin = id_info.decorations().iter().any(|decoration| {
+            matches!(
+                decoration,
+                Instruction::Decorate {
+                    decoration: Decoration::BuiltIn { .. },
+                    ..
+                }
+            )
+        });
+        if is_builtin {
+            continue;
+        }
+
+        let pointee = match *spirv.id(result_type_id).instruction() {
+            Instruction::TypePointer { ty, .. } => ty,
+            _ => continue,
+        };
+        count += match spirv.id(pointee).instruction() {
+            Instruction::TypeArray { length, .. } => match spirv.id(*length).instruction() {
+                Instruction::Constant { value, .. } => value.first().copied().unwrap_or(1),
+                _ => 1,
+            },
+            _ => 1,
+        };
+    }
+    Some(count)
+}
+
+/// 64-bit FNV-1a hash of the SPIR-V words, stable across runs and platforms
+fn content_hash_of(spirv_words: &[u32]) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    spirv_words
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+        })
+}
+
+/// Bytes of push constants the module's entry points need, measured to the end
+/// of the furthest range
+///
+/// Returns `None` when no entry point declares a push-constant block.
+pub(crate) fn push_constant_size_of(spirv_words: &[u32]) -> Option<u32> {
+    let spirv = Spirv::new(spirv_words).ok()?;
+    reflect::entry_points(&spirv)
+        .filter_map(|(_, info)| info.push_constant_requirements)
+        .map(|range| range.offset + range.size)
+        .max()
+}
+
 /// A managed shader module wrapper providing RAII resource management
 ///
 /// ShaderModule wraps a Vulkano shader module and provides automatic cleanup through
@@ -19,6 +190,18 @@ use crate::{GammaVkError, Result};
 pub struct ShaderModule {
     /// The underlying Vulkano shader module
     module: Arc<VulkanoShaderModule>,
+    /// The stage this module targets, if known
+    stage: Option<ShaderStage>,
+    /// The language the SPIR-V was compiled from, if recorded
+    source_language: Option<SourceLanguage>,
+    /// Color output locations of a fragment entry point, if this is one
+    color_output_count: Option<u32>,
+    /// Push-constant bytes the entry points need, if any declare a block
+    push_constant_size: Option<u32>,
+    /// Hash of the SPIR-V words the module was created from
+    content_hash: u64,
+    /// The SPIR-V words themselves, compared when hashes match
+    spirv_words: Arc<[u32]>,
 }
 
 impl ShaderModule {
@@ -53,6 +236,11 @@ impl ShaderModule {
     /// - The SPIR-V bytecode is invalid
     /// - Vulkan shader module creation fails
     pub fn from_spirv_file(device: &Arc<Device>, path: impl AsRef<Path>) -> Result<Self> {
+        // Reject oversized files before reading them into memory
+        if let Ok(metadata) = fs::metadata(path.as_ref()) {
+            check_spirv_size(metadata.len(), DEFAULT_MAX_SPIRV_SIZE)?;
+        }
+
         let spirv_bytes = fs::read(path.as_ref()).map_err(|e| {
             GammaVkError::shader_compilation(format!("Failed to read shader file: {}", e))
         })?;
@@ -60,6 +248,44 @@ impl ShaderModule {
         Self::from_spirv_bytes(device, &spirv_bytes)
     }
 
+    /// Create a new shader module from SPIR-V read out of any byte source
+    ///
+    /// Use this to load shaders from archives, embedded asset stores, or network
+    /// streams rather than loose files. At most [`DEFAULT_MAX_SPIRV_SIZE`] bytes
+    /// are buffered, so an unbounded reader cannot exhaust memory.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io::Cursor;
+    /// use gamma_vk::shader::ShaderModule;
+    /// use gamma_vk::context::VulkanContext;
+    ///
+    /// # fn example() -> gamma_vk::Result<()> {
+    /// let context = VulkanContext::new()?;
+    /// let bytes = std::fs::read("shaders/triangle.vert.spv").unwrap();
+    /// let shader = ShaderModule::from_spirv_reader(&context.device(), Cursor::new(bytes))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading fails, or for any reason
+    /// [`from_spirv_bytes`](Self::from_spirv_bytes) would.
+    pub fn from_spirv_reader(device: &Arc<Device>, reader: impl Read) -> Result<Self> {
+        // Read one byte past the limit so oversized input is still detected
+        let mut spirv_bytes = Vec::new();
+        reader
+            .take(DEFAULT_MAX_SPIRV_SIZE as u64 + 1)
+            .read_to_end(&mut spirv_bytes)
+            .map_err(|e| {
+                GammaVkError::shader_compilation(format!("Failed to read shader source: {}", e))
+            })?;
+
+        Self::from_spirv_bytes(device, &spirv_bytes)
+    }
+
     /// Create a new shader module from SPIR-V bytecode
     ///
     /// # Arguments
@@ -91,35 +317,61 @@ impl ShaderModule {
     /// * The SPIR-V bytecode is invalid or corrupted
     /// * The shader module creation fails on the device
     /// * The device does not support the shader features used
+    ///
+    /// See [`from_spirv_bytes_with_limit`](Self::from_spirv_bytes_with_limit) for
+    /// the checks skipped without the `checked` feature.
     pub fn from_spirv_bytes(device: &Arc<Device>, spirv_bytes: &[u8]) -> Result<Self> {
+        Self::from_spirv_bytes_with_limit(device, spirv_bytes, DEFAULT_MAX_SPIRV_SIZE)
+    }
+
+    /// Create a new shader module from SPIR-V bytecode no larger than `max_size` bytes
+    ///
+    /// Use this with a tighter limit when loading untrusted shader blobs.
+    /// [`from_spirv_bytes`](Self::from_spirv_bytes) applies [`DEFAULT_MAX_SPIRV_SIZE`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytecode exceeds `max_size`, before any allocation,
+    /// or for any reason [`from_spirv_bytes`](Self::from_spirv_bytes) would.
+    ///
+    /// # Unchecked builds
+    ///
+    /// Without the `checked` feature, release builds do not verify that the length
+    /// is a non-zero multiple of 4 or that the magic number is present; trailing
+    /// bytes are dropped and the words are handed to Vulkan as-is. Only pass
+    /// bytecode already known to be well-formed SPIR-V, such as assets validated
+    /// at build time. The `max_size` limit is always enforced.
+    pub fn from_spirv_bytes_with_limit(
+        device: &Arc<Device>,
+        spirv_bytes: &[u8],
+        max_size: usize,
+    ) -> Result<Self> {
+        check_spirv_size(spirv_bytes.len() as u64, max_size)?;
+
         // Convert bytes to u32 words for SPIR-V validation
-        if spirv_bytes.len() % 4 != 0 {
-            return Err(GammaVkError::shader_compilation(
+        ensure!(
+            spirv_bytes.len().is_multiple_of(4),
+            GammaVkError::shader_compilation(
                 "SPIR-V bytecode length must be a multiple of 4 bytes",
-            ));
-        }
+            )
+        );
 
         // Validate SPIR-V magic number
-        if spirv_bytes.len() < 4 {
-            return Err(GammaVkError::shader_compilation(
-                "SPIR-V bytecode too short - missing magic number",
-            ));
-        }
-
-        let magic_bytes = &spirv_bytes[0..4];
-        let magic = u32::from_le_bytes([
-            magic_bytes[0],
-            magic_bytes[1],
-            magic_bytes[2],
-            magic_bytes[3],
-        ]);
+        ensure!(
+            spirv_bytes.len() >= 4,
+            GammaVkError::shader_compilation("SPIR-V bytecode too short - missing magic number")
+        );
 
-        if magic != 0x07230203 {
-            return Err(GammaVkError::shader_compilation(format!(
+        let magic = spirv_bytes
+            .first_chunk::<4>()
+            .map_or(0, |bytes| u32::from_le_bytes(*bytes));
+        ensure!(
+            magic == 0x07230203,
+            GammaVkError::shader_compilation(format!(
                 "Invalid SPIR-V magic number: expected 0x07230203, got 0x{:08x}",
                 magic
-            )));
-        }
+            ))
+        );
 
         // Convert to u32 words
         let spirv_words: Vec<u32> = spirv_bytes
@@ -128,14 +380,80 @@ impl ShaderModule {
             .collect();
 
         // Create the shader module
-        // Safety: We've validated the SPIR-V magic number and word alignment above
+        // Safety: We've validated the SPIR-V magic number and word alignment above,
+        // or, without the `checked` feature, the caller guarantees both
         let create_info = ShaderModuleCreateInfo::new(&spirv_words);
         let module =
             unsafe { VulkanoShaderModule::new(device.clone(), create_info) }.map_err(|e| {
                 GammaVkError::shader_compilation(format!("Failed to create shader module: {}", e))
             })?;
 
-        Ok(Self { module })
+        // Infer the stage when the module has a single entry point
+        let stage = module.single_entry_point().and_then(|entry_point| {
+            ShaderStage::from_execution_model(entry_point.info().execution_model)
+        });
+
+        Ok(Self {
+            module,
+            stage,
+            source_language: source_language_of(&spirv_words),
+            color_output_count: fragment_output_count_of(&spirv_words),
+            push_constant_size: push_constant_size_of(&spirv_words),
+            content_hash: content_hash_of(&spirv_words),
+            spirv_words: spirv_words.into(),
+        })
+    }
+
+    /// Tag this module with an explicit stage, overriding any inferred stage
+    pub fn with_stage(mut self, stage: ShaderStage) -> Self {
+        self.stage = Some(stage);
+        self
+    }
+
+    /// Get the stage this module targets
+    ///
+    /// The stage is inferred from the SPIR-V execution model when the module has a
+    /// single entry point, or set explicitly with [`with_stage`](Self::with_stage).
+    pub fn stage(&self) -> Option<ShaderStage> {
+        self.stage
+    }
+
+    /// Get the language the SPIR-V was compiled from
+    ///
+    /// Read from the module's `OpSource` instruction. This is informational, for
+    /// example to warn about HLSL register-space binding conventions. Returns `None`
+    /// when the compiler emitted no `OpSource` or declared the language unknown.
+    pub fn source_language(&self) -> Option<SourceLanguage> {
+        self.source_language
+    }
+
+    /// Get the number of color outputs written by the fragment entry point
+    ///
+    /// Counts the module's non-built-in `Output` locations, which must match the
+    /// color attachment count of the subpass or rendering the pipeline targets.
+    /// Returns `None` unless the module has a single fragment entry point.
+    pub fn color_output_count(&self) -> Option<u32> {
+        self.color_output_count
+    }
+
+    /// Get the number of push-constant bytes the module needs
+    ///
+    /// Reflected from the push-constant blocks of the module's entry points, as the
+    /// offset plus size of the furthest range. Pipeline creation checks this against
+    /// the device's `maxPushConstantsSize`. Returns `None` when the module declares
+    /// no push constants.
+    pub fn push_constant_size(&self) -> Option<u32> {
+        self.push_constant_size
+    }
+
+    /// Get a 64-bit hash of the SPIR-V the module was created from
+    ///
+    /// Identical bytecode hashes identically regardless of where it was loaded
+    /// from, so this can key deduplication of shaders and the pipelines built from
+    /// them. Different bytecode can collide, so treat a match as a hint; `==` on
+    /// modules compares the SPIR-V itself and never reports a false match.
+    pub fn content_hash(&self) -> u64 {
+        self.content_hash
     }
 
     /// Get a reference to the underlying Vulkano shader module
@@ -148,25 +466,74 @@ impl ShaderModule {
     }
 }
 
+/// Reject SPIR-V larger than `max_size` bytes
+fn check_spirv_size(size: u64, max_size: usize) -> Result<()> {
+    if size > max_size as u64 {
+        return Err(GammaVkError::shader_compilation(format!(
+            "SPIR-V too large: {} bytes exceeds limit {}",
+            size, max_size
+        )));
+    }
+    Ok(())
+}
+
 impl std::fmt::Debug for ShaderModule {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ShaderModule")
             .field("module", &"VulkanoShaderModule")
+            .field("stage", &self.stage)
+            .field("source_language", &self.source_language)
+            .field("color_output_count", &self.color_output_count)
+            .field("push_constant_size", &self.push_constant_size)
+            .field("content_hash", &self.content_hash)
             .finish()
     }
 }
 
+/// Modules are equal when their SPIR-V is identical
+///
+/// The [content hashes](ShaderModule::content_hash) are compared first, and the
+/// words only when those match. The stage tag and the Vulkan handle are ignored,
+/// so the same SPIR-V loaded twice compares equal.
+impl PartialEq for ShaderModule {
+    fn eq(&self, other: &Self) -> bool {
+        self.content_hash == other.content_hash && self.spirv_words == other.spirv_words
+    }
+}
+
+impl Eq for ShaderModule {}
+
+impl std::hash::Hash for ShaderModule {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.content_hash.hash(state);
+    }
+}
+
 /// Convenience functions for loading common shaders
 pub mod common {
     use super::*;
 
+    /// SPIR-V for the basic triangle vertex shader, embedded at compile time
+    pub const TRIANGLE_VERTEX_SPIRV: &[u8] = include_bytes!("../shaders/triangle.vert.spv");
+
+    /// SPIR-V for the basic triangle fragment shader, embedded at compile time
+    pub const TRIANGLE_FRAGMENT_SPIRV: &[u8] = include_bytes!("../shaders/triangle.frag.spv");
+
     /// Load the basic triangle vertex shader
+    ///
+    /// The SPIR-V is embedded in the binary, so this works regardless of the
+    /// current working directory.
     pub fn load_triangle_vertex(device: &Arc<Device>) -> Result<ShaderModule> {
-        ShaderModule::from_spirv_file(device, "shaders/triangle.vert.spv")
+        ShaderModule::from_spirv_bytes(device, TRIANGLE_VERTEX_SPIRV)
+            .map(|shader| shader.with_stage(ShaderStage::Vertex))
     }
 
     /// Load the basic triangle fragment shader
+    ///
+    /// The SPIR-V is embedded in the binary, so this works regardless of the
+    /// current working directory.
     pub fn load_triangle_fragment(device: &Arc<Device>) -> Result<ShaderModule> {
-        ShaderModule::from_spirv_file(device, "shaders/triangle.frag.spv")
+        ShaderModule::from_spirv_bytes(device, TRIANGLE_FRAGMENT_SPIRV)
+            .map(|shader| shader.with_stage(ShaderStage::Fragment))
     }
 }
@@ -0,0 +1,782 @@
+//! Tests for command recording and submission
+//!
+//! These tests require a real Vulkan device and skip when one is unavailable.
+
+use gamma_vk::{
+    AccessPattern, Buffer, CommandRecorder, DrawCommand, DrawDescriptor, GammaVkError, Image,
+    IndexBuffer, QueryPool, ShaderModule, VertexBuffer, VulkanContext, pipeline,
+    render::{Framebuffer, RenderPass},
+};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use vulkano::{
+    buffer::{BufferUsage, IndexType},
+    command_buffer::{
+        BufferCopy, RenderPassBeginInfo, SubpassBeginInfo, SubpassContents, SubpassEndInfo,
+    },
+    descriptor_set::{DescriptorSet, WriteDescriptorSet},
+    device::QueueFlags,
+    format::Format,
+    pipeline::{
+        ComputePipeline, GraphicsPipeline, Pipeline, PipelineBindPoint, PipelineLayout,
+        PipelineShaderStageCreateInfo, compute::ComputePipelineCreateInfo,
+        graphics::viewport::Viewport, layout::PipelineDescriptorSetLayoutCreateInfo,
+    },
+};
+
+// Helper function to skip tests when Vulkan is not available (e.g., in CI)
+fn skip_if_no_vulkan() -> Option<VulkanContext> {
+    match VulkanContext::new() {
+        Ok(ctx) => Some(ctx),
+        Err(GammaVkError::LibraryLoad(_)) => {
+            eprintln!("Skipping test: Vulkan not available (expected in CI)");
+            None
+        }
+        Err(e) => panic!("Unexpected error creating VulkanContext: {}", e),
+    }
+}
+
+fn create_copy_pair(context: &VulkanContext, size: u64) -> (Buffer, Buffer) {
+    let allocator = context.memory_allocator();
+    let src = Buffer::new_host_visible(
+        &context.device(),
+        &allocator,
+        size,
+        BufferUsage::TRANSFER_SRC,
+    )
+    .expect("Failed to create source buffer");
+    let dst = Buffer::new_host_visible(
+        &context.device(),
+        &allocator,
+        size,
+        BufferUsage::TRANSFER_DST,
+    )
+    .expect("Failed to create destination buffer");
+    (src, dst)
+}
+
+#[test]
+fn test_copy_buffer_submit_and_wait() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let (src, dst) = create_copy_pair(&context, 64);
+    src.write_data(&[9u8; 64]).expect("Failed to write source");
+
+    let mut recorder = CommandRecorder::new(&context).expect("Failed to create recorder");
+    recorder
+        .copy_buffer(&src, &dst)
+        .expect("Failed to record copy");
+    recorder
+        .submit_and_wait(&context.graphics_queue())
+        .expect("Submission should succeed");
+
+    let contents = dst.inner().read().expect("Failed to read destination");
+    assert_eq!(&contents[..], &[9u8; 64]);
+}
+
+#[test]
+fn test_submit_and_wait_timeout_succeeds_for_trivial_copy() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    // Exercising the timeout path needs an artificial GPU stall, which is not
+    // feasible here; this verifies a generous timeout does not fire spuriously.
+    let (src, dst) = create_copy_pair(&context, 64);
+
+    let mut recorder = CommandRecorder::new(&context).expect("Failed to create recorder");
+    recorder
+        .copy_buffer(&src, &dst)
+        .expect("Failed to record copy");
+    recorder
+        .submit_and_wait_timeout(&context.graphics_queue(), Duration::from_secs(5))
+        .expect("Trivial copy should complete within the timeout");
+}
+
+#[test]
+fn test_copy_buffer_without_transfer_usage_fails() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let allocator = context.memory_allocator();
+    let src = Buffer::new_host_visible(
+        &context.device(),
+        &allocator,
+        64,
+        BufferUsage::VERTEX_BUFFER,
+    )
+    .expect("Failed to create buffer");
+    let dst =
+        Buffer::new_host_visible(&context.device(), &allocator, 64, BufferUsage::TRANSFER_DST)
+            .expect("Failed to create buffer");
+
+    let mut recorder = CommandRecorder::new(&context).expect("Failed to create recorder");
+    match recorder.copy_buffer(&src, &dst) {
+        Err(GammaVkError::CommandExecution { message }) => {
+            assert!(message.contains("buffer copy"), "got: {}", message);
+        }
+        other => panic!("Expected CommandExecution error, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn test_copy_buffer_regions_scatters_disjoint_ranges() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let (src, dst) = create_copy_pair(&context, 64);
+    let source: Vec<u8> = (0..64).collect();
+    src.write_data(&source).expect("Failed to write source");
+    dst.write_data(&[0xEE; 64])
+        .expect("Failed to write destination");
+
+    let regions = [
+        BufferCopy {
+            src_offset: 0,
+            dst_offset: 0,
+            size: 8,
+            ..Default::default()
+        },
+        BufferCopy {
+            src_offset: 32,
+            dst_offset: 48,
+            size: 8,
+            ..Default::default()
+        },
+    ];
+    let mut recorder = CommandRecorder::new(&context).expect("Failed to create recorder");
+    recorder
+        .copy_buffer_regions(&src, &dst, &regions)
+        .expect("Failed to record region copy");
+    recorder
+        .submit_and_wait(&context.graphics_queue())
+        .expect("Submission should succeed");
+
+    let contents = dst.inner().read().expect("Failed to read destination");
+    assert_eq!(&contents[0..8], &source[0..8]);
+    assert_eq!(&contents[48..56], &source[32..40]);
+    assert!(contents[8..48].iter().all(|&byte| byte == 0xEE));
+    assert!(contents[56..].iter().all(|&byte| byte == 0xEE));
+}
+
+#[test]
+fn test_copy_buffer_regions_rejects_out_of_range_region() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let (src, dst) = create_copy_pair(&context, 64);
+    let mut recorder = CommandRecorder::new(&context).expect("Failed to create recorder");
+
+    let past_end = BufferCopy {
+        src_offset: 60,
+        dst_offset: 0,
+        size: 8,
+        ..Default::default()
+    };
+    assert!(matches!(
+        recorder.copy_buffer_regions(&src, &dst, &[past_end]),
+        Err(GammaVkError::CommandExecution { .. })
+    ));
+    assert!(recorder.copy_buffer_regions(&src, &dst, &[]).is_err());
+}
+
+#[test]
+fn test_bind_vertex_and_index_buffers() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let allocator = context.memory_allocator();
+    let vertex_buffer = VertexBuffer::new_host_visible(&context.device(), &allocator, 256)
+        .expect("Failed to create vertex buffer");
+    let index_buffer = IndexBuffer::new_host_visible(&context.device(), &allocator, 12)
+        .expect("Failed to create index buffer");
+
+    let mut recorder = CommandRecorder::new(&context).expect("Failed to create recorder");
+    vertex_buffer
+        .bind(&mut recorder, 0)
+        .expect("Failed to bind vertex buffer");
+    index_buffer
+        .bind(&mut recorder, IndexType::U16)
+        .expect("Failed to bind index buffer");
+}
+
+#[test]
+fn test_bind_index_buffer_with_misaligned_size_fails() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let index_buffer =
+        IndexBuffer::new_host_visible(&context.device(), &context.memory_allocator(), 6)
+            .expect("Failed to create index buffer");
+
+    let mut recorder = CommandRecorder::new(&context).expect("Failed to create recorder");
+    let result = index_buffer.bind(&mut recorder, IndexType::U32);
+    assert!(
+        matches!(result, Err(GammaVkError::CommandExecution { .. })),
+        "6 bytes cannot hold whole 32-bit indices"
+    );
+}
+
+#[test]
+fn test_draw_descriptor_selects_draw_call() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let allocator = context.memory_allocator();
+    let vertex_buffer = VertexBuffer::new_host_visible(&context.device(), &allocator, 96)
+        .expect("Failed to create vertex buffer");
+    let index_buffer = IndexBuffer::new_host_visible(&context.device(), &allocator, 12)
+        .expect("Failed to create index buffer");
+
+    let non_indexed = DrawDescriptor::new(&vertex_buffer, 32).expect("Invalid vertex stride");
+    assert!(!non_indexed.is_indexed());
+    assert_eq!(non_indexed.command(), DrawCommand::Draw { vertex_count: 3 });
+
+    let indexed = DrawDescriptor::indexed(&vertex_buffer, 32, &index_buffer, IndexType::U16)
+        .expect("Invalid index buffer size");
+    assert!(indexed.is_indexed());
+    assert_eq!(
+        indexed.command(),
+        DrawCommand::DrawIndexed {
+            index_count: 6,
+            index_type: IndexType::U16,
+        }
+    );
+
+    // A stride that leaves a partial vertex is rejected
+    assert!(DrawDescriptor::new(&vertex_buffer, 40).is_err());
+
+    index_buffer
+        .buffer()
+        .write_data(bytemuck::cast_slice(&[0u16, 1, 2, 2, 1, 0]))
+        .expect("Failed to write indices");
+
+    // Both descriptors record their draw call inside a render pass
+    let target = RenderTarget::new(&context);
+    let mut recorder = CommandRecorder::new(&context).expect("Failed to create recorder");
+    target.begin(&mut recorder);
+    recorder
+        .draw_mesh(&target.pipeline, None, &non_indexed)
+        .expect("Failed to record non-indexed draw");
+    recorder
+        .draw_mesh(&target.pipeline, None, &indexed)
+        .expect("Failed to record indexed draw");
+    target.end(&mut recorder);
+    recorder
+        .submit_and_wait(&context.graphics_queue())
+        .expect("Failed to submit draws");
+}
+
+/// A single-color render pass with a pipeline that ignores vertex data
+struct RenderTarget {
+    _image: Image,
+    framebuffer: Framebuffer,
+    pipeline: Arc<GraphicsPipeline>,
+}
+
+impl RenderTarget {
+    const EXTENT: [u32; 2] = [64, 32];
+
+    fn new(context: &VulkanContext) -> Self {
+        let render_pass = RenderPass::new(context, [Format::R8G8B8A8_UNORM], None)
+            .expect("Failed to create render pass");
+        let image = Image::new_render_target(context, Format::R8G8B8A8_UNORM, Self::EXTENT)
+            .expect("Failed to create render target");
+        let framebuffer = Framebuffer::new(&render_pass, &[image.view().clone()])
+            .expect("Failed to create framebuffer");
+        let fragment_shader = gamma_vk::shader::common::load_triangle_fragment(&context.device())
+            .expect("Failed to load embedded fragment shader");
+        let pipeline = pipeline::fullscreen(context, &fragment_shader, render_pass.inner())
+            .expect("Failed to build pipeline");
+        Self {
+            _image: image,
+            framebuffer,
+            pipeline,
+        }
+    }
+
+    fn begin(&self, recorder: &mut CommandRecorder) {
+        recorder
+            .builder_mut()
+            .begin_render_pass(
+                RenderPassBeginInfo {
+                    clear_values: vec![Some([0.0, 0.0, 0.0, 1.0].into())],
+                    ..RenderPassBeginInfo::framebuffer(self.framebuffer.inner().clone())
+                },
+                SubpassBeginInfo {
+                    contents: SubpassContents::Inline,
+                    ..Default::default()
+                },
+            )
+            .expect("Failed to begin render pass")
+            .set_viewport(
+                0,
+                [Viewport {
+                    offset: [0.0, 0.0],
+                    extent: [Self::EXTENT[0] as f32, Self::EXTENT[1] as f32],
+                    depth_range: 0.0..=1.0,
+                }]
+                .into_iter()
+                .collect(),
+            )
+            .expect("Failed to set viewport");
+    }
+
+    fn end(&self, recorder: &mut CommandRecorder) {
+        recorder
+            .builder_mut()
+            .end_render_pass(SubpassEndInfo::default())
+            .expect("Failed to end render pass");
+    }
+}
+
+#[test]
+fn test_time_scope_measures_copy() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let mut queries = QueryPool::timestamps(&context, 4).expect("Failed to create query pool");
+    if !queries.is_supported() {
+        eprintln!("Skipping test: graphics queue does not support timestamps");
+        return;
+    }
+
+    let (src, dst) = create_copy_pair(&context, 1024);
+    let mut recorder = CommandRecorder::new(&context).expect("Failed to create recorder");
+    let scope = recorder
+        .time_scope(&mut queries, "copy", |rec| {
+            rec.copy_buffer(&src, &dst).map(|_| ())
+        })
+        .expect("Failed to record timed scope");
+    assert_eq!(scope, Some(0));
+
+    let submitted = Instant::now();
+    recorder
+        .submit_and_wait(&context.graphics_queue())
+        .expect("Failed to submit timed copy");
+    let wall_clock = submitted.elapsed();
+
+    let timings = context
+        .resolve_timestamps(&queries)
+        .expect("Failed to resolve timestamps");
+    assert_eq!(timings.len(), 1);
+    assert_eq!(timings[0].0, "copy");
+    // The GPU cannot spend longer on the copy than the whole submission took;
+    // a bad tick conversion or swapped timestamps would blow past this bound
+    assert!(
+        timings[0].1 <= wall_clock,
+        "GPU time {:?} exceeds wall-clock submission time {:?}",
+        timings[0].1,
+        wall_clock
+    );
+}
+
+#[test]
+fn test_query_pool_rejects_query_count_overflow() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    // Two queries per scope would need more than u32::MAX queries
+    let result = QueryPool::timestamps(&context, u32::MAX / 2 + 1);
+    assert!(matches!(
+        result,
+        Err(GammaVkError::CommandExecution { message }) if message.contains("u32::MAX")
+    ));
+}
+
+#[test]
+fn test_debug_label_records_enclosed_commands() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let (src, dst) = create_copy_pair(&context, 64);
+    src.write_data(&[5u8; 64]).expect("Failed to write source");
+
+    // The default context does not enable ext_debug_utils, so labels are no-ops
+    let mut recorder = CommandRecorder::new(&context).expect("Failed to create recorder");
+    recorder
+        .debug_label("upload", |rec| {
+            rec.debug_label("copy", |rec| {
+                assert_eq!(rec.debug_label_depth(), 0);
+                rec.copy_buffer(&src, &dst).map(|_| ())
+            })
+            .map(|_| ())
+        })
+        .expect("Failed to record labeled region");
+    recorder
+        .submit_and_wait(&context.graphics_queue())
+        .expect("Failed to submit labeled copy");
+
+    let copied: [u8; 64] = dst.read_struct().expect("Failed to read destination");
+    assert_eq!(copied, [5u8; 64]);
+}
+
+#[cfg(feature = "windowing")]
+#[test]
+fn test_debug_label_regions_nest_and_close_on_error() {
+    use vulkano::{VulkanLibrary, instance::InstanceExtensions, swapchain::Surface};
+
+    let Ok(library) = VulkanLibrary::new() else {
+        eprintln!("Skipping test: Vulkan not available (expected in CI)");
+        return;
+    };
+    let supported = library.supported_extensions();
+    if !(supported.ext_debug_utils && supported.khr_surface && supported.ext_headless_surface) {
+        eprintln!("Skipping test: ext_debug_utils or headless surfaces unsupported");
+        return;
+    }
+
+    // A headless surface lets build_with_surface enable ext_debug_utils as well
+    let extensions = InstanceExtensions {
+        ext_debug_utils: true,
+        khr_surface: true,
+        ext_headless_surface: true,
+        ..InstanceExtensions::empty()
+    };
+    let context = match VulkanContext::builder().build_with_surface(extensions, |instance| {
+        Surface::headless(instance, None).map_err(|e| {
+            GammaVkError::initialization(format!("Failed to create headless surface: {}", e))
+        })
+    }) {
+        Ok(context) => context,
+        Err(GammaVkError::Initialization { message })
+            if message.starts_with("No physical device") =>
+        {
+            eprintln!("Skipping test: {}", message);
+            return;
+        }
+        Err(e) => panic!("Unexpected error creating VulkanContext: {}", e),
+    };
+    assert!(context.enabled_extensions().ext_debug_utils);
+
+    let (src, dst) = create_copy_pair(&context, 64);
+    src.write_data(&[9u8; 64]).expect("Failed to write source");
+
+    let mut recorder = CommandRecorder::new(&context).expect("Failed to create recorder");
+    recorder
+        .debug_label("upload", |rec| {
+            assert_eq!(rec.debug_label_depth(), 1);
+            rec.debug_label("copy", |rec| {
+                assert_eq!(rec.debug_label_depth(), 2);
+                rec.copy_buffer(&src, &dst).map(|_| ())
+            })
+            .map(|_| ())
+        })
+        .expect("Failed to record nested labels");
+    assert_eq!(recorder.debug_label_depth(), 0);
+
+    // A failing region still closes before the error is returned
+    let result = recorder.debug_label("failing", |_| {
+        Err(GammaVkError::command_execution("recording failed"))
+    });
+    assert!(matches!(
+        result,
+        Err(GammaVkError::CommandExecution { message }) if message == "recording failed"
+    ));
+    assert_eq!(recorder.debug_label_depth(), 0);
+
+    recorder
+        .submit_and_wait(&context.graphics_queue())
+        .expect("Failed to submit labeled copy");
+    let copied: [u8; 64] = dst.read_struct().expect("Failed to read destination");
+    assert_eq!(copied, [9u8; 64]);
+}
+
+/// Build the pipeline for `shaders/double.comp`, or `None` if the graphics queue
+/// cannot run compute work
+fn doubling_pipeline(context: &VulkanContext) -> Option<Arc<ComputePipeline>> {
+    let queue_family = context.graphics_queue_family_index() as usize;
+    if !context.physical_device().queue_family_properties()[queue_family]
+        .queue_flags
+        .intersects(QueueFlags::COMPUTE)
+    {
+        eprintln!("Skipping test: graphics queue does not support compute");
+        return None;
+    }
+
+    let device = context.device();
+    let shader =
+        ShaderModule::from_spirv_bytes(&device, include_bytes!("../shaders/double.comp.spv"))
+            .expect("Failed to load compute shader");
+    let stage = PipelineShaderStageCreateInfo::new(
+        shader
+            .vulkano_module()
+            .entry_point("main")
+            .expect("Compute shader has no main entry point"),
+    );
+    let layout = PipelineLayout::new(
+        device.clone(),
+        PipelineDescriptorSetLayoutCreateInfo::from_stages([&stage])
+            .into_pipeline_layout_create_info(device.clone())
+            .expect("Failed to derive pipeline layout"),
+    )
+    .expect("Failed to create pipeline layout");
+    let pipeline = ComputePipeline::new(
+        device,
+        None,
+        ComputePipelineCreateInfo::stage_layout(stage, layout),
+    )
+    .expect("Failed to create compute pipeline");
+    Some(pipeline)
+}
+
+#[test]
+fn test_compute_passes_are_synchronized_by_the_builder() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+    let Some(pipeline) = doubling_pipeline(&context) else {
+        return;
+    };
+    let device = context.device();
+
+    let data = Buffer::new_host_visible_with_access(
+        &device,
+        &context.memory_allocator(),
+        64 * 4,
+        BufferUsage::STORAGE_BUFFER,
+        AccessPattern::RandomAccess,
+    )
+    .expect("Failed to create storage buffer");
+    let initial: [u32; 64] = std::array::from_fn(|i| i as u32);
+    data.write_struct(&initial)
+        .expect("Failed to write initial data");
+
+    let descriptor_set = DescriptorSet::new(
+        context.descriptor_set_allocator(),
+        pipeline.layout().set_layouts()[0].clone(),
+        [WriteDescriptorSet::buffer(0, data.inner().clone())],
+        [],
+    )
+    .expect("Failed to create descriptor set");
+
+    let mut recorder = CommandRecorder::new(&context).expect("Failed to create recorder");
+    recorder
+        .builder_mut()
+        .bind_pipeline_compute(pipeline.clone())
+        .expect("Failed to bind compute pipeline")
+        .bind_descriptor_sets(
+            PipelineBindPoint::Compute,
+            pipeline.layout().clone(),
+            0,
+            descriptor_set,
+        )
+        .expect("Failed to bind descriptor set");
+
+    // Safety: the shader bounds-checks every access against the buffer length
+    unsafe { recorder.builder_mut().dispatch([1, 1, 1]) }.expect("Failed to record first pass");
+    // No explicit barrier: the builder orders the second pass after the first's writes
+    // Safety: as above
+    unsafe { recorder.builder_mut().dispatch([1, 1, 1]) }.expect("Failed to record second pass");
+
+    recorder
+        .submit_and_wait(&context.graphics_queue())
+        .expect("Failed to submit compute passes");
+
+    // The second pass doubled the first pass's output
+    let result: [u32; 64] = data.read_struct().expect("Failed to read back data");
+    let expected: [u32; 64] = std::array::from_fn(|i| i as u32 * 4);
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn test_dispatch_indirect_reads_workgroup_counts_from_buffer() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+    let Some(pipeline) = doubling_pipeline(&context) else {
+        return;
+    };
+
+    let allocator = context.memory_allocator();
+    let data = Buffer::new_host_visible_with_access(
+        &context.device(),
+        &allocator,
+        64 * 4,
+        BufferUsage::STORAGE_BUFFER,
+        AccessPattern::RandomAccess,
+    )
+    .expect("Failed to create storage buffer");
+    let initial: [u32; 64] = std::array::from_fn(|i| i as u32);
+    data.write_struct(&initial)
+        .expect("Failed to write initial data");
+
+    // The command sits after a leading word to exercise a non-zero offset
+    let indirect = Buffer::new_host_visible(
+        &context.device(),
+        &allocator,
+        16,
+        BufferUsage::INDIRECT_BUFFER,
+    )
+    .expect("Failed to create indirect buffer");
+    indirect
+        .write_struct(&[0u32, 1, 1, 1])
+        .expect("Failed to write dispatch command");
+
+    let descriptor_set = DescriptorSet::new(
+        context.descriptor_set_allocator(),
+        pipeline.layout().set_layouts()[0].clone(),
+        [WriteDescriptorSet::buffer(0, data.inner().clone())],
+        [],
+    )
+    .expect("Failed to create descriptor set");
+
+    let mut recorder = CommandRecorder::new(&context).expect("Failed to create recorder");
+    recorder
+        .dispatch_indirect(&pipeline, Some(descriptor_set), &indirect, 4)
+        .expect("Failed to record indirect dispatch");
+    recorder
+        .submit_and_wait(&context.graphics_queue())
+        .expect("Failed to submit indirect dispatch");
+
+    let result: [u32; 64] = data.read_struct().expect("Failed to read back data");
+    let expected: [u32; 64] = std::array::from_fn(|i| i as u32 * 2);
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn test_dispatch_indirect_validates_buffer() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+    let Some(pipeline) = doubling_pipeline(&context) else {
+        return;
+    };
+
+    let allocator = context.memory_allocator();
+    let new_buffer = |usage| {
+        Buffer::new_host_visible(&context.device(), &allocator, 16, usage)
+            .expect("Failed to create buffer")
+    };
+    let mut recorder = CommandRecorder::new(&context).expect("Failed to create recorder");
+
+    let storage = new_buffer(BufferUsage::STORAGE_BUFFER);
+    let error = recorder
+        .dispatch_indirect(&pipeline, None, &storage, 0)
+        .unwrap_err();
+    assert!(error.to_string().contains("INDIRECT_BUFFER"));
+
+    let indirect = new_buffer(BufferUsage::INDIRECT_BUFFER);
+    for offset in [2, 8, u64::MAX - 3] {
+        assert!(matches!(
+            recorder.dispatch_indirect(&pipeline, None, &indirect, offset),
+            Err(GammaVkError::CommandExecution { .. })
+        ));
+    }
+}
+
+#[test]
+fn test_reusable_fill_executes_twice() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let buffer = Buffer::new_host_visible_with_access(
+        &context.device(),
+        &context.memory_allocator(),
+        256,
+        BufferUsage::TRANSFER_DST,
+        AccessPattern::RandomAccess,
+    )
+    .expect("Failed to create buffer");
+
+    let mut recorder =
+        CommandRecorder::new_reusable(&context).expect("Failed to create reusable recorder");
+    recorder
+        .fill_buffer(&buffer, 0xDEAD_BEEF)
+        .expect("Failed to record fill");
+    let fill = recorder
+        .build_reusable()
+        .expect("Failed to build reusable command buffer");
+
+    for _ in 0..2 {
+        buffer
+            .write_struct(&[0u32; 64])
+            .expect("Failed to reset buffer");
+        fill.execute(&context.graphics_queue())
+            .expect("Failed to execute reusable fill");
+
+        let contents: [u32; 64] = buffer.read_struct().expect("Failed to read buffer");
+        assert!(contents.iter().all(|&word| word == 0xDEAD_BEEF));
+    }
+}
+
+#[test]
+fn test_submit_batch_runs_every_command_buffer() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let allocator = context.memory_allocator();
+    let targets: Vec<Buffer> = (0..2)
+        .map(|_| {
+            Buffer::new_host_visible_with_access(
+                &context.device(),
+                &allocator,
+                256,
+                BufferUsage::TRANSFER_DST,
+                AccessPattern::RandomAccess,
+            )
+            .expect("Failed to create buffer")
+        })
+        .collect();
+
+    let mut batch = Vec::new();
+    for (buffer, value) in targets.iter().zip([0x1111_1111u32, 0x2222_2222]) {
+        let mut recorder = CommandRecorder::new(&context).expect("Failed to create recorder");
+        recorder
+            .fill_buffer(buffer, value)
+            .expect("Failed to record fill");
+        batch.push(recorder.build().expect("Failed to build command buffer"));
+    }
+
+    context
+        .submit_batch(&context.graphics_queue(), batch, None)
+        .expect("Failed to submit batch")
+        .wait(None)
+        .expect("Batch did not complete");
+
+    for (buffer, value) in targets.iter().zip([0x1111_1111u32, 0x2222_2222]) {
+        let contents: [u32; 64] = buffer.read_struct().expect("Failed to read buffer");
+        assert!(contents.iter().all(|&word| word == value));
+    }
+}
+
+#[test]
+fn test_submit_empty_batch_signals_immediately() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    context
+        .submit_batch(&context.graphics_queue(), Vec::new(), None)
+        .expect("Empty batch should submit")
+        .wait(Some(Duration::from_secs(5)))
+        .expect("Empty batch should signal");
+}
+
+#[test]
+fn test_build_reusable_requires_reusable_recorder() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let recorder = CommandRecorder::new(&context).expect("Failed to create recorder");
+    assert!(matches!(
+        recorder.build_reusable(),
+        Err(GammaVkError::CommandExecution { .. })
+    ));
+}
@@ -3,10 +3,14 @@
 //! These tests follow TDD principles and define expected behavior.
 //! All tests should fail if functionality is not available.
 
-use gamma_vk::{GammaVkError, VulkanContext};
+use gamma_vk::{
+    GammaVkError, VulkanContext,
+    context::{DiagnosticStage, PortabilityMode},
+};
 use std::sync::Arc;
 use vulkano::Version;
 use vulkano::device::DeviceOwned;
+use vulkano::device::physical::PhysicalDeviceType;
 
 // Helper function to skip tests when Vulkan is not available (e.g., in CI)
 fn skip_if_no_vulkan() -> Option<VulkanContext> {
@@ -20,6 +24,14 @@ fn skip_if_no_vulkan() -> Option<VulkanContext> {
     }
 }
 
+// Whether an error means no physical device meets the context's requirements
+fn is_missing_device(error: &GammaVkError) -> bool {
+    matches!(
+        error,
+        GammaVkError::Initialization { message } if message.starts_with("No physical device")
+    )
+}
+
 #[test]
 fn context_creation_succeeds() {
     let Some(context) = skip_if_no_vulkan() else {
@@ -307,11 +319,335 @@ fn context_provides_memory_allocator() {
 
     // Allocator should be associated with the same device
     assert!(
-        Arc::ptr_eq(&allocator1.device(), &context.device()),
+        Arc::ptr_eq(allocator1.device(), &context.device()),
         "Allocator should use context's device"
     );
 }
 
+#[test]
+fn context_creates_distinct_allocators_on_same_device() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let shared = context.memory_allocator();
+    let created = context.create_allocator();
+
+    assert!(
+        !Arc::ptr_eq(&shared, &created),
+        "Created allocator should be distinct from the shared allocator"
+    );
+    assert!(
+        Arc::ptr_eq(created.device(), &context.device()),
+        "Created allocator should use context's device"
+    );
+}
+
+#[test]
+fn context_reports_device_info() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    assert!(
+        !context.device_name().is_empty(),
+        "Device name should not be empty"
+    );
+    assert!(matches!(
+        context.device_type(),
+        PhysicalDeviceType::DiscreteGpu
+            | PhysicalDeviceType::IntegratedGpu
+            | PhysicalDeviceType::VirtualGpu
+            | PhysicalDeviceType::Cpu
+            | PhysicalDeviceType::Other
+    ));
+    assert_eq!(
+        context.vendor_id(),
+        context.physical_device().properties().vendor_id
+    );
+}
+
+#[test]
+fn context_force_device_uuid_selects_matching_device() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let first_device = context
+        .instance
+        .enumerate_physical_devices()
+        .expect("Failed to enumerate physical devices")
+        .next()
+        .expect("No physical devices");
+    let Some(uuid) = first_device.properties().device_uuid else {
+        eprintln!("Skipping test: device does not report a UUID");
+        return;
+    };
+
+    let forced = VulkanContext::builder()
+        .force_device_uuid(uuid)
+        .build()
+        .expect("Forcing an existing device UUID should succeed");
+    assert_eq!(
+        forced.physical_device().properties().device_uuid,
+        Some(uuid)
+    );
+
+    match VulkanContext::builder()
+        .force_device_uuid([0xAB; 16])
+        .build()
+    {
+        Err(GammaVkError::Initialization { message }) => {
+            assert!(message.contains("forced UUID"), "got: {}", message);
+        }
+        other => panic!("Expected initialization error, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn context_feature_helpers_match_supported_features() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let features = *context.physical_device().supported_features();
+    assert_eq!(context.supports_anisotropy(), features.sampler_anisotropy);
+    assert_eq!(context.supports_geometry_shader(), features.geometry_shader);
+    assert_eq!(context.supports_wide_lines(), features.wide_lines);
+    assert_eq!(
+        context.supports_fill_mode_non_solid(),
+        features.fill_mode_non_solid
+    );
+}
+
+#[test]
+fn max_usable_sample_count_is_supported_power_of_two() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let max = context.max_usable_sample_count() as u32;
+    assert!(
+        max >= 1 && max.is_power_of_two(),
+        "Sample count {} should be a power of two",
+        max
+    );
+
+    let supported = context.supported_sample_counts();
+    assert!(supported.contains_enum(context.max_usable_sample_count()));
+    assert!(supported.contains_enum(vulkano::image::SampleCount::Sample1));
+
+    let physical_device = context.physical_device();
+    let properties = physical_device.properties();
+    assert!(
+        properties
+            .framebuffer_color_sample_counts
+            .contains(supported)
+    );
+    assert!(
+        properties
+            .framebuffer_depth_sample_counts
+            .contains(supported)
+    );
+}
+
+#[test]
+fn context_with_api_version_1_2() {
+    match VulkanContext::builder().api_version(Version::V1_2).build() {
+        Ok(context) => {
+            assert!(
+                context.instance.api_version() >= Version::V1_2,
+                "Instance should report at least the requested API version"
+            );
+        }
+        Err(GammaVkError::LibraryLoad(_)) => {
+            eprintln!("Skipping test: Vulkan not available (expected in CI)");
+        }
+        Err(GammaVkError::InstanceCreation(message)) => {
+            eprintln!("Skipping test: Vulkan 1.2 not supported: {}", message);
+        }
+        Err(e) => panic!("Unexpected error creating VulkanContext: {}", e),
+    }
+}
+
+#[test]
+fn diagnose_reports_stages_in_order() {
+    let diagnostics = VulkanContext::diagnose();
+    let stages = diagnostics.stages();
+
+    assert!(!stages.is_empty(), "Library loading is always attempted");
+    assert_eq!(stages[0].stage, DiagnosticStage::LibraryLoad);
+    assert!(
+        stages.windows(2).all(|pair| pair[0].stage < pair[1].stage),
+        "Stages should be reported in creation order"
+    );
+
+    // Only the last attempted stage may fail
+    if let Some(failure) = diagnostics.failure() {
+        assert_eq!(Some(failure.stage), diagnostics.final_stage());
+        assert!(!failure.message.is_empty());
+        assert!(!diagnostics.is_healthy());
+    }
+}
+
+#[test]
+fn diagnose_healthy_system_reaches_device_creation() {
+    let Some(_context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let diagnostics = VulkanContext::diagnose();
+    assert!(
+        diagnostics.stages().iter().all(|report| report.success),
+        "All stages should succeed when a context can be created:\n{}",
+        diagnostics
+    );
+    assert_eq!(
+        diagnostics.final_stage(),
+        Some(DiagnosticStage::DeviceCreation)
+    );
+    assert!(diagnostics.is_healthy());
+}
+
+#[test]
+fn allocation_stats_track_live_buffers() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let allocator = context.memory_allocator();
+    let baseline = context.allocation_stats();
+
+    let buffers: Vec<_> = (0..4)
+        .map(|_| {
+            gamma_vk::Buffer::new_host_visible(
+                &context.device(),
+                &allocator,
+                256,
+                vulkano::buffer::BufferUsage::VERTEX_BUFFER,
+            )
+            .expect("Failed to create buffer")
+        })
+        .collect();
+
+    let with_buffers = context.allocation_stats();
+    assert_eq!(with_buffers.live_allocations, baseline.live_allocations + 4);
+    assert!(with_buffers.allocated_bytes >= baseline.allocated_bytes + 4 * 256);
+    assert!(with_buffers.reserved_bytes >= with_buffers.allocated_bytes);
+
+    drop(buffers);
+    assert_eq!(
+        context.allocation_stats().live_allocations,
+        baseline.live_allocations
+    );
+}
+
+#[test]
+fn command_buffer_allocator_is_shared_and_bound_to_device() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let first = context.command_buffer_allocator();
+    let second = context.command_buffer_allocator();
+    assert!(
+        Arc::ptr_eq(&first, &second),
+        "Command buffer allocator should be created once and shared"
+    );
+    assert_eq!(first.device(), &context.device());
+}
+
+#[test]
+fn descriptor_set_allocator_is_shared_and_bound_to_device() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let first = context.descriptor_set_allocator();
+    let second = context.descriptor_set_allocator();
+    assert!(
+        Arc::ptr_eq(&first, &second),
+        "Descriptor set allocator should be created once and shared"
+    );
+    assert_eq!(first.device(), &context.device());
+}
+
+#[test]
+fn frame_context_cycles_without_deadlock_and_reuses_fences() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let mut frames = context.frames(2).expect("Failed to create frame context");
+    assert_eq!(frames.frame_count(), 2);
+
+    let mut fences_seen = Vec::new();
+    for iteration in 0..6 {
+        let frame = frames.begin_frame().expect("Failed to begin frame");
+        assert_eq!(frame.index(), iteration % 2);
+        let fence = frame.fence().clone();
+        if !fences_seen.iter().any(|seen| Arc::ptr_eq(seen, &fence)) {
+            fences_seen.push(fence);
+        }
+
+        let recorder = gamma_vk::CommandRecorder::new(&context).expect("Failed to create recorder");
+        recorder
+            .submit_and_wait(&context.graphics_queue())
+            .expect("Failed to submit frame work");
+        frames.end_frame(frame).expect("Failed to end frame");
+    }
+
+    assert_eq!(fences_seen.len(), 2, "Each slot should reuse one fence");
+    frames.wait_idle().expect("Failed to wait for frames");
+}
+
+#[test]
+fn frame_context_rejects_zero_frames_and_stale_guards() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    assert!(context.frames(0).is_err());
+
+    let mut frames = context.frames(2).expect("Failed to create frame context");
+    // Beginning twice hands out the current slot again; once that slot is ended
+    // the duplicate guard no longer matches the current frame
+    let frame = frames.begin_frame().expect("Failed to begin frame");
+    let duplicate = frames.begin_frame().expect("Failed to begin frame");
+    assert_eq!(frame.index(), duplicate.index());
+    frames.end_frame(frame).expect("Failed to end frame");
+    assert!(frames.end_frame(duplicate).is_err());
+}
+
+#[test]
+fn min_device_local_memory_rejects_every_device_when_impossible() {
+    match VulkanContext::builder()
+        .min_device_local_memory(u64::MAX)
+        .build()
+    {
+        Err(GammaVkError::Initialization { message }) => {
+            assert!(
+                message.contains(&format!("at least {} bytes", u64::MAX)),
+                "{}",
+                message
+            );
+            assert!(message.contains("candidates"), "{}", message);
+        }
+        Err(GammaVkError::LibraryLoad(_)) => {
+            eprintln!("Skipping test: Vulkan not available (expected in CI)");
+        }
+        Err(e) => panic!("Expected a device memory error, got: {}", e),
+        Ok(_) => panic!("No device has u64::MAX bytes of device-local memory"),
+    }
+}
+
+#[test]
+fn context_is_send_and_sync() {
+    // Required to store the context as an ECS world resource shared by systems
+    fn assert_send_sync<T: Send + Sync + 'static>() {}
+    assert_send_sync::<VulkanContext>();
+}
+
 #[test]
 fn context_builder_pattern_works() {
     // Try to create context with builder
@@ -355,6 +691,374 @@ fn context_builder_with_minimal_config() {
     }
 }
 
+#[test]
+fn context_with_max_queue_priority_builds() {
+    match VulkanContext::builder().queue_priority(1.0).build() {
+        Ok(context) => {
+            assert_eq!(context.queue_priority(), 1.0);
+        }
+        Err(GammaVkError::LibraryLoad(_)) => {
+            eprintln!("Skipping test: Vulkan not available (expected in CI)");
+        }
+        Err(e) => {
+            panic!("Builder with queue priority 1.0 should work: {}", e);
+        }
+    }
+}
+
+#[test]
+fn queue_priority_outside_unit_range_is_rejected() {
+    // Validated before the Vulkan library is loaded, so this runs everywhere
+    for priority in [-0.1, 1.5, f32::NAN] {
+        match VulkanContext::builder().queue_priority(priority).build() {
+            Err(GammaVkError::Initialization { message }) => {
+                assert!(message.contains("Queue priority"), "{}", message);
+            }
+            Err(e) => panic!("Expected a queue priority error, got: {}", e),
+            Ok(_) => panic!("Queue priority {} should be rejected", priority),
+        }
+    }
+}
+
+#[test]
+fn context_with_custom_allocator_block_size_allocates_buffers() {
+    // Small blocks suit apps that make few, tiny allocations
+    match VulkanContext::builder()
+        .allocator_block_size(4 * 1024 * 1024)
+        .build()
+    {
+        Ok(context) => {
+            let buffer = gamma_vk::Buffer::new_host_visible(
+                &context.device(),
+                &context.memory_allocator(),
+                1024,
+                vulkano::buffer::BufferUsage::TRANSFER_DST,
+            )
+            .expect("Custom block size allocator should allocate buffers");
+            assert_eq!(buffer.size(), 1024);
+        }
+        Err(GammaVkError::LibraryLoad(_)) => {
+            eprintln!("Skipping test: Vulkan not available (expected in CI)");
+        }
+        Err(e) => {
+            panic!("Builder with custom block size should work: {}", e);
+        }
+    }
+}
+
+#[test]
+fn context_with_portability_never_disables_portability_enumeration() {
+    match VulkanContext::builder()
+        .portability(PortabilityMode::Never)
+        .build()
+    {
+        Ok(context) => {
+            assert!(
+                !context.enabled_extensions().khr_portability_enumeration,
+                "PortabilityMode::Never should not enable portability enumeration"
+            );
+        }
+        Err(GammaVkError::LibraryLoad(_)) => {
+            eprintln!("Skipping test: Vulkan not available (expected in CI)");
+        }
+        Err(e) if is_missing_device(&e) => {
+            eprintln!("Skipping test: {}", e);
+        }
+        Err(GammaVkError::InstanceCreation(message)) if cfg!(target_os = "macos") => {
+            // MoltenVK-only systems have no standard Vulkan path
+            eprintln!(
+                "Skipping test: standard Vulkan instance unavailable: {}",
+                message
+            );
+        }
+        Err(e) => panic!("PortabilityMode::Never context creation failed: {}", e),
+    }
+}
+
+#[test]
+fn portability_mode_auto_is_enabled_only_on_macos() {
+    assert_eq!(PortabilityMode::default(), PortabilityMode::Auto);
+    assert_eq!(
+        PortabilityMode::Auto.is_enabled(),
+        cfg!(target_os = "macos")
+    );
+    assert!(PortabilityMode::Always.is_enabled());
+    assert!(!PortabilityMode::Never.is_enabled());
+}
+
+#[test]
+fn common_format_supports_sampling() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let format = vulkano::format::Format::R8G8B8A8_UNORM;
+    let properties = context
+        .format_properties(format)
+        .expect("Core formats should be queryable");
+
+    // Sampling R8G8B8A8_UNORM with optimal tiling is mandatory in the Vulkan spec
+    assert!(
+        properties
+            .optimal_tiling_features
+            .contains(vulkano::format::FormatFeatures::SAMPLED_IMAGE)
+    );
+    assert!(context.supports_sampling(format));
+    assert!(context.supports_color_attachment(format));
+}
+
+#[test]
+fn queue_families_report_graphics_family() {
+    let Some(context) = skip_if_no_vulkan() else {
+        return;
+    };
+
+    let families = context.queue_families();
+    assert!(!families.is_empty(), "Device should report queue families");
+    assert!(
+        families.iter().any(|family| family.supports_graphics()),
+        "At least one queue family should support graphics"
+    );
+
+    let graphics_family = families
+        .iter()
+        .find(|family| family.index == context.graphics_queue_family_index())
+        .expect("Selected graphics family should be reported");
+    assert!(graphics_family.supports_graphics());
+    assert!(graphics_family.queue_count > 0);
+}
+
+#[test]
+fn test_staging_buffer_is_reused_across_uploads() {
+    let context = match VulkanContext::new() {
+        Ok(ctx) => ctx,
+        Err(GammaVkError::LibraryLoad(_)) => {
+            eprintln!("Skipping test: Vulkan not available (expected in CI)");
+            return;
+        }
+        Err(e) => panic!("Unexpected error creating VulkanContext: {}", e),
+    };
+
+    assert_eq!(context.staging_capacity(), 0);
+
+    let first = context
+        .with_staging(1024, |staging| {
+            staging.write_data(&[1u8; 1024])?;
+            Ok(staging.inner().buffer().clone())
+        })
+        .expect("First staging upload failed");
+    assert_eq!(context.staging_capacity(), 1024);
+
+    // Smaller uploads reuse the existing allocation
+    let second = context
+        .with_staging(256, |staging| {
+            staging.write_data(&[2u8; 256])?;
+            Ok(staging.inner().buffer().clone())
+        })
+        .expect("Second staging upload failed");
+    assert_eq!(context.staging_capacity(), 1024);
+    assert!(Arc::ptr_eq(&first, &second));
+
+    // Larger uploads grow the staging buffer
+    context
+        .with_staging(4096, |staging| staging.write_data(&[3u8; 4096]))
+        .expect("Growing staging upload failed");
+    assert_eq!(context.staging_capacity(), 4096);
+}
+
+#[test]
+fn test_zero_size_staging_fails_after_allocation() {
+    let context = match VulkanContext::new() {
+        Ok(ctx) => ctx,
+        Err(GammaVkError::LibraryLoad(_)) => {
+            eprintln!("Skipping test: Vulkan not available (expected in CI)");
+            return;
+        }
+        Err(e) => panic!("Unexpected error creating VulkanContext: {}", e),
+    };
+
+    context
+        .with_staging(64, |staging| staging.write_data(&[1u8; 64]))
+        .expect("Staging upload failed");
+
+    // An existing staging buffer must not make an empty request succeed
+    let result = context.with_staging(0, |_| Ok(()));
+    assert!(matches!(result, Err(GammaVkError::BufferCreation { .. })));
+    assert_eq!(context.staging_capacity(), 64);
+}
+
+#[test]
+fn context_without_surface_has_no_present_queue() {
+    match VulkanContext::new() {
+        Ok(context) => {
+            assert!(context.present_queue().is_none());
+            assert!(context.present_queue_family_index().is_none());
+        }
+        Err(GammaVkError::LibraryLoad(_)) => {
+            eprintln!("Skipping test: Vulkan not available (expected in CI)");
+        }
+        Err(e) => panic!("Unexpected error: {}", e),
+    }
+}
+
+#[cfg(feature = "windowing")]
+mod windowing {
+    use super::*;
+    use winit::raw_window_handle::{
+        DisplayHandle, HandleError, HasDisplayHandle, HasWindowHandle, RawDisplayHandle,
+        RawWindowHandle, WindowHandle, XlibDisplayHandle, XlibWindowHandle,
+    };
+
+    /// A fake Xlib window; context creation only inspects the platform, not the window
+    struct FakeXlibWindow;
+
+    impl HasWindowHandle for FakeXlibWindow {
+        fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+            let raw = RawWindowHandle::Xlib(XlibWindowHandle::new(1));
+            Ok(unsafe { WindowHandle::borrow_raw(raw) })
+        }
+    }
+
+    impl HasDisplayHandle for FakeXlibWindow {
+        fn display_handle(&self) -> Result<DisplayHandle<'_>, HandleError> {
+            let raw = RawDisplayHandle::Xlib(XlibDisplayHandle::new(None, 0));
+            Ok(unsafe { DisplayHandle::borrow_raw(raw) })
+        }
+    }
+
+    /// Whether the Vulkan library supports `required`; logs why the test is skipped otherwise
+    fn supports_instance_extensions(required: &vulkano::instance::InstanceExtensions) -> bool {
+        let Ok(library) = vulkano::VulkanLibrary::new() else {
+            eprintln!("Skipping test: Vulkan not available (expected in CI)");
+            return false;
+        };
+        if !library.supported_extensions().contains(required) {
+            eprintln!(
+                "Skipping test: instance extensions {:?} unsupported",
+                required
+            );
+            return false;
+        }
+        true
+    }
+
+    #[test]
+    fn context_for_window_enables_surface_extensions() {
+        let required = vulkano::swapchain::Surface::required_extensions(&FakeXlibWindow)
+            .expect("Failed to query Xlib surface extensions");
+        if !supports_instance_extensions(&required) {
+            return;
+        }
+
+        match VulkanContext::for_window(&FakeXlibWindow) {
+            Ok(context) => {
+                let extensions = context.enabled_extensions();
+                assert!(extensions.khr_surface);
+                assert!(extensions.khr_xlib_surface);
+                assert!(context.device().enabled_extensions().khr_swapchain);
+            }
+            Err(GammaVkError::LibraryLoad(_)) => {
+                eprintln!("Skipping test: Vulkan not available (expected in CI)");
+            }
+            Err(e) if is_missing_device(&e) => {
+                eprintln!("Skipping test: {}", e);
+            }
+            Err(e) => panic!("Unexpected error creating VulkanContext for window: {}", e),
+        }
+    }
+
+    #[test]
+    fn context_for_surface_selects_present_capable_family() {
+        use vulkano::instance::InstanceExtensions;
+        use vulkano::swapchain::Surface;
+
+        let extensions = InstanceExtensions {
+            khr_surface: true,
+            ext_headless_surface: true,
+            ..InstanceExtensions::empty()
+        };
+        if !supports_instance_extensions(&extensions) {
+            return;
+        }
+        let mut surface = None;
+        let result = VulkanContext::builder().build_with_surface(extensions, |instance| {
+            let headless = Surface::headless(instance, None)
+                .map_err(|e| GammaVkError::initialization(e.to_string()))?;
+            surface = Some(headless.clone());
+            Ok(headless)
+        });
+
+        match result {
+            Ok(context) => {
+                let surface = surface.expect("Surface factory was not called");
+                let present_family = context
+                    .present_queue_family_index()
+                    .expect("Context built for a surface has a present queue");
+                assert!(
+                    context
+                        .physical_device()
+                        .surface_support(present_family, &surface)
+                        .unwrap()
+                );
+                assert_eq!(
+                    context.present_queue().unwrap().queue_family_index(),
+                    present_family
+                );
+                assert!(context.device().enabled_extensions().khr_swapchain);
+            }
+            Err(GammaVkError::LibraryLoad(_)) => {
+                eprintln!("Skipping test: Vulkan not available (expected in CI)");
+            }
+            Err(e) if is_missing_device(&e) => {
+                eprintln!("Skipping test: {}", e);
+            }
+            Err(e) => panic!("Unexpected error creating VulkanContext: {}", e),
+        }
+    }
+
+    #[test]
+    fn surface_capabilities_report_formats_and_fifo() {
+        use vulkano::instance::InstanceExtensions;
+        use vulkano::swapchain::{PresentMode, Surface};
+
+        let extensions = InstanceExtensions {
+            khr_surface: true,
+            ext_headless_surface: true,
+            ..InstanceExtensions::empty()
+        };
+        if !supports_instance_extensions(&extensions) {
+            return;
+        }
+        let mut surface = None;
+        let result = VulkanContext::builder().build_with_surface(extensions, |instance| {
+            let headless = Surface::headless(instance, None)
+                .map_err(|e| GammaVkError::initialization(e.to_string()))?;
+            surface = Some(headless.clone());
+            Ok(headless)
+        });
+
+        match result {
+            Ok(context) => {
+                let surface = surface.expect("Surface factory was not called");
+                let info = context
+                    .surface_capabilities(&surface)
+                    .expect("Failed to query surface capabilities");
+                assert!(!info.formats.is_empty());
+                assert!(info.present_modes.contains(&PresentMode::Fifo));
+                assert!(info.min_image_count >= 1);
+                assert!(info.preferred_format().is_some());
+            }
+            Err(GammaVkError::LibraryLoad(_)) => {
+                eprintln!("Skipping test: Vulkan not available (expected in CI)");
+            }
+            Err(e) if is_missing_device(&e) => {
+                eprintln!("Skipping test: {}", e);
+            }
+            Err(e) => panic!("Unexpected error creating VulkanContext: {}", e),
+        }
+    }
+}
+
 /*
 #[test]
 fn context_prefers_discrete_gpu() {
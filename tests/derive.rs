@@ -0,0 +1,37 @@
+//! Tests for the `derive` feature
+//!
+//! Run with `cargo test --features derive`. Derived components must work through
+//! the public `gamma_vk::ecs` paths exactly like hand-written impls.
+
+use gamma_vk::ecs::{Component, World};
+
+#[derive(Debug, PartialEq, Component)]
+struct Health(u32);
+
+#[derive(Debug, PartialEq, Component)]
+struct Tagged<T> {
+    tag: T,
+}
+
+#[test]
+fn test_derived_components_usable_in_world() {
+    let mut world: World = World::new().unwrap();
+    let entity = world
+        .spawn()
+        .with(Health(100))
+        .with(Tagged { tag: "player" })
+        .build();
+
+    assert_eq!(world.get::<Health>(entity), Some(&Health(100)));
+    assert_eq!(
+        world.get::<Tagged<&'static str>>(entity),
+        Some(&Tagged { tag: "player" })
+    );
+}
+
+#[test]
+fn test_derived_component_is_a_component() {
+    fn assert_component<C: Component>() {}
+    assert_component::<Health>();
+    assert_component::<Tagged<u8>>();
+}
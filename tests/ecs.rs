@@ -0,0 +1,127 @@
+//! Tests for running ECS systems against GPU resources
+//!
+//! These tests require a real Vulkan device and skip when one is unavailable.
+
+use gamma_vk::{
+    CommandRecorder, DrawDescriptor, GammaVkError, Image, VertexBuffer, VulkanContext,
+    ecs::{Component, World},
+    pipeline,
+    render::{Framebuffer, RenderPass},
+    shader,
+};
+use vulkano::{
+    command_buffer::{RenderPassBeginInfo, SubpassBeginInfo, SubpassContents, SubpassEndInfo},
+    format::Format,
+    pipeline::graphics::viewport::Viewport,
+};
+
+fn create_test_context() -> Option<VulkanContext> {
+    match VulkanContext::new() {
+        Ok(context) => Some(context),
+        Err(GammaVkError::LibraryLoad(_)) => {
+            eprintln!("Skipping test: Vulkan not available (expected in CI)");
+            None
+        }
+        Err(e) => panic!("Unexpected error creating VulkanContext: {}", e),
+    }
+}
+
+struct Transform {
+    x: f32,
+}
+impl Component for Transform {}
+
+struct MeshHandle {
+    vertices: VertexBuffer,
+    vertex_stride: u32,
+}
+impl Component for MeshHandle {}
+
+#[test]
+fn test_render_system_draws_meshes_from_world_resources() {
+    let Some(context) = create_test_context() else {
+        return;
+    };
+
+    let extent = [64, 32];
+    let render_pass = RenderPass::new(&context, [Format::R8G8B8A8_UNORM], None)
+        .expect("Failed to create render pass");
+    let target = Image::new_render_target(&context, Format::R8G8B8A8_UNORM, extent)
+        .expect("Failed to create render target");
+    let framebuffer = Framebuffer::new(&render_pass, &[target.view().clone()])
+        .expect("Failed to create framebuffer");
+    let fragment_shader = shader::common::load_triangle_fragment(&context.device())
+        .expect("Failed to load embedded fragment shader");
+    let pipeline = pipeline::fullscreen(&context, &fragment_shader, render_pass.inner())
+        .expect("Failed to build pipeline");
+
+    let mut world: World = World::new().unwrap();
+    for x in [0.0, 1.0] {
+        let vertices =
+            VertexBuffer::new_host_visible(&context.device(), &context.memory_allocator(), 36)
+                .expect("Failed to create vertex buffer");
+        world
+            .spawn()
+            .with(Transform { x })
+            .with(MeshHandle {
+                vertices,
+                vertex_stride: 12,
+            })
+            .build();
+    }
+    // Not drawn: the render system only visits entities with both components
+    world.spawn().with(Transform { x: 2.0 }).build();
+    world.insert_resource(context);
+
+    let mut drawn = Vec::new();
+    world.run(|world: &mut World| {
+        let context = world
+            .get_resource::<VulkanContext>()
+            .expect("context resource");
+        let mut recorder = CommandRecorder::new(context).expect("Failed to create recorder");
+        recorder
+            .builder_mut()
+            .begin_render_pass(
+                RenderPassBeginInfo {
+                    clear_values: vec![Some([0.0, 0.0, 0.0, 1.0].into())],
+                    ..RenderPassBeginInfo::framebuffer(framebuffer.inner().clone())
+                },
+                SubpassBeginInfo {
+                    contents: SubpassContents::Inline,
+                    ..Default::default()
+                },
+            )
+            .expect("Failed to begin render pass")
+            .set_viewport(
+                0,
+                [Viewport {
+                    offset: [0.0, 0.0],
+                    extent: [extent[0] as f32, extent[1] as f32],
+                    depth_range: 0.0..=1.0,
+                }]
+                .into_iter()
+                .collect(),
+            )
+            .expect("Failed to set viewport");
+
+        for (_, (transform, mesh)) in world.query2::<Transform, MeshHandle>() {
+            let draw = DrawDescriptor::new(&mesh.vertices, mesh.vertex_stride)
+                .expect("Invalid vertex stride");
+            recorder
+                .draw_mesh(&pipeline, None, &draw)
+                .expect("Failed to record draw");
+            drawn.push(transform.x);
+        }
+
+        recorder
+            .builder_mut()
+            .end_render_pass(SubpassEndInfo::default())
+            .expect("Failed to end render pass");
+        recorder
+            .submit_and_wait(&context.graphics_queue())
+            .expect("Failed to submit render system commands");
+    });
+
+    drawn.sort_by(f32::total_cmp);
+    assert_eq!(drawn, [0.0, 1.0]);
+}
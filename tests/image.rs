@@ -0,0 +1,136 @@
+//! Tests for images and framebuffers
+
+use gamma_vk::{
+    GammaVkError, Image, VulkanContext,
+    image::Sampler,
+    render::{Framebuffer, RenderPass},
+};
+use vulkano::{format::Format, image::ImageUsage};
+
+fn create_test_context() -> Option<VulkanContext> {
+    match VulkanContext::new() {
+        Ok(context) => Some(context),
+        Err(GammaVkError::LibraryLoad(_)) => {
+            eprintln!("Skipping test: Vulkan not available (expected in CI)");
+            None
+        }
+        Err(e) => panic!("Unexpected error creating VulkanContext: {}", e),
+    }
+}
+
+#[test]
+fn test_render_target_has_attachment_and_sampled_usage() {
+    let Some(context) = create_test_context() else {
+        return;
+    };
+
+    let target = Image::new_render_target(&context, Format::R8G8B8A8_UNORM, [64, 32])
+        .expect("Failed to create render target");
+
+    assert_eq!(target.format(), Format::R8G8B8A8_UNORM);
+    assert_eq!(target.extent(), [64, 32]);
+    assert!(
+        target
+            .usage()
+            .contains(ImageUsage::COLOR_ATTACHMENT | ImageUsage::SAMPLED)
+    );
+}
+
+#[test]
+fn test_render_target_rejects_zero_extent() {
+    let Some(context) = create_test_context() else {
+        return;
+    };
+
+    let result = Image::new_render_target(&context, Format::R8G8B8A8_UNORM, [0, 32]);
+    assert!(matches!(result, Err(GammaVkError::ImageCreation { .. })));
+}
+
+#[test]
+fn test_framebuffer_matches_render_target() {
+    let Some(context) = create_test_context() else {
+        return;
+    };
+
+    let target = Image::new_render_target(&context, Format::R8G8B8A8_UNORM, [64, 32])
+        .expect("Failed to create render target");
+    let render_pass = RenderPass::new(&context, [Format::R8G8B8A8_UNORM], None)
+        .expect("Failed to create render pass");
+
+    let framebuffer = Framebuffer::new(&render_pass, &[target.view().clone()])
+        .expect("Failed to create framebuffer");
+    assert_eq!(framebuffer.extent(), [64, 32]);
+}
+
+#[test]
+fn test_framebuffer_rejects_format_mismatch() {
+    let Some(context) = create_test_context() else {
+        return;
+    };
+
+    let target = Image::new_render_target(&context, Format::R8G8B8A8_UNORM, [64, 32])
+        .expect("Failed to create render target");
+    let render_pass = RenderPass::new(&context, [Format::R16G16B16A16_SFLOAT], None)
+        .expect("Failed to create render pass");
+
+    let result = Framebuffer::new(&render_pass, &[target.view().clone()]);
+    assert!(matches!(result, Err(GammaVkError::PipelineCreation { .. })));
+}
+
+#[test]
+fn test_framebuffer_rejects_attachment_count_mismatch() {
+    let Some(context) = create_test_context() else {
+        return;
+    };
+
+    let target = Image::new_render_target(&context, Format::R8G8B8A8_UNORM, [64, 32])
+        .expect("Failed to create render target");
+    let render_pass = RenderPass::new(
+        &context,
+        [Format::R8G8B8A8_UNORM, Format::R8G8B8A8_UNORM],
+        None,
+    )
+    .expect("Failed to create render pass");
+
+    let result = Framebuffer::new(&render_pass, &[target.view().clone()]);
+    assert!(matches!(result, Err(GammaVkError::PipelineCreation { .. })));
+}
+
+#[test]
+fn test_linear_repeat_sampler_builds() {
+    let Some(context) = create_test_context() else {
+        return;
+    };
+
+    let sampler = Sampler::linear_repeat(&context).expect("Failed to create sampler");
+    assert_eq!(sampler.anisotropy(), None);
+}
+
+#[test]
+fn test_nearest_clamp_sampler_builds() {
+    let Some(context) = create_test_context() else {
+        return;
+    };
+
+    Sampler::nearest_clamp(&context).expect("Failed to create sampler");
+}
+
+#[test]
+fn test_anisotropic_sampler_clamps_or_falls_back() {
+    let Some(context) = create_test_context() else {
+        return;
+    };
+
+    let sampler =
+        Sampler::anisotropic(&context, 1.0e6).expect("Anisotropic sampler should not fail");
+
+    if context.supports_anisotropy() {
+        let limit = context
+            .physical_device()
+            .properties()
+            .max_sampler_anisotropy;
+        assert_eq!(sampler.anisotropy(), Some(limit));
+    } else {
+        assert_eq!(sampler.anisotropy(), None);
+    }
+}
@@ -0,0 +1,182 @@
+//! Tests for the built-in pipeline helpers
+
+use gamma_vk::{
+    GammaVkError, ShaderModule, VulkanContext, pipeline,
+    render::{DynamicRendering, RenderPass},
+};
+use vulkano::{format::Format, pipeline::DynamicState};
+
+fn create_test_context() -> Option<VulkanContext> {
+    match VulkanContext::new() {
+        Ok(context) => Some(context),
+        Err(GammaVkError::LibraryLoad(_)) => {
+            eprintln!("Skipping test: Vulkan not available (expected in CI)");
+            None
+        }
+        Err(e) => panic!("Unexpected error creating VulkanContext: {}", e),
+    }
+}
+
+#[test]
+fn test_fullscreen_pipeline_builds_with_simple_fragment_shader() {
+    let Some(context) = create_test_context() else {
+        return;
+    };
+
+    let fragment_shader =
+        match ShaderModule::from_spirv_file(&context.device(), "shaders/triangle.frag.spv") {
+            Ok(shader) => shader,
+            Err(e) => {
+                eprintln!("Skipping test: fragment shader asset missing: {}", e);
+                return;
+            }
+        };
+
+    let render_pass = vulkano::single_pass_renderpass!(
+        context.device(),
+        attachments: {
+            color: {
+                format: Format::R8G8B8A8_UNORM,
+                samples: 1,
+                load_op: Clear,
+                store_op: Store,
+            },
+        },
+        pass: {
+            color: [color],
+            depth_stencil: {},
+        },
+    )
+    .expect("Failed to create render pass");
+
+    let pipeline = pipeline::fullscreen(&context, &fragment_shader, &render_pass)
+        .expect("Failed to build fullscreen pipeline");
+
+    assert!(
+        pipeline.dynamic_state().contains(&DynamicState::Viewport),
+        "Fullscreen pipeline viewport should be dynamic"
+    );
+}
+
+#[test]
+fn test_fullscreen_pipeline_builds_with_dynamic_rendering() {
+    let context = match VulkanContext::builder().enable_dynamic_rendering().build() {
+        Ok(context) => context,
+        Err(GammaVkError::LibraryLoad(_)) => {
+            eprintln!("Skipping test: Vulkan not available (expected in CI)");
+            return;
+        }
+        Err(GammaVkError::Initialization { message }) => {
+            eprintln!("Skipping test: dynamic rendering unavailable: {}", message);
+            return;
+        }
+        Err(e) => panic!("Unexpected error creating VulkanContext: {}", e),
+    };
+    assert!(context.dynamic_rendering_enabled());
+
+    let fragment_shader = gamma_vk::shader::common::load_triangle_fragment(&context.device())
+        .expect("Failed to load embedded fragment shader");
+    let rendering = DynamicRendering::new(&context, [Format::R8G8B8A8_UNORM])
+        .expect("Dynamic rendering should be enabled");
+
+    let pipeline = pipeline::fullscreen_dynamic(&context, &fragment_shader, &rendering)
+        .expect("Failed to build dynamic-rendering fullscreen pipeline");
+
+    assert!(pipeline.dynamic_state().contains(&DynamicState::Viewport));
+    assert!(pipeline.depth_stencil_state().is_none());
+
+    // A depth attachment requires depth-stencil state in the pipeline
+    let rendering = rendering.with_depth_format(Format::D32_SFLOAT);
+    let pipeline = pipeline::fullscreen_dynamic(&context, &fragment_shader, &rendering)
+        .expect("Failed to build fullscreen pipeline with a depth attachment");
+    assert!(
+        pipeline
+            .depth_stencil_state()
+            .is_some_and(|state| state.depth.is_some())
+    );
+}
+
+#[test]
+fn test_dynamic_rendering_requires_enabled_feature() {
+    let Some(context) = create_test_context() else {
+        return;
+    };
+
+    let result = DynamicRendering::new(&context, [Format::R8G8B8A8_UNORM]);
+    assert!(
+        matches!(result, Err(GammaVkError::PipelineCreation { .. })),
+        "DynamicRendering should require enable_dynamic_rendering()"
+    );
+}
+
+fn gbuffer_render_pass(context: &VulkanContext) -> RenderPass {
+    RenderPass::new(
+        context,
+        [
+            Format::R8G8B8A8_UNORM,
+            Format::R16G16B16A16_SFLOAT,
+            Format::R32G32B32A32_SFLOAT,
+        ],
+        Some(Format::D32_SFLOAT),
+    )
+    .expect("Failed to create G-buffer render pass")
+}
+
+#[test]
+fn test_render_pass_with_multiple_color_attachments() {
+    let Some(context) = create_test_context() else {
+        return;
+    };
+
+    let render_pass = gbuffer_render_pass(&context);
+
+    assert_eq!(render_pass.color_attachment_count(), 3);
+    assert_eq!(render_pass.depth_format(), Some(Format::D32_SFLOAT));
+    let subpass = vulkano::render_pass::Subpass::from(render_pass.inner().clone(), 0)
+        .expect("Render pass should have one subpass");
+    assert_eq!(subpass.num_color_attachments(), 3);
+
+    let fragment_shader =
+        match ShaderModule::from_spirv_file(&context.device(), "shaders/gbuffer.frag.spv") {
+            Ok(shader) => shader,
+            Err(e) => {
+                eprintln!(
+                    "Skipping pipeline check: G-buffer shader asset missing: {}",
+                    e
+                );
+                return;
+            }
+        };
+    pipeline::fullscreen(&context, &fragment_shader, render_pass.inner())
+        .expect("Failed to build pipeline writing three color attachments");
+}
+
+#[test]
+fn test_fullscreen_rejects_mismatched_color_output_count() {
+    let Some(context) = create_test_context() else {
+        return;
+    };
+
+    let render_pass = gbuffer_render_pass(&context);
+    let fragment_shader = gamma_vk::shader::common::load_triangle_fragment(&context.device())
+        .expect("Failed to load embedded fragment shader");
+
+    let result = pipeline::fullscreen(&context, &fragment_shader, render_pass.inner());
+    match result {
+        Err(GammaVkError::PipelineCreation { message }) => {
+            assert!(message.contains("1 color outputs"), "{}", message);
+            assert!(message.contains("3 color attachments"), "{}", message);
+        }
+        other => panic!("Expected a color output mismatch error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_render_pass_requires_an_attachment() {
+    let Some(context) = create_test_context() else {
+        return;
+    };
+
+    let result = RenderPass::new(&context, [], None);
+    assert!(matches!(result, Err(GammaVkError::PipelineCreation { .. })));
+}
@@ -74,6 +74,7 @@ mod spirv_validation {
     }
     
     #[test]
+    #[cfg(feature = "checked")]
     fn test_invalid_spirv_magic_number() {
         let Some(context) = create_test_context() else { return };
         
@@ -110,6 +111,7 @@ mod spirv_validation {
     }
     
     #[test]
+    #[cfg(feature = "checked")]
     fn test_spirv_bytecode_too_short() {
         let Some(context) = create_test_context() else { return };
         
@@ -131,6 +133,7 @@ mod spirv_validation {
     }
     
     #[test]
+    #[cfg(feature = "checked")]
     fn test_spirv_bytecode_misaligned() {
         let Some(context) = create_test_context() else { return };
         
@@ -152,6 +155,7 @@ mod spirv_validation {
     }
     
     #[test]
+    #[cfg(feature = "checked")]
     fn test_empty_spirv_bytecode() {
         let Some(context) = create_test_context() else { return };
         
@@ -170,6 +174,26 @@ mod spirv_validation {
             _ => panic!("Expected ShaderCompilation error"),
         }
     }
+    
+    #[test]
+    fn test_spirv_exceeding_size_limit_rejected() {
+        let Some(context) = create_test_context() else { return };
+        
+        // Otherwise valid header, but larger than the configured cap
+        let oversized = minimal_spirv_header();
+        let result = ShaderModule::from_spirv_bytes_with_limit(&context.device(), &oversized, 16);
+        
+        match result {
+            Err(GammaVkError::ShaderCompilation { message }) => {
+                assert!(
+                    message.contains("SPIR-V too large: 20 bytes exceeds limit 16"),
+                    "Expected size limit error, got: {}",
+                    message
+                );
+            }
+            _ => panic!("Expected ShaderCompilation error"),
+        }
+    }
 }
 
 // Integration Tests - File Loading
@@ -218,6 +242,37 @@ mod file_loading {
         }
     }
     
+    #[test]
+    fn test_from_spirv_reader_cursor() {
+        let Some(context) = create_test_context() else { return };
+        let Some(bytes) = load_test_shader_bytes() else { return };
+        
+        let shader = ShaderModule::from_spirv_reader(&context.device(), std::io::Cursor::new(bytes))
+            .expect("Failed to load shader from in-memory reader");
+        
+        assert_eq!(shader.stage(), Some(gamma_vk::ShaderStage::Vertex));
+    }
+    
+    #[test]
+    fn test_from_spirv_reader_io_error() {
+        let Some(context) = create_test_context() else { return };
+        
+        struct FailingReader;
+        impl std::io::Read for FailingReader {
+            fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("pak entry corrupted"))
+            }
+        }
+        
+        match ShaderModule::from_spirv_reader(&context.device(), FailingReader) {
+            Err(GammaVkError::ShaderCompilation { message }) => {
+                assert!(message.contains("Failed to read shader source"), "got: {}", message);
+                assert!(message.contains("pak entry corrupted"), "got: {}", message);
+            }
+            other => panic!("Expected ShaderCompilation error, got {:?}", other.map(|_| ())),
+        }
+    }
+    
     #[test]
     fn test_shader_files_have_valid_spirv() {
         // Validate shader files if they exist
@@ -323,6 +378,7 @@ mod resource_management {
 }
 
 // Error Handling Tests
+#[cfg(feature = "checked")]
 mod error_handling {
     use super::*;
     use super::helpers::*;
@@ -388,13 +444,22 @@ mod common_shaders {
     fn test_load_triangle_vertex_shader() {
         let Some(context) = create_test_context() else { return };
         
-        match gamma_vk::shader::common::load_triangle_vertex(&context.device()) {
+        // Embedded at compile time, so this must not depend on the working directory
+        let shader = gamma_vk::shader::common::load_triangle_vertex(&context.device())
+            .expect("Embedded vertex shader should always load");
+        assert_eq!(shader.stage(), Some(gamma_vk::ShaderStage::Vertex));
+    }
+    
+    #[test]
+    fn test_vertex_stage_inferred_from_spirv() {
+        let Some(context) = create_test_context() else { return };
+        
+        match gamma_vk::ShaderModule::from_spirv_file(&context.device(), "shaders/triangle.vert.spv") {
             Ok(shader) => {
-                let _module = shader.vulkano_module();
-                println!("Successfully loaded common vertex shader");
+                assert_eq!(shader.stage(), Some(gamma_vk::ShaderStage::Vertex));
             }
             Err(_) => {
-                println!("Common vertex shader not available - this is expected if shaders/triangle.vert.spv doesn't exist");
+                println!("Vertex shader not available - skipping stage inference test");
             }
         }
     }
@@ -403,16 +468,109 @@ mod common_shaders {
     fn test_load_triangle_fragment_shader() {
         let Some(context) = create_test_context() else { return };
         
-        match gamma_vk::shader::common::load_triangle_fragment(&context.device()) {
+        let shader = gamma_vk::shader::common::load_triangle_fragment(&context.device())
+            .expect("Embedded fragment shader should always load");
+        assert_eq!(shader.stage(), Some(gamma_vk::ShaderStage::Fragment));
+    }
+    
+    #[test]
+    fn test_embedded_triangle_shaders_are_spirv() {
+        use gamma_vk::shader::common::{TRIANGLE_FRAGMENT_SPIRV, TRIANGLE_VERTEX_SPIRV};
+        
+        // No device or filesystem access needed
+        for spirv in [TRIANGLE_VERTEX_SPIRV, TRIANGLE_FRAGMENT_SPIRV] {
+            assert!(spirv.len().is_multiple_of(4));
+            assert_eq!(&spirv[..4], &[0x03, 0x02, 0x23, 0x07]);
+        }
+    }
+    
+    #[test]
+    fn test_source_language_detected_from_glsl() {
+        let Some(context) = create_test_context() else { return };
+        
+        // triangle.vert.spv is compiled from GLSL and records it in OpSource
+        match gamma_vk::ShaderModule::from_spirv_file(&context.device(), "shaders/triangle.vert.spv") {
             Ok(shader) => {
-                let _module = shader.vulkano_module();
-                println!("Successfully loaded common fragment shader");
+                assert_eq!(shader.source_language(), Some(gamma_vk::SourceLanguage::Glsl));
             }
             Err(_) => {
-                println!("Common fragment shader not available - this is expected if shaders/triangle.frag.spv doesn't exist");
+                println!("Vertex shader not available - skipping source language test");
             }
         }
     }
+    
+    #[test]
+    fn test_source_language_absent_without_opsource() {
+        let Some(context) = create_test_context() else { return };
+        
+        // The built-in fullscreen shader is emitted without debug instructions
+        let shader = gamma_vk::ShaderModule::from_spirv_bytes(
+            &context.device(),
+            gamma_vk::pipeline::FULLSCREEN_VERTEX_SPIRV,
+        )
+        .expect("Embedded fullscreen shader should always load");
+        assert_eq!(shader.source_language(), None);
+    }
+    
+    #[test]
+    fn test_color_output_count_reflects_fragment_outputs() {
+        let Some(context) = create_test_context() else { return };
+        
+        let device = context.device();
+        let (Ok(gbuffer), Ok(triangle)) = (
+            gamma_vk::ShaderModule::from_spirv_file(&device, "shaders/gbuffer.frag.spv"),
+            gamma_vk::ShaderModule::from_spirv_file(&device, "shaders/triangle.frag.spv"),
+        ) else {
+            println!("Fragment shaders not available - skipping color output test");
+            return;
+        };
+        assert_eq!(gbuffer.color_output_count(), Some(3));
+        assert_eq!(triangle.color_output_count(), Some(1));
+        
+        // Only fragment entry points report color outputs
+        let vertex = gamma_vk::ShaderModule::from_spirv_bytes(
+            &device,
+            gamma_vk::pipeline::FULLSCREEN_VERTEX_SPIRV,
+        )
+        .expect("Embedded fullscreen shader should always load");
+        assert_eq!(vertex.color_output_count(), None);
+    }
+    
+    #[test]
+    fn test_push_constant_size_reflected() {
+        let Some(context) = create_test_context() else { return };
+        
+        let device = context.device();
+        let Ok(tinted) = gamma_vk::ShaderModule::from_spirv_file(&device, "shaders/tinted.frag.spv") else {
+            println!("Push-constant shader not available - skipping reflection test");
+            return;
+        };
+        assert_eq!(tinted.push_constant_size(), Some(192));
+        
+        let triangle = gamma_vk::shader::common::load_triangle_fragment(&device)
+            .expect("Embedded triangle shader should always load");
+        assert_eq!(triangle.push_constant_size(), None);
+    }
+    
+    #[test]
+    fn test_content_hash_identifies_identical_spirv() {
+        let Some(context) = create_test_context() else { return };
+        
+        let device = context.device();
+        let from_bytes = gamma_vk::ShaderModule::from_spirv_bytes(&device, gamma_vk::shader::common::TRIANGLE_VERTEX_SPIRV)
+            .expect("Embedded triangle shader should always load");
+        let Ok(from_file) = gamma_vk::ShaderModule::from_spirv_file(&device, "shaders/triangle.vert.spv") else {
+            println!("Triangle shader file not available - skipping content hash test");
+            return;
+        };
+        assert_eq!(from_bytes.content_hash(), from_file.content_hash());
+        assert_eq!(from_bytes, from_file);
+        
+        let fragment = gamma_vk::shader::common::load_triangle_fragment(&device)
+            .expect("Embedded triangle shader should always load");
+        assert_ne!(from_bytes.content_hash(), fragment.content_hash());
+        assert_ne!(from_bytes, fragment);
+    }
 }
 
 // Debug implementation tests